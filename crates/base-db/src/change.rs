@@ -3,12 +3,13 @@
 
 use std::{fmt, sync::Arc};
 
+use fxhash::FxHashMap;
 use salsa::Durability;
 use vfs::FileId;
 
 use crate::{
     input::{SourceRoot, SourceRootId},
-    SourceDatabaseExt,
+    ContentChange, SourceDatabaseExt,
 };
 
 /// Encapsulate a bunch of raw `.set` calls on the database.
@@ -16,6 +17,7 @@ use crate::{
 pub struct Change {
     pub roots: Option<Vec<SourceRoot>>,
     pub files_changed: Vec<(FileId, Option<Arc<str>>)>,
+    pub content_changes: FxHashMap<FileId, Vec<ContentChange>>,
 }
 
 impl fmt::Debug for Change {
@@ -44,7 +46,12 @@ impl Change {
         self.files_changed.push((file_id, new_text))
     }
 
-    pub fn apply(self, db: &mut dyn SourceDatabaseExt) {
+    /// Record the changes which turned the previous text of a changed file into its new text.
+    pub fn set_content_changes(&mut self, file_id: FileId, changes: Vec<ContentChange>) {
+        self.content_changes.insert(file_id, changes);
+    }
+
+    pub fn apply(mut self, db: &mut dyn SourceDatabaseExt) {
         if let Some(roots) = self.roots {
             let mut res = Vec::new();
             for (idx, root) in roots.into_iter().enumerate() {
@@ -66,6 +73,8 @@ impl Change {
             let durability = durability(&source_root);
             // XXX: can't actually remove the file, just reset the text
             let text = text.unwrap_or_else(|| Arc::from(""));
+            let changes = self.content_changes.remove(&file_id).unwrap_or_default();
+            db.set_file_content_changes_with_durability(file_id, changes.into(), durability);
             db.set_file_text_with_durability(file_id, text, durability)
         }
     }
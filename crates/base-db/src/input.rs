@@ -55,6 +55,10 @@ impl SourceRoot {
             .resolve_path_relative_to_root(&self.root, path)
     }
 
+    pub fn root(&self) -> &VfsPath {
+        &self.root
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = FileId> + '_ {
         self.file_set.iter()
     }
@@ -1,7 +1,6 @@
 use std::{hash::Hash, sync::Arc};
 
 use include::file_includes_query;
-use input::{SourceRoot, SourceRootId};
 use syntax::utils::lsp_position_to_ts_point;
 use text_size::{TextRange, TextSize};
 use vfs::{AnchoredPath, FileId};
@@ -10,6 +9,7 @@ mod change;
 mod graph;
 mod include;
 mod input;
+mod parse_cache;
 
 pub use {
     change::Change,
@@ -18,7 +18,8 @@ pub use {
         infer_include_ext, Include, IncludeKind, IncludeType, UnresolvedInclude, RE_CHEVRON,
         RE_QUOTE,
     },
-    input::SourceRootConfig,
+    input::{SourceRoot, SourceRootConfig, SourceRootId},
+    parse_cache::{CachedTree, ContentChange, ParseCache, ParseCacheDatabase},
 };
 
 pub const DEFAULT_PARSE_LRU_CAP: usize = 128;
@@ -73,11 +74,11 @@ impl Tree {
         self.0.edit(edit);
     }
 
-    pub fn root_node(&self) -> tree_sitter::Node {
+    pub fn root_node(&self) -> tree_sitter::Node<'_> {
         self.tree().root_node()
     }
 
-    pub fn covering_element(&self, range: lsp_types::Range) -> Option<tree_sitter::Node> {
+    pub fn covering_element(&self, range: lsp_types::Range) -> Option<tree_sitter::Node<'_>> {
         let start = lsp_position_to_ts_point(&range.start);
         let end = lsp_position_to_ts_point(&range.end);
         self.root_node().descendant_for_point_range(start, end)
@@ -114,6 +115,11 @@ impl Eq for Tree {}
 /// model. Everything else in rust-analyzer is derived from these queries.
 #[salsa::query_group(SourceDatabaseStorage)]
 pub trait SourceDatabase: FileLoader + std::fmt::Debug {
+    /// Changes which turned the previous text of the file into its current text, if they are
+    /// known.
+    #[salsa::input]
+    fn file_content_changes(&self, file_id: FileId) -> Arc<[ContentChange]>;
+
     #[salsa::invoke(file_includes_query)]
     fn file_includes(&self, file_id: FileId) -> (Arc<Vec<Include>>, Arc<Vec<UnresolvedInclude>>);
 
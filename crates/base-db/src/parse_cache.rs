@@ -0,0 +1,135 @@
+//! Last syntax tree of the files being edited, used to reparse them incrementally.
+
+use std::sync::Arc;
+
+use fxhash::FxHashMap;
+use parking_lot::Mutex;
+use text_size::{TextRange, TextSize};
+use vfs::FileId;
+
+use crate::Tree;
+
+/// A change of the text of a file: the `range` of the previous text is replaced by `new_len`
+/// bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ContentChange {
+    pub range: TextRange,
+    pub new_len: TextSize,
+}
+
+impl ContentChange {
+    /// Compose a sequence of changes, each applying to the text left by the previous one, into
+    /// a single change covering all of them.
+    pub fn compose(changes: &[ContentChange]) -> Option<ContentChange> {
+        let (first, rest) = changes.split_first()?;
+        let mut start = first.range.start();
+        let mut old_end = first.range.end();
+        let mut new_end = first.range.start() + first.new_len;
+        for change in rest {
+            let change_end = change.range.start() + change.new_len;
+            if change.range.end() > new_end {
+                old_end += change.range.end() - new_end;
+            }
+            new_end = if new_end <= change.range.start() {
+                new_end
+            } else if new_end >= change.range.end() {
+                new_end - change.range.len() + change.new_len
+            } else {
+                change_end
+            };
+            new_end = new_end.max(change_end);
+            start = start.min(change.range.start());
+        }
+        Some(ContentChange {
+            range: TextRange::new(start, old_end),
+            new_len: new_end - start,
+        })
+    }
+}
+
+/// Syntax tree of a file, along with the preprocessed text it was parsed from.
+#[derive(Debug, Clone)]
+pub struct CachedTree {
+    pub text: Arc<str>,
+    pub tree: Tree,
+}
+
+/// Cache of the last syntax tree of the files, shared between the snapshots of the database.
+#[derive(Debug, Default)]
+pub struct ParseCache(Mutex<FxHashMap<FileId, CachedTree>>);
+
+// Entries are replaced as a whole, so a panic can not leave the cache in an inconsistent state.
+impl std::panic::RefUnwindSafe for ParseCache {}
+
+impl ParseCache {
+    pub fn get(&self, file_id: FileId) -> Option<CachedTree> {
+        self.0.lock().get(&file_id).cloned()
+    }
+
+    pub fn insert(&self, file_id: FileId, text: Arc<str>, tree: Tree) {
+        self.0.lock().insert(file_id, CachedTree { text, tree });
+    }
+
+    pub fn remove(&self, file_id: FileId) {
+        self.0.lock().remove(&file_id);
+    }
+}
+
+/// Database which keeps the last syntax tree of the files being edited.
+pub trait ParseCacheDatabase {
+    fn parse_cache(&self) -> &ParseCache;
+}
+
+#[cfg(test)]
+mod test {
+    use text_size::{TextRange, TextSize};
+
+    use crate::ContentChange;
+
+    fn change(start: u32, end: u32, new_len: u32) -> ContentChange {
+        ContentChange {
+            range: TextRange::new(start.into(), end.into()),
+            new_len: TextSize::new(new_len),
+        }
+    }
+
+    #[test]
+    fn test_compose_empty() {
+        assert_eq!(ContentChange::compose(&[]), None);
+    }
+
+    #[test]
+    fn test_compose_single() {
+        assert_eq!(
+            ContentChange::compose(&[change(2, 4, 3)]),
+            Some(change(2, 4, 3))
+        );
+    }
+
+    #[test]
+    fn test_compose_after() {
+        // "abcdef" -> "abXYZdef" -> "abXYZdQf"
+        assert_eq!(
+            ContentChange::compose(&[change(2, 3, 3), change(6, 7, 1)]),
+            Some(change(2, 5, 5))
+        );
+    }
+
+    #[test]
+    fn test_compose_before() {
+        // "abcdef" -> "abcdXf" -> "Yf"
+        assert_eq!(
+            ContentChange::compose(&[change(4, 5, 1), change(0, 5, 1)]),
+            Some(change(0, 5, 1))
+        );
+    }
+
+    #[test]
+    fn test_compose_overlapping() {
+        // "abcdef" -> "abXf" -> "aYZ"
+        assert_eq!(
+            ContentChange::compose(&[change(2, 5, 1), change(1, 4, 2)]),
+            Some(change(1, 6, 2))
+        );
+    }
+}
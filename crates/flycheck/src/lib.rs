@@ -402,7 +402,7 @@ impl CommandHandle {
         if read_at_least_one_message || exit_status.success() {
             Ok(())
         } else {
-            Err(io::Error::new(io::ErrorKind::Other, format!(
+            Err(io::Error::other(format!(
                 "Cargo watcher failed, the command produced no valid metadata (exit code: {exit_status:?}):\n{error}"
             )))
         }
@@ -0,0 +1,251 @@
+//! Control-flow graph of the statements of a body.
+
+use std::sync::Arc;
+
+use fxhash::FxHashSet;
+use la_arena::{Arena, ArenaMap, Idx};
+use syntax::TSKind;
+
+use crate::{
+    hir::{Expr, ExprId, Literal, LoopKind},
+    DefDatabase, DefWithBodyId,
+};
+
+use super::Body;
+
+pub type BasicBlockId = Idx<BasicBlock>;
+
+/// A sequence of statements which are executed one after the other.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct BasicBlock {
+    /// Statements of the block, in order. A statement which holds other statements, e.g. a
+    /// block or a loop, comes before them.
+    pub statements: Vec<ExprId>,
+    pub successors: Vec<BasicBlockId>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ControlFlowGraph {
+    blocks: Arena<BasicBlock>,
+    entry: BasicBlockId,
+    /// Block reached when the end of the body is reached without a `return`.
+    exit: BasicBlockId,
+    block_by_statement: ArenaMap<ExprId, BasicBlockId>,
+    reachable: FxHashSet<BasicBlockId>,
+}
+
+impl ControlFlowGraph {
+    pub(crate) fn control_flow_graph_query(
+        db: &dyn DefDatabase,
+        def: DefWithBodyId,
+    ) -> Arc<ControlFlowGraph> {
+        let body = db.body(def);
+        Arc::new(ControlFlowGraph::new(&body))
+    }
+
+    fn new(body: &Body) -> ControlFlowGraph {
+        let mut builder = CfgBuilder::new(body);
+        if let Some(body_expr) = body.body_expr {
+            builder.lower_statement(body_expr);
+        }
+        let CfgBuilder {
+            mut blocks,
+            entry,
+            exit,
+            current,
+            block_by_statement,
+            ..
+        } = builder;
+        blocks[current].successors.push(exit);
+
+        let mut reachable = FxHashSet::default();
+        let mut stack = vec![entry];
+        while let Some(block) = stack.pop() {
+            if reachable.insert(block) {
+                stack.extend(blocks[block].successors.iter().copied());
+            }
+        }
+
+        ControlFlowGraph {
+            blocks,
+            entry,
+            exit,
+            block_by_statement,
+            reachable,
+        }
+    }
+
+    pub fn blocks(&self) -> impl Iterator<Item = (BasicBlockId, &BasicBlock)> {
+        self.blocks.iter()
+    }
+
+    pub fn entry(&self) -> BasicBlockId {
+        self.entry
+    }
+
+    pub fn exit(&self) -> BasicBlockId {
+        self.exit
+    }
+
+    /// Returns the block of the statement, or [`None`] if the expression is not a statement.
+    pub fn block_of(&self, statement: ExprId) -> Option<BasicBlockId> {
+        self.block_by_statement.get(statement).copied()
+    }
+
+    /// Whether the statement can be executed. Expressions which are not statements are never
+    /// reachable.
+    pub fn is_reachable(&self, statement: ExprId) -> bool {
+        self.block_of(statement)
+            .is_some_and(|block| self.reachable.contains(&block))
+    }
+
+    /// Whether the end of the body can be reached without a `return`.
+    pub fn falls_through(&self) -> bool {
+        self.reachable.contains(&self.exit)
+    }
+}
+
+struct CfgBuilder<'a> {
+    body: &'a Body,
+    blocks: Arena<BasicBlock>,
+    entry: BasicBlockId,
+    exit: BasicBlockId,
+    /// Block the next statement is added to.
+    current: BasicBlockId,
+    /// Targets of `continue` and `break` in the loops the current statement is in, the
+    /// innermost last.
+    loops: Vec<(BasicBlockId, BasicBlockId)>,
+    block_by_statement: ArenaMap<ExprId, BasicBlockId>,
+}
+
+impl<'a> CfgBuilder<'a> {
+    fn new(body: &'a Body) -> Self {
+        let mut blocks = Arena::default();
+        let entry = blocks.alloc(BasicBlock::default());
+        let exit = blocks.alloc(BasicBlock::default());
+        Self {
+            body,
+            blocks,
+            entry,
+            exit,
+            current: entry,
+            loops: Vec::new(),
+            block_by_statement: ArenaMap::default(),
+        }
+    }
+
+    fn new_block(&mut self) -> BasicBlockId {
+        self.blocks.alloc(BasicBlock::default())
+    }
+
+    fn add_edge(&mut self, from: BasicBlockId, to: BasicBlockId) {
+        self.blocks[from].successors.push(to);
+    }
+
+    /// Adds an edge from the current block to `to`, if any, and continues in a new block.
+    fn jump(&mut self, to: Option<BasicBlockId>) {
+        if let Some(to) = to {
+            self.add_edge(self.current, to);
+        }
+        // What follows a jump is only reachable if something else jumps to it.
+        self.current = self.new_block();
+    }
+
+    fn lower_statement(&mut self, statement: ExprId) {
+        self.blocks[self.current].statements.push(statement);
+        self.block_by_statement.insert(statement, self.current);
+        match &self.body[statement] {
+            Expr::Block { statements, .. } => {
+                for statement in statements.iter() {
+                    self.lower_statement(*statement);
+                }
+            }
+            Expr::Condition {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                let before = self.current;
+                let after = self.new_block();
+                for branch in [Some(*then_branch), *else_branch] {
+                    let Some(branch) = branch else {
+                        self.add_edge(before, after);
+                        continue;
+                    };
+                    self.current = self.new_block();
+                    self.add_edge(before, self.current);
+                    self.lower_statement(branch);
+                    self.add_edge(self.current, after);
+                }
+                self.current = after;
+            }
+            Expr::Loop {
+                kind,
+                condition,
+                body,
+                ..
+            } => {
+                let header = self.new_block();
+                let body_block = self.new_block();
+                let after = self.new_block();
+                self.add_edge(
+                    self.current,
+                    if *kind == LoopKind::DoWhile {
+                        body_block
+                    } else {
+                        header
+                    },
+                );
+                self.add_edge(header, body_block);
+                if !self.is_always_true(*condition) {
+                    self.add_edge(header, after);
+                }
+                self.loops.push((header, after));
+                self.current = body_block;
+                if let Some(body) = body {
+                    self.lower_statement(*body);
+                }
+                self.add_edge(self.current, header);
+                self.loops.pop();
+                self.current = after;
+            }
+            Expr::Switch { cases, .. } => {
+                let before = self.current;
+                let after = self.new_block();
+                // The cases do not fall through.
+                for case in cases.iter() {
+                    self.current = self.new_block();
+                    self.add_edge(before, self.current);
+                    self.lower_statement(case.body());
+                    self.add_edge(self.current, after);
+                }
+                if !cases.iter().any(|case| case.is_default()) {
+                    self.add_edge(before, after);
+                }
+                self.current = after;
+            }
+            Expr::Control { keyword, .. } => match (keyword, self.loops.last().copied()) {
+                (TSKind::anon_return_, _) => self.jump(None),
+                // `break` exits the enclosing loop, even from a `switch`.
+                (TSKind::anon_break, Some((_, after))) => self.jump(Some(after)),
+                (TSKind::anon_continue, Some((header, _))) => self.jump(Some(header)),
+                // A `break` or `continue` outside of a loop does not compile, ignore it rather
+                // than reporting what follows it.
+                _ => (),
+            },
+            _ => (),
+        }
+    }
+
+    /// Whether the condition of a loop is missing, as in `for (;;)`, or always true.
+    fn is_always_true(&self, condition: Option<ExprId>) -> bool {
+        let Some(condition) = condition else {
+            return true;
+        };
+        match &self.body[condition] {
+            Expr::Literal(Literal::Bool(it)) => *it,
+            Expr::Literal(Literal::Int(it)) => *it != 0,
+            _ => false,
+        }
+    }
+}
@@ -6,7 +6,10 @@ use vfs::FileId;
 
 use crate::{
     ast_id_map::AstIdMap,
-    hir::{type_ref::TypeRef, Expr, ExprId, FloatTypeWrapper, Literal, SwitchCase},
+    hir::{
+        type_ref::{dimension_sizes, with_dimensions, TypeRef},
+        Expr, ExprId, FloatTypeWrapper, Literal, LoopKind, SwitchCase,
+    },
     item_tree::Name,
     BlockLoc, DefDatabase, DefWithBodyId, InFile, NodePtr,
 };
@@ -78,7 +81,11 @@ impl ExprCollector<'_> {
                 .alloc(Name::from_node(&name_node, self.source));
             let binding = Expr::Binding {
                 ident_id,
-                type_ref: TypeRef::from_returntype_node(&node, "type", self.source),
+                type_ref: with_dimensions(
+                    TypeRef::from_returntype_node(&node, "type", self.source),
+                    &node,
+                ),
+                dimensions: dimension_sizes(&node, self.source),
                 initializer: node
                     .child_by_field_name("initialValue")
                     .map(|default_node| self.collect_expr(default_node)),
@@ -104,7 +111,8 @@ impl ExprCollector<'_> {
                     .alloc(Name::from_node(&name_node, self.source));
                 let binding = Expr::Binding {
                     ident_id,
-                    type_ref: type_ref.clone(),
+                    type_ref: with_dimensions(type_ref.clone(), &child),
+                    dimensions: dimension_sizes(&child, self.source),
                     initializer: child
                         .child_by_field_name("initialValue")
                         .map(|default_node| self.collect_expr(default_node)),
@@ -131,7 +139,8 @@ impl ExprCollector<'_> {
                     .alloc(Name::from_node(&name_node, self.source));
                 let binding = Expr::Binding {
                     ident_id,
-                    type_ref: type_ref.clone(),
+                    type_ref: with_dimensions(type_ref.clone(), &child),
+                    dimensions: dimension_sizes(&child, self.source),
                     initializer: child
                         .child_by_field_name("initialValue")
                         .map(|default_node| self.collect_expr(default_node)),
@@ -194,6 +203,7 @@ impl ExprCollector<'_> {
                     initialization.push(self.collect_expr(init));
                 }
                 let for_loop = Expr::Loop {
+                    kind: LoopKind::For,
                     initialization: initialization.into_boxed_slice(),
                     condition: expr
                         .child_by_field_name("condition")
@@ -208,7 +218,13 @@ impl ExprCollector<'_> {
                 Some(self.alloc_expr(for_loop, NodePtr::from(&expr)))
             }
             TSKind::while_statement | TSKind::do_while_statement => {
+                let kind = if TSKind::from(expr) == TSKind::do_while_statement {
+                    LoopKind::DoWhile
+                } else {
+                    LoopKind::While
+                };
                 let loop_expr = Expr::Loop {
+                    kind,
                     initialization: Default::default(),
                     condition: expr
                         .child_by_field_name("condition")
@@ -269,10 +285,11 @@ impl ExprCollector<'_> {
                 Some(self.alloc_expr(switch, NodePtr::from(&expr)))
             }
             TSKind::return_statement => {
-                let expr = expr.child_by_field_name("expression")?;
                 let control_expr = Expr::Control {
                     keyword: TSKind::anon_return_,
-                    operand: self.maybe_collect_expr(expr),
+                    operand: expr
+                        .child_by_field_name("expression")
+                        .and_then(|it| self.maybe_collect_expr(it)),
                 };
                 Some(self.alloc_expr(control_expr, NodePtr::from(&expr)))
             }
@@ -374,10 +391,10 @@ impl ExprCollector<'_> {
             | TSKind::update_expression
             | TSKind::preproc_unary_expression => {
                 // For our needs, unary and update expressions are the same
-                let expr = expr.child_by_field_name("argument")?;
+                let argument = expr.child_by_field_name("argument")?;
                 let op = expr.child_by_field_name("operator").map(TSKind::from);
                 let unary = Expr::UnaryOp {
-                    operand: self.collect_expr(expr),
+                    operand: self.collect_expr(argument),
                     op,
                 };
                 Some(self.alloc_expr(unary, NodePtr::from(&expr)))
@@ -22,19 +22,23 @@ use crate::{
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParamData {
+    pub name: Option<Name>,
     pub type_ref: Option<TypeRef>,
     pub has_default: bool,
     pub is_rest: bool,
     pub is_const: bool,
+    pub is_ref: bool,
 }
 
 impl From<&Param> for ParamData {
     fn from(param: &Param) -> Self {
         ParamData {
+            name: param.name.clone(),
             type_ref: param.type_ref.clone(),
             has_default: param.has_default,
             is_rest: param.is_rest,
             is_const: param.is_const,
+            is_ref: param.is_ref,
         }
     }
 }
@@ -478,6 +482,7 @@ impl MethodmapData {
 pub struct TypedefData {
     pub name: Option<Name>,
     pub type_ref: TypeRef,
+    params: Vec<ParamData>,
     pub deprecated: bool,
 }
 
@@ -486,9 +491,15 @@ impl TypedefData {
         let loc = id.lookup(db).id;
         let item_tree = loc.tree_id().item_tree(db);
         let typedef = &item_tree[loc.value];
+        let params = typedef
+            .params
+            .clone()
+            .map(|param_idx| ParamData::from(&item_tree[param_idx]))
+            .collect_vec();
         let typedef_data = TypedefData {
             name: typedef.name.clone(),
             type_ref: typedef.type_ref.clone(),
+            params,
             deprecated: typedef.deprecated,
         };
 
@@ -498,6 +509,10 @@ impl TypedefData {
     pub fn name(&self) -> Option<Name> {
         self.name.clone()
     }
+
+    pub fn params(&self) -> &[ParamData] {
+        &self.params
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -542,6 +557,7 @@ impl TypesetData {
 pub struct FunctagData {
     pub name: Option<Name>,
     pub type_ref: Option<TypeRef>,
+    params: Vec<ParamData>,
     pub deprecated: bool,
 }
 
@@ -550,9 +566,15 @@ impl FunctagData {
         let loc = id.lookup(db).id;
         let item_tree = loc.tree_id().item_tree(db);
         let functag = &item_tree[loc.value];
+        let params = functag
+            .params
+            .clone()
+            .map(|param_idx| ParamData::from(&item_tree[param_idx]))
+            .collect_vec();
         let functag_data = FunctagData {
             name: functag.name.clone(),
             type_ref: functag.type_ref.clone(),
+            params,
             deprecated: functag.deprecated,
         };
 
@@ -562,6 +584,10 @@ impl FunctagData {
     pub fn name(&self) -> Option<Name> {
         self.name.clone()
     }
+
+    pub fn params(&self) -> &[ParamData] {
+        &self.params
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -829,6 +855,7 @@ impl EnumStructData {
 pub struct GlobalData {
     name: Name,
     type_ref: Option<TypeRef>,
+    dimensions: Box<[Option<u32>]>,
     visibility: RawVisibilityId,
 }
 
@@ -840,6 +867,7 @@ impl GlobalData {
         let global_data = GlobalData {
             name: global.name.clone(),
             type_ref: global.type_ref.clone(),
+            dimensions: global.dimensions.clone(),
             visibility: global.visibility,
         };
 
@@ -854,6 +882,12 @@ impl GlobalData {
         self.type_ref.as_ref()
     }
 
+    /// Returns the sizes of the dimensions of the global if it is an array, e.g. `[Some(64)]`
+    /// for `char name[64]`.
+    pub fn dimensions(&self) -> &[Option<u32>] {
+        &self.dimensions
+    }
+
     pub fn visibility(&self) -> RawVisibilityId {
         self.visibility
     }
@@ -914,6 +948,7 @@ impl HasChildSource<Idx<TypedefData>> for TypesetId {
                 let typedef = TypedefData {
                     name: None,
                     type_ref,
+                    params: Vec::new(),
                     deprecated: Default::default(),
                 };
                 map.insert(typedefs.alloc(typedef), NodePtr::from(&child));
@@ -944,6 +979,7 @@ impl HasChildSource<Idx<FunctagData>> for FuncenumId {
             let functag = FunctagData {
                 name: None,
                 type_ref,
+                params: Vec::new(),
                 deprecated: Default::default(),
             };
             map.insert(functags.alloc(functag), NodePtr::from(&child));
@@ -1,18 +1,18 @@
 use std::sync::Arc;
 
 use base_db::{
-    infer_include_ext, FileExtension, IncludeKind, IncludeType, SourceDatabase, Tree, RE_CHEVRON,
-    RE_QUOTE,
+    infer_include_ext, ContentChange, FileExtension, IncludeKind, IncludeType, ParseCacheDatabase,
+    SourceDatabase, Tree, RE_CHEVRON, RE_QUOTE,
 };
 use fxhash::FxHashMap;
 use preprocessor::db::PreprocDatabase;
 use smallvec::SmallVec;
-use syntax::TSKind;
+use syntax::{utils::input_edit, TSKind};
 use vfs::{AnchoredPath, FileId};
 
 use crate::{
     ast_id_map::AstIdMap,
-    body::{scope::ExprScopes, Body, BodySourceMap},
+    body::{cfg::ControlFlowGraph, scope::ExprScopes, Body, BodySourceMap},
     data::{
         EnumData, EnumStructData, FuncenumData, FunctagData, FunctionData, GlobalData, MacroData,
         MethodmapData, PropertyData, StructData, TypedefData, TypesetData, VariantData,
@@ -61,7 +61,7 @@ pub trait InternDatabase: SourceDatabase {
 }
 
 #[salsa::query_group(DefDatabaseStorage)]
-pub trait DefDatabase: InternDatabase + PreprocDatabase {
+pub trait DefDatabase: InternDatabase + PreprocDatabase + ParseCacheDatabase {
     /// Parses the file into the syntax tree.
     #[salsa::invoke(parse_query)]
     fn parse(&self, file_id: FileId) -> Tree;
@@ -91,6 +91,9 @@ pub trait DefDatabase: InternDatabase + PreprocDatabase {
     #[salsa::invoke(ExprScopes::expr_scopes_query)]
     fn expr_scopes(&self, def: DefWithBodyId, file_id: FileId) -> Arc<ExprScopes>;
 
+    #[salsa::invoke(ControlFlowGraph::control_flow_graph_query)]
+    fn control_flow_graph(&self, def: DefWithBodyId) -> Arc<ControlFlowGraph>;
+
     // region: data
     #[salsa::invoke(FunctionData::function_data_query)]
     fn function_data(&self, id: FunctionId) -> Arc<FunctionData>;
@@ -150,10 +153,65 @@ fn parse_query(db: &dyn DefDatabase, file_id: FileId) -> Tree {
         .set_language(&tree_sitter_sourcepawn::language())
         .expect("Failed to set language");
     let text = db.preprocessed_text(file_id);
-    parser
-        .parse(text.as_bytes(), None)
+    let changes = db.file_content_changes(file_id);
+    if changes.is_empty() {
+        // Only the files which are being edited keep their last tree around.
+        db.parse_cache().remove(file_id);
+        return parser
+            .parse(text.as_bytes(), None)
+            .expect("Failed to parse a file.")
+            .into();
+    }
+
+    let cached = db.parse_cache().get(file_id);
+    if let Some(cached) = cached.as_ref().filter(|cached| cached.text == text) {
+        return cached.tree.clone();
+    }
+    let old_tree = cached.and_then(|cached| {
+        let edit = preprocessed_edit(db, file_id, &cached.text, &text, &changes)?;
+        let mut tree = cached.tree;
+        tree.edit(&edit);
+        Some(tree)
+    });
+    let tree: Tree = parser
+        .parse(text.as_bytes(), old_tree.as_ref().map(Tree::tree))
         .expect("Failed to parse a file.")
-        .into()
+        .into();
+    db.parse_cache().insert(file_id, text, tree.clone());
+    tree
+}
+
+/// Maps the changes of the text of a file to the edit of its preprocessed text, from `old_text`
+/// to `new_text`.
+///
+/// Returns `None` if the preprocessed text did not change in the same way as the text of the
+/// file, e.g. because a macro or a preprocessor directive was edited. The file must then be
+/// parsed from scratch.
+fn preprocessed_edit(
+    db: &dyn DefDatabase,
+    file_id: FileId,
+    old_text: &str,
+    new_text: &str,
+    changes: &[ContentChange],
+) -> Option<tree_sitter::InputEdit> {
+    let change = ContentChange::compose(changes)?;
+    let preprocessing_results = db.preprocess_file(file_id);
+    let source_map = preprocessing_results.source_map();
+    let start: usize = source_map
+        .closest_s_position_always(change.range.start())
+        .into();
+    let new_end: usize = source_map
+        .closest_s_position_always(change.range.start() + change.new_len)
+        .into();
+    let old_end = (new_end + old_text.len()).checked_sub(new_text.len())?;
+    if start > new_end.min(old_end)
+        || old_text.get(..start)? != new_text.get(..start)?
+        || old_text.get(old_end..)? != new_text.get(new_end..)?
+    {
+        return None;
+    }
+
+    Some(input_edit(old_text, new_text, start, old_end, new_end))
 }
 
 /// Resolves an include node to a file id and include type and kind.
@@ -489,6 +547,10 @@ impl DefMap {
         self.values.get(name).cloned()
     }
 
+    pub fn names(&self) -> impl Iterator<Item = &Name> {
+        self.values.keys()
+    }
+
     pub fn get_from_str(&self, name: &str) -> Option<SmallVec<[FileDefId; 1]>> {
         self.get(&Name::from(name))
     }
@@ -1,7 +1,6 @@
 use std::fmt;
 
 use la_arena::Idx;
-use smallvec::SmallVec;
 use syntax::TSKind;
 
 use crate::{item_tree::Name, BlockId};
@@ -14,14 +13,6 @@ pub type Ident = Name;
 
 pub type IdentId = Idx<Ident>;
 
-#[derive(Debug, Clone, Eq, PartialEq)]
-pub struct Binding {
-    pub name: Name,
-    // pub mode: BindingAnnotation,
-    pub definitions: SmallVec<[IdentId; 1]>,
-    // pub problems: Option<BindingProblems>,
-}
-
 pub type ExprId = Idx<Expr>;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -77,6 +68,7 @@ pub enum Expr {
         else_branch: ExprId,
     },
     Loop {
+        kind: LoopKind,
         initialization: Box<[ExprId]>,
         condition: Option<ExprId>,
         iteration: Option<ExprId>,
@@ -108,11 +100,21 @@ pub enum Expr {
     Binding {
         ident_id: IdentId,
         type_ref: Option<TypeRef>,
+        /// Sizes of the dimensions of an array, e.g. `[Some(64)]` for `char name[64]`.
+        dimensions: Box<[Option<u32>]>,
         initializer: Option<ExprId>,
     },
     Literal(Literal),
 }
 
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum LoopKind {
+    For,
+    While,
+    /// A `do ... while` loop, whose body is executed before its condition.
+    DoWhile,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct SwitchCase {
     values: Box<[ExprId]>,
@@ -131,6 +133,11 @@ impl SwitchCase {
     pub fn values(&self) -> &[ExprId] {
         &self.values
     }
+
+    /// Whether the case is the `default` case of the switch.
+    pub fn is_default(&self) -> bool {
+        self.values.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
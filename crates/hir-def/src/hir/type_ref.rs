@@ -53,6 +53,22 @@ impl TypeRef {
             TSKind::anon_String => Self::OldString,
             TSKind::anon_Float => Self::Float,
             TSKind::r#type => TypeRef::Name(Name::from_node(node, source)),
+            TSKind::array_type => {
+                let mut dimensions = 0;
+                let mut type_ref = None;
+                for child in node.named_children(&mut node.walk()) {
+                    match TSKind::from(child) {
+                        TSKind::dimension | TSKind::fixed_dimension => dimensions += 1,
+                        _ => type_ref = Some(Self::from_node(&child, source)),
+                    }
+                }
+                match type_ref {
+                    Some(type_ref) if dimensions > 0 => {
+                        Self::Array((Box::new(type_ref), dimensions))
+                    }
+                    _ => TypeRef::Name(Name::from_node(node, source)),
+                }
+            }
             TSKind::old_type => {
                 let text = node
                     .utf8_text(source.as_bytes())
@@ -88,13 +104,50 @@ impl TypeRef {
         }
     }
 
+    /// Returns the equivalent of the type in the new syntax, e.g. `char` for `String:`.
+    ///
+    /// Untagged values of the old syntax are `int`s, hence `_:` is converted to `int`.
+    pub fn to_new_syntax(&self) -> Self {
+        match self {
+            TypeRef::OldString => TypeRef::Char,
+            TypeRef::OldFloat => TypeRef::Float,
+            TypeRef::OldName(name) => match name.to_string().as_str() {
+                "String" => TypeRef::Char,
+                "Float" => TypeRef::Float,
+                "_" => TypeRef::Int,
+                "bool" => TypeRef::Bool,
+                "any" => TypeRef::Any,
+                _ => TypeRef::Name(name.clone()),
+            },
+            TypeRef::Array((type_ref, size)) => {
+                TypeRef::Array((Box::new(type_ref.to_new_syntax()), *size))
+            }
+            _ => self.clone(),
+        }
+    }
+
+    /// Returns a neutral value of the type, e.g. to return from a stub, or [`None`] for `void`
+    /// and arrays, which cannot be returned.
+    pub fn default_value(&self) -> Option<String> {
+        match self.type_as_string().as_str() {
+            _ if matches!(self, TypeRef::Array(_)) => None,
+            "void" => None,
+            "int" | "any" | "char" | "_" => Some("0".to_string()),
+            "bool" => Some("false".to_string()),
+            "float" | "Float" => Some("0.0".to_string()),
+            "Action" => Some("Plugin_Continue".to_string()),
+            type_ => Some(format!("view_as<{type_}>(0)")),
+        }
+    }
+
+    /// Returns the type of the elements of an array of this type, e.g. `int` for `int[]`.
     pub fn to_lower_dim(&self) -> Self {
         match self {
             TypeRef::Array((type_ref, size)) => {
                 if *size > 1 {
                     TypeRef::Array((type_ref.clone(), size - 1))
                 } else {
-                    self.clone()
+                    *type_ref.clone()
                 }
             }
             _ => self.clone(),
@@ -127,6 +180,17 @@ impl fmt::Display for TypeRef {
 }
 
 impl TypeRef {
+    /// Whether the type is a string, i.e. an array of `char`.
+    pub fn is_string(&self) -> bool {
+        match self {
+            // `String:` is only used for arrays.
+            TypeRef::OldString => true,
+            TypeRef::OldName(name) => name.to_string() == "String",
+            TypeRef::Array((type_ref, _)) => type_ref.to_new_syntax().type_as_string() == "char",
+            _ => false,
+        }
+    }
+
     /// Returns the type as a string without the array brackets or
     /// the colon for old types
     pub fn type_as_string(&self) -> String {
@@ -149,3 +213,78 @@ impl TypeRef {
 pub fn type_string_from_node(node: &Node, source: &str) -> String {
     TypeRef::from_node(node, source).type_as_string()
 }
+
+/// Returns the type of a declaration with the dimensions which follow its name, e.g. `int[]` for
+/// `int arr[3]`.
+pub(crate) fn with_dimensions(
+    type_ref: Option<TypeRef>,
+    node: &tree_sitter::Node,
+) -> Option<TypeRef> {
+    let type_ref = type_ref?;
+    let size = node
+        .children(&mut node.walk())
+        .filter(|it| {
+            matches!(
+                TSKind::from(it),
+                TSKind::fixed_dimension | TSKind::dimension
+            )
+        })
+        .count();
+    if size == 0 {
+        return Some(type_ref);
+    }
+    match type_ref {
+        TypeRef::Array((type_ref, dims)) => Some(TypeRef::Array((type_ref, dims + size))),
+        type_ref => Some(TypeRef::Array((Box::new(type_ref), size))),
+    }
+}
+
+/// Returns the sizes of the dimensions which follow the name of a declaration, e.g. `[Some(3)]`
+/// for `int arr[3]`.
+///
+/// The size of a dimension is [`None`] if it is not given, as in `char name[] = "foo"`, or if it
+/// is not a constant.
+pub(crate) fn dimension_sizes(node: &Node, source: &str) -> Box<[Option<u32>]> {
+    node.children(&mut node.walk())
+        .filter_map(|it| match TSKind::from(it) {
+            TSKind::fixed_dimension => Some(
+                it.named_children(&mut it.walk())
+                    .next()
+                    .and_then(|size| eval_size(&size, source)),
+            ),
+            TSKind::dimension => Some(None),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Evaluates the size of a dimension made of integer literals, e.g. `MAX_NAME_LENGTH + 1` once
+/// the macro is expanded.
+pub fn eval_size(node: &Node, source: &str) -> Option<u32> {
+    match TSKind::from(node) {
+        TSKind::int_literal => {
+            let text = node.utf8_text(source.as_bytes()).ok()?.replace('_', "");
+            match text.get(..2) {
+                Some("0x") => u32::from_str_radix(&text[2..], 16).ok(),
+                Some("0b") => u32::from_str_radix(&text[2..], 2).ok(),
+                Some("0o") => u32::from_str_radix(&text[2..], 8).ok(),
+                _ => text.parse().ok(),
+            }
+        }
+        TSKind::parenthesized_expression => {
+            eval_size(&node.child_by_field_name("expression")?, source)
+        }
+        TSKind::binary_expression => {
+            let lhs = eval_size(&node.child_by_field_name("left")?, source)?;
+            let rhs = eval_size(&node.child_by_field_name("right")?, source)?;
+            match TSKind::from(node.child_by_field_name("operator")?) {
+                TSKind::anon_PLUS => lhs.checked_add(rhs),
+                TSKind::anon_DASH => lhs.checked_sub(rhs),
+                TSKind::anon_STAR => lhs.checked_mul(rhs),
+                TSKind::anon_SLASH => lhs.checked_div(rhs),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
@@ -1,19 +1,70 @@
 use std::sync::Arc;
 
 use fxhash::FxHashMap;
+use itertools::Itertools;
 use smallvec::smallvec;
-use stdx::impl_from;
+use stdx::{edit_distance, impl_from};
+
+use syntax::TSKind;
 
 use crate::{
     body::Body,
-    data::{EnumStructItemData, FunctionData, MethodmapItemData},
+    data::{EnumStructItemData, FunctionData, MethodmapItemData, ParamData},
     hir::{type_ref::TypeRef, Expr, Literal},
-    item_tree::Name,
+    item_tree::{FunctionKind, Name},
     resolver::{HasResolver, Resolver, ValueNs},
     DefDatabase, DefWithBodyId, ExprId, FieldId, FunctionId, InFile, ItemContainerId, Lookup,
-    PropertyId,
+    MethodmapId, PropertyId,
 };
 
+/// Constants defined by the compiler, which are not declared in any file.
+const COMPILER_CONSTANTS: &[&str] = &[
+    "INVALID_FUNCTION",
+    "__DATE__",
+    "__TIME__",
+    "__BINARY_PATH__",
+    "__BINARY_NAME__",
+    "cellmin",
+    "cellmax",
+    "cellbits",
+    "charmin",
+    "charmax",
+    "charbits",
+    "ucharmax",
+    "EOS",
+    "__Pawn",
+    "__LINE__",
+    "__FILE__",
+    "debug",
+];
+
+/// Returns the names which are close enough to `name` to be a misspelling of it, the closest
+/// first.
+pub fn closest_names(name: &str, names: impl IntoIterator<Item = Name>) -> Vec<Name> {
+    const MAX_CANDIDATES: usize = 3;
+    let max_distance = name.chars().count() / 3;
+    let lowercase_name = name.to_lowercase();
+    names
+        .into_iter()
+        .filter_map(|candidate| {
+            let candidate_str = candidate.to_string();
+            if candidate_str.len().abs_diff(name.len()) > max_distance {
+                return None;
+            }
+            // Differences of case are the most common typos.
+            let distance = edit_distance(&candidate_str.to_lowercase(), &lowercase_name);
+            if distance > max_distance {
+                return None;
+            }
+            Some(((distance, edit_distance(&candidate_str, name)), candidate))
+        })
+        .sorted()
+        .dedup()
+        .take(MAX_CANDIDATES)
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
 pub(crate) fn infer_query(db: &dyn DefDatabase, def: DefWithBodyId) -> Arc<InferenceResult> {
     let body = db.body(def);
     let resolver = def.resolver(db);
@@ -35,12 +86,16 @@ pub enum InferenceDiagnostic {
         receiver: Name,
         name: Name,
         method_with_same_name_exists: bool,
+        /// Fields of the receiver which are close to the name, the closest first.
+        candidates: Vec<Name>,
     },
     UnresolvedMethodCall {
         expr: ExprId,
         receiver: Name,
         name: Name,
         field_with_same_name_exists: bool,
+        /// Methods of the receiver which are close to the name, the closest first.
+        candidates: Vec<Name>,
     },
     UnresolvedConstructor {
         expr: ExprId,
@@ -51,16 +106,67 @@ pub enum InferenceDiagnostic {
         expr: ExprId,
         name: Name,
         callee: Name,
+        /// Parameters of the callee which are close to the name, the closest first.
+        candidates: Vec<Name>,
     },
     IncorrectNumberOfArguments {
         expr: ExprId,
         name: Name,
         expected: usize,
         actual: usize,
+        /// Names of the mandatory parameters which have no argument.
+        missing_params: Vec<Name>,
     },
     InvalidUseOfThis {
         expr: ExprId,
     },
+    UnresolvedIdent {
+        expr: ExprId,
+        name: Name,
+        /// Whether the identifier is the callee of a call.
+        is_callee: bool,
+        /// Type of the parameter the identifier is passed to, if it is an argument of a call.
+        expected_type: Option<TypeRef>,
+    },
+    TypeMismatch {
+        expr: ExprId,
+        expected: TypeRef,
+        actual: TypeRef,
+    },
+    CallbackSignatureMismatch {
+        expr: ExprId,
+        function: FunctionId,
+        /// Type of the parameter the function is passed to.
+        expected: TypeRef,
+        /// How the signature differs, if the type has a single signature.
+        mismatch: Option<SignatureMismatch>,
+    },
+}
+
+/// How the signature of a function differs from the signature of a callback type.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SignatureMismatch {
+    /// The function has more mandatory parameters than the callback receives.
+    TooManyParams {
+        expected: usize,
+        actual: usize,
+    },
+    /// The parameter at `idx` does not have the type of the parameter of the callback.
+    ParamType {
+        idx: usize,
+        expected: TypeRef,
+        actual: TypeRef,
+    },
+    /// The parameter at `idx` is passed by value where the callback passes it by reference, or
+    /// the other way around.
+    ParamRef {
+        idx: usize,
+        expected: bool,
+    },
+    ReturnType {
+        expected: TypeRef,
+        actual: TypeRef,
+    },
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -77,6 +183,28 @@ pub enum AttributeId {
 
 impl_from!(FieldId, PropertyId for AttributeId);
 
+/// Whether values of these tags can never be used in place of one another, e.g. in the
+/// signature of a callback.
+fn tags_conflict(expected: Tag, actual: Tag) -> bool {
+    match (expected, actual) {
+        (Tag::Float, Tag::Float) => false,
+        (Tag::Float, _) | (_, Tag::Float) => true,
+        (Tag::Methodmap(_), Tag::Int) | (Tag::Int, Tag::Methodmap(_)) => true,
+        (Tag::Methodmap(expected), Tag::Methodmap(actual)) => expected != actual,
+        _ => false,
+    }
+}
+
+/// The tag of a value, as far as tag mismatches are concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tag {
+    Int,
+    Bool,
+    Char,
+    Float,
+    Methodmap(MethodmapId),
+}
+
 /// The result of type inference: A mapping from expressions and patterns to types.
 #[derive(Clone, PartialEq, Eq, Debug, Default)]
 pub struct InferenceResult {
@@ -86,6 +214,10 @@ pub struct InferenceResult {
     method_resolutions: FxHashMap<ExprId, FunctionId>,
     /// For each named argument, records the local it resolves to.
     named_arg_resolutions: FxHashMap<ExprId, (DefWithBodyId, ExprId)>,
+    /// For each identifier expr which is a local of the body, records its binding.
+    local_resolutions: FxHashMap<ExprId, ExprId>,
+    /// For each expr whose type could be inferred, records its type.
+    type_of_expr: FxHashMap<ExprId, TypeRef>,
 
     pub diagnostics: Vec<InferenceDiagnostic>,
 }
@@ -102,6 +234,21 @@ impl InferenceResult {
     pub fn named_arg_resolution(&self, expr: ExprId) -> Option<(DefWithBodyId, ExprId)> {
         self.named_arg_resolutions.get(&expr).copied()
     }
+
+    pub fn local_resolution(&self, expr: ExprId) -> Option<ExprId> {
+        self.local_resolutions.get(&expr).copied()
+    }
+
+    pub fn type_of_expr(&self, expr: ExprId) -> Option<&TypeRef> {
+        self.type_of_expr.get(&expr)
+    }
+
+    /// Returns the identifier exprs which resolve to a local of the body, with their binding.
+    pub fn local_resolutions(&self) -> impl Iterator<Item = (ExprId, ExprId)> + '_ {
+        self.local_resolutions
+            .iter()
+            .map(|(expr, binding)| (*expr, *binding))
+    }
 }
 
 /// The inference context contains all information needed during type inference.
@@ -175,6 +322,86 @@ impl<'a> InferenceContext<'a> {
             .into()
     }
 
+    /// Returns the names of the mandatory parameters of the current call after the first
+    /// `nb_args` ones.
+    fn current_call_missing_params(&self, nb_args: usize) -> Vec<Name> {
+        let Some(data) = self.current_call_data() else {
+            return Vec::new();
+        };
+        data.params()
+            .iter()
+            .enumerate()
+            .skip(nb_args)
+            .filter(|(_, param)| !(param.has_default || param.is_rest))
+            .map(|(idx, param)| {
+                param
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| Name::from(format!("arg{}", idx + 1).as_str()))
+            })
+            .collect()
+    }
+
+    /// Returns the fields, or the methods, of the type which are close to `name`.
+    fn closest_members(&self, type_name: &str, name: &Name, methods: bool) -> Vec<Name> {
+        let names = match self.resolver.resolve_ident(type_name) {
+            Some(ValueNs::EnumStructId(it)) => {
+                let data = self.db.enum_struct_data(it.value);
+                data.items_map
+                    .iter()
+                    .filter(|(_, item)| {
+                        matches!(data.item(**item), EnumStructItemData::Method(_)) == methods
+                    })
+                    .map(|(name, _)| name.clone())
+                    .collect_vec()
+            }
+            Some(ValueNs::MethodmapId(it)) => {
+                let data = self.db.methodmap_data(it.value);
+                data.items_map
+                    .iter()
+                    .filter(|(_, item)| match data.item(**item) {
+                        MethodmapItemData::Property(_) => !methods,
+                        MethodmapItemData::Method(_) | MethodmapItemData::Static(_) => methods,
+                        MethodmapItemData::Constructor(_) | MethodmapItemData::Destructor(_) => {
+                            false
+                        }
+                    })
+                    .map(|(name, _)| name.clone())
+                    .collect_vec()
+            }
+            _ => Vec::new(),
+        };
+        closest_names(&name.to_string(), names)
+    }
+
+    /// Returns the type of the parameter of the current call which receives the argument at
+    /// `idx`.
+    fn current_call_param_type(&self, idx: usize) -> Option<TypeRef> {
+        let data = self.current_call_data()?;
+        let params = data.params();
+        params
+            .get(idx)
+            .or_else(|| params.last().filter(|param| param.is_rest))?
+            .type_ref
+            .clone()
+    }
+
+    /// Records the type expected by the current call for its argument `arg` at `idx`, if the
+    /// argument is an unresolved identifier.
+    fn set_expected_type(&mut self, arg: &ExprId, idx: usize) {
+        if !matches!(self.body[*arg], Expr::Ident(_)) {
+            return;
+        }
+        let expected = self.current_call_param_type(idx);
+        // Inferring the argument may have pushed other diagnostics after it.
+        let diagnostic = self.result.diagnostics.iter_mut().rev().find(
+            |it| matches!(it, InferenceDiagnostic::UnresolvedIdent { expr, .. } if expr == arg),
+        );
+        if let Some(InferenceDiagnostic::UnresolvedIdent { expected_type, .. }) = diagnostic {
+            *expected_type = expected;
+        }
+    }
+
     fn current_call_name(&self) -> Option<Name> {
         let data = self.current_call_data()?;
 
@@ -184,6 +411,14 @@ impl<'a> InferenceContext<'a> {
 
 impl InferenceContext<'_> {
     pub(crate) fn infer_expr(&mut self, expr: &ExprId) -> Option<TypeRef> {
+        let ty = self.infer_expr_inner(expr);
+        if let Some(ty) = &ty {
+            self.result.type_of_expr.insert(*expr, ty.clone());
+        }
+        ty
+    }
+
+    fn infer_expr_inner(&mut self, expr: &ExprId) -> Option<TypeRef> {
         match &self.body[*expr] {
             Expr::Block { id: _, statements } => {
                 let g = self
@@ -208,6 +443,7 @@ impl InferenceContext<'_> {
                 condition,
                 iteration,
                 body,
+                ..
             } => {
                 for init in initialization.iter() {
                     self.infer_expr(init);
@@ -242,32 +478,7 @@ impl InferenceContext<'_> {
                 None
             }
             Expr::NamedArg { name, value } => {
-                let current_call = self.current_call()?;
-                let id = current_call.id?;
-                let ValueNs::FunctionId(it) = id else {
-                    return None;
-                };
-                let function = it.first()?.value;
-                let mut resolver = function.resolver(self.db);
-                resolver.update_to_first_local_scope(self.db, function.into());
-                let Expr::Ident(name_str) = self.body[*name].clone() else {
-                    return None;
-                };
-                if let Some(ValueNs::LocalId((_, local, idx))) =
-                    resolver.resolve_ident(name_str.to_string().as_str())
-                {
-                    self.result
-                        .named_arg_resolutions
-                        .insert(*name, (local, idx));
-                } else {
-                    self.result
-                        .diagnostics
-                        .push(InferenceDiagnostic::UnresolvedNamedArg {
-                            expr: *name,
-                            name: name_str,
-                            callee: self.current_call_name().expect("No current call"),
-                        });
-                }
+                self.infer_named_arg_name(name);
                 self.infer_expr(value)
             }
             Expr::New { name, args } => {
@@ -277,11 +488,54 @@ impl InferenceContext<'_> {
                 self.infer_constructor(expr, name)
             }
             Expr::FieldAccess { target, name } => self.infer_field_access(expr, target, name),
-            Expr::UnaryOp { operand, .. } => self.infer_expr(operand),
-            Expr::BinaryOp { lhs, rhs, .. } => {
-                let _ = self.infer_expr(lhs);
-                // Assume the type of the left-hand side is the same as the right-hand side.
-                self.infer_expr(rhs)
+            Expr::UnaryOp { operand, op } => {
+                let ty = self.infer_expr(operand);
+                match op {
+                    Some(TSKind::anon_BANG) => Some(TypeRef::Bool),
+                    Some(TSKind::sizeof_expression) => Some(TypeRef::Int),
+                    _ => ty,
+                }
+            }
+            Expr::BinaryOp { lhs, rhs, op } => {
+                let lhs_ty = self.infer_expr(lhs);
+                let rhs_ty = self.infer_expr(rhs);
+                match op {
+                    Some(TSKind::anon_EQ) => {
+                        self.check_type_mismatch(rhs, lhs_ty.as_ref(), rhs_ty.as_ref());
+                        lhs_ty
+                    }
+                    Some(
+                        TSKind::anon_EQ_EQ
+                        | TSKind::anon_BANG_EQ
+                        | TSKind::anon_LT
+                        | TSKind::anon_GT
+                        | TSKind::anon_LT_EQ
+                        | TSKind::anon_GT_EQ
+                        | TSKind::anon_AMP_AMP
+                        | TSKind::anon_PIPE_PIPE,
+                    ) => Some(TypeRef::Bool),
+                    Some(
+                        TSKind::anon_PLUS_EQ
+                        | TSKind::anon_DASH_EQ
+                        | TSKind::anon_STAR_EQ
+                        | TSKind::anon_SLASH_EQ
+                        | TSKind::anon_PERCENT_EQ_
+                        | TSKind::anon_AMP_EQ
+                        | TSKind::anon_PIPE_EQ
+                        | TSKind::anon_CARET_EQ
+                        | TSKind::anon_LT_LT_EQ
+                        | TSKind::anon_GT_GT_EQ
+                        | TSKind::anon_GT_GT_GT_EQ_,
+                    ) => lhs_ty,
+                    // The float operators of `float.inc` accept an `int` on either side.
+                    _ if [&lhs_ty, &rhs_ty]
+                        .into_iter()
+                        .any(|ty| self.tag_of(ty.as_ref()) == Some(Tag::Float)) =>
+                    {
+                        Some(TypeRef::Float)
+                    }
+                    _ => lhs_ty.or(rhs_ty),
+                }
             }
             Expr::TernaryOp {
                 condition,
@@ -289,9 +543,9 @@ impl InferenceContext<'_> {
                 else_branch,
             } => {
                 self.infer_expr(condition);
-                let _ = self.infer_expr(then_branch);
-                // Assume the type of the then branch is the same as the else branch.
-                self.infer_expr(else_branch)
+                let then_ty = self.infer_expr(then_branch);
+                let else_ty = self.infer_expr(else_branch);
+                then_ty.or(else_ty)
             }
             Expr::ScopeAccess { scope, field } => self.infer_field_access(expr, scope, field),
             Expr::ArrayIndexedAccess { array, index } => {
@@ -308,21 +562,30 @@ impl InferenceContext<'_> {
                     Literal::Bool(_) => TypeRef::Bool,
                     Literal::Float(_) => TypeRef::Float,
                     Literal::Char(_) => TypeRef::Char,
-                    Literal::String(_) => TypeRef::OldString,
+                    Literal::String(_) => TypeRef::Array((Box::new(TypeRef::Char), 1)),
                     Literal::Null => TypeRef::Void,
                     Literal::Array(elements) => {
                         let mut ty = None;
                         for element in elements.iter() {
                             ty = self.infer_expr(element);
                         }
-                        ty?
+                        match ty? {
+                            TypeRef::Array((ty, size)) => TypeRef::Array((ty, size + 1)),
+                            ty => TypeRef::Array((Box::new(ty), 1)),
+                        }
                     }
                 };
                 Some(ty)
             }
-            Expr::Control { operand, .. } => {
+            Expr::Control { keyword, operand } => {
                 if let Some(operand) = operand {
-                    self.infer_expr(operand);
+                    let ty = self.infer_expr(operand);
+                    if *keyword == TSKind::anon_return_ {
+                        if let DefWithBodyId::FunctionId(fn_id) = self.owner {
+                            let ret_type = self.db.function_data(fn_id).type_ref();
+                            self.check_type_mismatch(operand, ret_type.as_ref(), ty.as_ref());
+                        }
+                    }
                 }
                 None
             }
@@ -354,16 +617,33 @@ impl InferenceContext<'_> {
             }
             Expr::Ident(name) => {
                 let name: String = name.clone().into();
-                let res = self.resolver.resolve_ident(&name)?; // TODO: Should we emit a diagnostic here?
+                let Some(res) = self.resolver.resolve_ident(&name) else {
+                    if !COMPILER_CONSTANTS.contains(&name.as_str()) {
+                        let is_callee = self.current_call().is_some_and(|it| it.expr == *expr);
+                        self.result
+                            .diagnostics
+                            .push(InferenceDiagnostic::UnresolvedIdent {
+                                expr: *expr,
+                                name: Name::from(name.as_str()),
+                                is_callee,
+                                expected_type: None,
+                            });
+                    }
+                    return None;
+                };
                 match &res {
                     ValueNs::GlobalId(it) => {
                         let item_tree = self.db.file_item_tree(it.file_id);
                         item_tree[it.value.lookup(self.db).value].type_ref.clone()
                     }
-                    ValueNs::LocalId((_, _, expr_id)) => {
+                    ValueNs::LocalId((_, owner, expr_id)) => {
+                        if *owner == self.owner {
+                            self.result.local_resolutions.insert(*expr, *expr_id);
+                        }
                         let Expr::Binding {
                             ident_id: _,
                             type_ref,
+                            dimensions: _,
                             initializer: _,
                         } = &self.body[*expr_id]
                         else {
@@ -385,6 +665,10 @@ impl InferenceContext<'_> {
                                 current_call.id = Some(res.clone());
                             }
                         }
+                        if self.current_call().is_none_or(|it| it.expr != *expr) {
+                            // The function is used as a value, e.g. passed as a callback.
+                            return TypeRef::Name(Name::from("Function")).into();
+                        }
                         let mut ret_type = None;
                         for fn_id in it.iter() {
                             let item_tree = self.db.file_item_tree(fn_id.file_id);
@@ -412,10 +696,22 @@ impl InferenceContext<'_> {
                         let item_tree = self.db.file_item_tree(it.file_id);
                         TypeRef::Name(item_tree[it.value.lookup(self.db).id].name.clone()).into()
                     }
-                    ValueNs::VariantId(_)
-                    | ValueNs::EnumId(_)
-                    | ValueNs::MacroId(_)
-                    | ValueNs::StructId(_) => None,
+                    ValueNs::VariantId(it) => {
+                        let ItemContainerId::EnumId(enum_id) = it.value.lookup(self.db).container
+                        else {
+                            return None;
+                        };
+                        let enum_loc = enum_id.lookup(self.db);
+                        let item_tree = enum_loc.id.item_tree(self.db);
+                        let name = item_tree[enum_loc.id].name.clone();
+                        if name.to_string().starts_with("unnamed_enum_") {
+                            // The entries of an anonymous enum are untagged.
+                            Some(TypeRef::Int)
+                        } else {
+                            Some(TypeRef::Name(name))
+                        }
+                    }
+                    ValueNs::EnumId(_) | ValueNs::MacroId(_) | ValueNs::StructId(_) => None,
                 }
             }
             Expr::MethodCall {
@@ -425,8 +721,10 @@ impl InferenceContext<'_> {
             } => {
                 self.push_call(*target);
                 let ty = self.infer_method_call(expr, target, method_name);
-                for arg in args.iter() {
-                    self.infer_expr(arg);
+                for (idx, arg) in args.iter().enumerate() {
+                    let arg_ty = self.infer_expr(arg);
+                    self.set_expected_type(arg, idx);
+                    self.check_arg_type(arg, idx, arg_ty.as_ref());
                 }
                 self.pop_call();
                 ty
@@ -434,8 +732,10 @@ impl InferenceContext<'_> {
             Expr::Call { callee, args } => {
                 self.push_call(*callee);
                 let ty = self.infer_expr(callee);
-                for arg in args.iter() {
-                    self.infer_expr(arg);
+                for (idx, arg) in args.iter().enumerate() {
+                    let arg_ty = self.infer_expr(arg);
+                    self.set_expected_type(arg, idx);
+                    self.check_arg_type(arg, idx, arg_ty.as_ref());
                 }
                 if let Some((min, max)) = self.current_call_params_numbers() {
                     if args.len() < min || args.len() > max.unwrap_or(usize::MAX) {
@@ -449,6 +749,7 @@ impl InferenceContext<'_> {
                                     max.unwrap_or(usize::MAX)
                                 },
                                 actual: args.len(),
+                                missing_params: self.current_call_missing_params(args.len()),
                             },
                         );
                     }
@@ -468,7 +769,8 @@ impl InferenceContext<'_> {
                 ..
             } => {
                 if let Some(initializer) = initializer {
-                    self.infer_expr(initializer);
+                    let ty = self.infer_expr(initializer);
+                    self.check_type_mismatch(initializer, type_ref.as_ref(), ty.as_ref());
                 }
                 type_ref.as_ref().cloned()
             }
@@ -476,12 +778,287 @@ impl InferenceContext<'_> {
         }
     }
 
+    /// Checks the type of the argument at `idx` of the current call against the type of the
+    /// parameter which receives it.
+    fn check_arg_type(&mut self, arg: &ExprId, idx: usize, actual: Option<&TypeRef>) {
+        if matches!(self.body[*arg], Expr::NamedArg { .. }) {
+            return;
+        }
+        let expected = self.current_call_param_type(idx);
+        self.check_type_mismatch(arg, expected.as_ref(), actual);
+        if let Some(expected) = expected {
+            self.check_callback_signature(arg, expected);
+        }
+    }
+
+    /// Reports a [`InferenceDiagnostic::CallbackSignatureMismatch`] if the expression is a
+    /// function whose signature matches none of the signatures of the callback type `expected`.
+    fn check_callback_signature(&mut self, expr: &ExprId, expected: TypeRef) -> Option<()> {
+        let Expr::Ident(name) = &self.body[*expr] else {
+            return None;
+        };
+        let ValueNs::FunctionId(ids) = self.resolver.resolve_ident(&name.to_string())? else {
+            return None;
+        };
+        let function = ids
+            .iter()
+            .find(|it| self.db.function_data(it.value).kind == FunctionKind::Def)
+            .or_else(|| ids.first())?
+            .value;
+        let signatures = self.callback_signatures(&expected)?;
+        let data = self.db.function_data(function);
+        let mismatches = signatures
+            .iter()
+            .map(|(params, ret_type)| self.signature_mismatch(&data, params, ret_type.as_ref()))
+            .collect_vec();
+        if mismatches.iter().any(Option::is_none) {
+            return None;
+        }
+        let mismatch = match mismatches.as_slice() {
+            [mismatch] => mismatch.clone(),
+            _ => None,
+        };
+        self.result
+            .diagnostics
+            .push(InferenceDiagnostic::CallbackSignatureMismatch {
+                expr: *expr,
+                function,
+                expected,
+                mismatch,
+            });
+
+        Some(())
+    }
+
+    /// Returns the signatures accepted by a callback type, i.e. a typedef, a typeset, a functag
+    /// or a funcenum, or [`None`] if the type is not a callback type.
+    fn callback_signatures(&self, ty: &TypeRef) -> Option<Vec<(Vec<ParamData>, Option<TypeRef>)>> {
+        let typedef_signature = |id| {
+            let data = self.db.typedef_data(id);
+            (data.params().to_vec(), Some(data.type_ref.clone()))
+        };
+        let functag_signature = |id| {
+            let data = self.db.functag_data(id);
+            (data.params().to_vec(), data.type_ref.clone())
+        };
+        let res = match self.resolver.resolve_ident(&ty.type_as_string())? {
+            ValueNs::TypedefId(it) => vec![typedef_signature(it.value)],
+            ValueNs::TypesetId(it) => self
+                .db
+                .typeset_data(it.value)
+                .typedefs
+                .iter()
+                .map(|(_, id)| typedef_signature(*id))
+                .collect(),
+            ValueNs::FunctagId(it) => vec![functag_signature(it.value)],
+            ValueNs::FuncenumId(it) => self
+                .db
+                .funcenum_data(it.value)
+                .functags
+                .iter()
+                .map(|(_, id)| functag_signature(*id))
+                .collect(),
+            _ => return None,
+        };
+
+        Some(res)
+    }
+
+    /// Returns how the signature of the function differs from a callback signature, or
+    /// [`None`] if the function can be used as this callback.
+    ///
+    /// A function may have fewer parameters than the callback receives. Like for
+    /// [`Self::check_type_mismatch`], only the tags which are never compatible are compared.
+    fn signature_mismatch(
+        &self,
+        function: &FunctionData,
+        params: &[ParamData],
+        ret_type: Option<&TypeRef>,
+    ) -> Option<SignatureMismatch> {
+        let fn_params = function.params();
+        let mandatory = fn_params
+            .iter()
+            .filter(|it| !(it.has_default || it.is_rest))
+            .count();
+        if mandatory > params.len() && !params.last().is_some_and(|it| it.is_rest) {
+            return Some(SignatureMismatch::TooManyParams {
+                expected: params.len(),
+                actual: mandatory,
+            });
+        }
+        for (idx, (expected, actual)) in params.iter().zip(fn_params).enumerate() {
+            if expected.is_rest || actual.is_rest {
+                break;
+            }
+            let (Some(expected_ty), Some(actual_ty)) = (&expected.type_ref, &actual.type_ref)
+            else {
+                continue;
+            };
+            let (Some(expected_tag), Some(actual_tag)) =
+                (self.tag_of(Some(expected_ty)), self.tag_of(Some(actual_ty)))
+            else {
+                continue;
+            };
+            if tags_conflict(expected_tag, actual_tag) {
+                return Some(SignatureMismatch::ParamType {
+                    idx,
+                    expected: expected_ty.to_new_syntax(),
+                    actual: actual_ty.to_new_syntax(),
+                });
+            }
+            if expected.is_ref != actual.is_ref {
+                return Some(SignatureMismatch::ParamRef {
+                    idx,
+                    expected: expected.is_ref,
+                });
+            }
+        }
+
+        let (Some(expected), Some(actual)) = (ret_type, function.type_ref.as_ref()) else {
+            return None;
+        };
+        let (expected, actual) = (expected.to_new_syntax(), actual.to_new_syntax());
+        let (expected_str, actual_str) = (expected.to_string(), actual.to_string());
+        let is_mismatch = expected_str != actual_str
+            && (expected_str == "void"
+                || actual_str == "void"
+                || self
+                    .tag_of(Some(&expected))
+                    .zip(self.tag_of(Some(&actual)))
+                    .is_some_and(|(expected, actual)| tags_conflict(expected, actual)));
+        is_mismatch.then_some(SignatureMismatch::ReturnType { expected, actual })
+    }
+
+    /// Reports a [`InferenceDiagnostic::TypeMismatch`] if a value of type `actual` cannot be
+    /// used where a value of type `expected` is.
+    ///
+    /// Only the mismatches which spcomp always reports are reported, i.e. between `float` and
+    /// the other scalar tags, between `int` and methodmaps, and between methodmaps which do not
+    /// inherit from one another. An `int` literal is accepted where a `float` or a methodmap is
+    /// expected.
+    fn check_type_mismatch(
+        &mut self,
+        expr: &ExprId,
+        expected: Option<&TypeRef>,
+        actual: Option<&TypeRef>,
+    ) {
+        let (Some(expected), Some(actual)) = (expected, actual) else {
+            return;
+        };
+        let (Some(expected_tag), Some(actual_tag)) =
+            (self.tag_of(Some(expected)), self.tag_of(Some(actual)))
+        else {
+            return;
+        };
+        let is_mismatch = match (expected_tag, actual_tag) {
+            (Tag::Float, Tag::Float) => false,
+            (Tag::Float | Tag::Methodmap(_), Tag::Int)
+                if matches!(self.body[*expr], Expr::Literal(Literal::Int(_))) =>
+            {
+                false
+            }
+            (Tag::Float, _) | (_, Tag::Float) => true,
+            (Tag::Methodmap(_), Tag::Int) => true,
+            (Tag::Int, Tag::Methodmap(_)) => true,
+            (Tag::Methodmap(expected), Tag::Methodmap(actual)) => {
+                !self.inherits_from(actual, expected)
+            }
+            _ => false,
+        };
+        if is_mismatch {
+            self.result
+                .diagnostics
+                .push(InferenceDiagnostic::TypeMismatch {
+                    expr: *expr,
+                    expected: expected.to_new_syntax(),
+                    actual: actual.to_new_syntax(),
+                });
+        }
+    }
+
+    /// Returns the tag of a scalar type, or [`None`] for the arrays and the types which accept,
+    /// or are accepted by, any tag.
+    fn tag_of(&self, ty: Option<&TypeRef>) -> Option<Tag> {
+        let name = match ty?.to_new_syntax() {
+            TypeRef::Int => return Some(Tag::Int),
+            TypeRef::Bool => return Some(Tag::Bool),
+            TypeRef::Char => return Some(Tag::Char),
+            TypeRef::Float => return Some(Tag::Float),
+            TypeRef::Name(name) => name.to_string(),
+            _ => return None,
+        };
+        match name.as_str() {
+            "int" => Some(Tag::Int),
+            "bool" => Some(Tag::Bool),
+            "char" => Some(Tag::Char),
+            "float" => Some(Tag::Float),
+            _ => match self.resolver.resolve_ident(&name)? {
+                ValueNs::MethodmapId(it) => Some(Tag::Methodmap(it.value)),
+                _ => None,
+            },
+        }
+    }
+
+    /// Whether the methodmap is `parent` or inherits from it.
+    fn inherits_from(&self, methodmap: MethodmapId, parent: MethodmapId) -> bool {
+        let mut current = Some(methodmap);
+        // Guard against inheritance cycles.
+        for _ in 0..32 {
+            match current {
+                Some(id) if id == parent => return true,
+                Some(id) => current = self.db.methodmap_data(id).inherits,
+                None => return false,
+            }
+        }
+        false
+    }
+
     pub(crate) fn collect_fn(&mut self, _func: FunctionId) {
         if let Some(id) = self.body.body_expr {
             self.infer_expr(&id);
         }
     }
 
+    /// Resolves the name of a named argument to the parameter of the current call.
+    fn infer_named_arg_name(&mut self, name: &ExprId) -> Option<()> {
+        let current_call = self.current_call()?;
+        let id = current_call.id?;
+        let ValueNs::FunctionId(it) = id else {
+            return None;
+        };
+        let function = it.first()?.value;
+        let mut resolver = function.resolver(self.db);
+        resolver.update_to_first_local_scope(self.db, function.into());
+        let Expr::Ident(name_str) = self.body[*name].clone() else {
+            return None;
+        };
+        if let Some(ValueNs::LocalId((_, local, idx))) =
+            resolver.resolve_ident(name_str.to_string().as_str())
+        {
+            self.result
+                .named_arg_resolutions
+                .insert(*name, (local, idx));
+        } else {
+            self.result
+                .diagnostics
+                .push(InferenceDiagnostic::UnresolvedNamedArg {
+                    expr: *name,
+                    candidates: closest_names(
+                        &name_str.to_string(),
+                        self.db
+                            .function_data(function)
+                            .params()
+                            .iter()
+                            .filter_map(|param| param.name.clone()),
+                    ),
+                    name: name_str,
+                    callee: self.current_call_name().expect("No current call"),
+                });
+        }
+
+        Some(())
+    }
+
     fn infer_constructor(&mut self, expr: &ExprId, name: &Name) -> Option<TypeRef> {
         let type_name_str: String = name.clone().into();
         match self.resolver.resolve_ident(&type_name_str) {
@@ -557,6 +1134,7 @@ impl InferenceContext<'_> {
                                 .diagnostics
                                 .push(InferenceDiagnostic::UnresolvedField {
                                     expr: *receiver,
+                                    candidates: self.closest_members(&type_name_str, name, false),
                                     receiver: type_name,
                                     name: name.clone(),
                                     method_with_same_name_exists: true,
@@ -586,6 +1164,7 @@ impl InferenceContext<'_> {
                                 .diagnostics
                                 .push(InferenceDiagnostic::UnresolvedField {
                                     expr: *receiver,
+                                    candidates: self.closest_members(&type_name_str, name, false),
                                     receiver: type_name,
                                     name: name.clone(),
                                     method_with_same_name_exists: true,
@@ -595,12 +1174,15 @@ impl InferenceContext<'_> {
                     }
                 }
             }
+            // The entries of an enum have no fields.
+            ValueNs::EnumId(_) => return None,
             _ => (),
         }
         self.result
             .diagnostics
             .push(InferenceDiagnostic::UnresolvedField {
                 expr: *receiver,
+                candidates: self.closest_members(&type_name_str, name, false),
                 receiver: type_name,
                 name: name.clone(),
                 method_with_same_name_exists: false,
@@ -629,6 +1211,11 @@ impl InferenceContext<'_> {
                             self.result.diagnostics.push(
                                 InferenceDiagnostic::UnresolvedMethodCall {
                                     expr: *receiver,
+                                    candidates: self.closest_members(
+                                        &type_name_str,
+                                        method_name,
+                                        true,
+                                    ),
                                     receiver: type_name,
                                     name: method_name.clone(),
                                     field_with_same_name_exists: true,
@@ -659,6 +1246,11 @@ impl InferenceContext<'_> {
                             self.result.diagnostics.push(
                                 InferenceDiagnostic::UnresolvedMethodCall {
                                     expr: *receiver,
+                                    candidates: self.closest_members(
+                                        &type_name_str,
+                                        method_name,
+                                        true,
+                                    ),
                                     receiver: type_name,
                                     name: method_name.clone(),
                                     field_with_same_name_exists: true,
@@ -684,12 +1276,15 @@ impl InferenceContext<'_> {
                     }
                 }
             }
+            // The entries of an enum have no methods.
+            ValueNs::EnumId(_) => return None,
             _ => (),
         }
         self.result
             .diagnostics
             .push(InferenceDiagnostic::UnresolvedMethodCall {
                 expr: *receiver,
+                candidates: self.closest_members(&type_name_str, method_name, true),
                 receiver: type_name,
                 name: method_name.clone(),
                 field_with_same_name_exists: false,
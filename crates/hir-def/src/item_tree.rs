@@ -14,7 +14,12 @@ use syntax::TSKind;
 use vfs::FileId;
 
 pub use crate::ast_id_map::AstId;
-use crate::{db::DefDatabase, hir::type_ref::TypeRef, src::HasSource, BlockId, ItemTreeId, Lookup};
+use crate::{
+    db::DefDatabase,
+    hir::type_ref::{dimension_sizes, with_dimensions, TypeRef},
+    src::HasSource,
+    BlockId, ItemTreeId, Lookup,
+};
 
 use self::lower::Ctx;
 
@@ -146,7 +151,8 @@ impl ItemTree {
                                         name_node.utf8_text(source.as_bytes()).unwrap(),
                                     ),
                                     visibility: RawVisibilityId::NONE,
-                                    type_ref: type_ref.clone(),
+                                    type_ref: with_dimensions(type_ref.clone(), &sub_child),
+                                    dimensions: dimension_sizes(&sub_child, &source),
                                     ast_id: ast_id_map.ast_id_of(&sub_child),
                                 };
                                 let id = item_tree.data_mut().variables.alloc(res);
@@ -166,7 +172,8 @@ impl ItemTree {
                             let res = Variable {
                                 name: Name::from(name_node.utf8_text(source.as_bytes()).unwrap()),
                                 visibility: RawVisibilityId::NONE,
-                                type_ref: type_ref.clone(),
+                                type_ref: with_dimensions(type_ref.clone(), &sub_child),
+                                dimensions: dimension_sizes(&sub_child, &source),
                                 ast_id: ast_id_map.ast_id_of(&sub_child),
                             };
                             let id = item_tree.data_mut().variables.alloc(res);
@@ -297,6 +304,8 @@ pub struct Variable {
     pub name: Name,
     pub visibility: RawVisibilityId,
     pub type_ref: Option<TypeRef>,
+    /// Sizes of the dimensions of an array, e.g. `[Some(64)]` for `char name[64]`.
+    pub dimensions: Box<[Option<u32>]>,
     pub ast_id: AstId,
 }
 
@@ -320,9 +329,12 @@ pub struct Function {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Param {
+    pub name: Option<Name>,
     pub has_default: bool,
     pub is_rest: bool,
     pub is_const: bool,
+    /// Whether the parameter is passed by reference, i.e. declared with a `&` or as an array.
+    pub is_ref: bool,
     pub type_ref: Option<TypeRef>,
     pub ast_id: AstId,
 }
@@ -442,11 +454,6 @@ pub struct Variant {
     pub deprecated: bool,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
-pub struct Block {
-    pub ast_id: AstId,
-}
-
 /// Trait implemented by all item nodes in the item tree.
 pub trait ItemTreeNode: Clone {
     fn ast_id(&self) -> AstId;
@@ -10,7 +10,10 @@ use tree_sitter::QueryCursor;
 use vfs::FileId;
 
 use crate::{
-    ast_id_map::AstIdMap, hir::type_ref::TypeRef, item_tree::Macro, DefDatabase, FileItem, Name,
+    ast_id_map::AstIdMap,
+    hir::type_ref::{dimension_sizes, with_dimensions, TypeRef},
+    item_tree::Macro,
+    DefDatabase, FileItem, Name,
 };
 
 use super::{
@@ -142,6 +145,7 @@ impl<'db> Ctx<'db> {
                 name: Name::from(name_node.utf8_text(self.source.as_bytes()).unwrap()),
                 visibility,
                 type_ref: type_ref.clone(),
+                dimensions: Default::default(),
                 ast_id: self.source_ast_id_map.ast_id_of(node),
             };
             let id = self.tree.data_mut().variables.alloc(res);
@@ -161,7 +165,8 @@ impl<'db> Ctx<'db> {
                     let res = Variable {
                         name: Name::from(name_node.utf8_text(self.source.as_bytes()).unwrap()),
                         visibility,
-                        type_ref: type_ref.clone(),
+                        type_ref: with_dimensions(type_ref.clone(), &child),
+                        dimensions: dimension_sizes(&child, &self.source),
                         ast_id: self.source_ast_id_map.ast_id_of(&child),
                     };
                     let id = self.tree.data_mut().variables.alloc(res);
@@ -182,7 +187,8 @@ impl<'db> Ctx<'db> {
                 let res = Variable {
                     name: Name::from(name_node.utf8_text(self.source.as_bytes()).unwrap()),
                     visibility,
-                    type_ref: type_ref.clone(),
+                    type_ref: with_dimensions(type_ref.clone(), &child),
+                    dimensions: dimension_sizes(&child, &self.source),
                     ast_id: self.source_ast_id_map.ast_id_of(&child),
                 };
                 let id = self.tree.data_mut().variables.alloc(res);
@@ -308,11 +314,15 @@ impl<'db> Ctx<'db> {
             .for_each(|n| match TSKind::from(n) {
                 TSKind::parameter_declaration | TSKind::rest_parameter => {
                     let res = Param {
+                        name: n
+                            .child_by_field_name("name")
+                            .map(|name| Name::from_node(&name, &self.source)),
                         type_ref: TypeRef::from_returntype_node(&n, "type", &self.source),
                         ast_id: self.source_ast_id_map.ast_id_of(&n),
                         has_default: n.child_by_field_name("defaultValue").is_some(),
                         is_rest: TSKind::from(n) == TSKind::rest_parameter,
                         is_const: n.child_by_field_name("storage_class").is_some(),
+                        is_ref: is_ref_parameter(&n),
                     };
                     self.tree.data_mut().params.alloc(res);
                 }
@@ -608,11 +618,15 @@ impl<'db> Ctx<'db> {
                 };
                 let storage_class_node = param_node.child_by_field_name("storage_class");
                 let param = Param {
+                    name: param_node
+                        .child_by_field_name("name")
+                        .map(|name| Name::from_node(&name, &self.source)),
                     type_ref: TypeRef::from_returntype_node(&param_node, "type", &self.source),
                     ast_id: self.source_ast_id_map.ast_id_of(&param_node),
                     has_default: false,
                     is_rest: false,
                     is_const: storage_class_node.is_some(),
+                    is_ref: is_ref_parameter(&param_node),
                 };
                 let start_idx = self.next_param_idx();
                 self.tree.data_mut().params.alloc(param);
@@ -644,8 +658,10 @@ impl<'db> Ctx<'db> {
                     let Some(field_name_node) = e.child_by_field_name("name") else {
                         return;
                     };
-                    let Some(type_ref) = TypeRef::from_returntype_node(&e, "type", &self.source)
-                    else {
+                    let Some(type_ref) = with_dimensions(
+                        TypeRef::from_returntype_node(&e, "type", &self.source),
+                        &e,
+                    ) else {
                         return;
                     };
                     let res = Field {
@@ -726,3 +742,13 @@ impl<'db> Ctx<'db> {
         ))
     }
 }
+
+/// Whether a parameter is passed by reference. Arrays are always passed by reference.
+fn is_ref_parameter(node: &tree_sitter::Node) -> bool {
+    node.children(&mut node.walk()).any(|child| {
+        matches!(
+            TSKind::from(child),
+            TSKind::anon_AMP | TSKind::array_type | TSKind::dimension | TSKind::fixed_dimension
+        )
+    })
+}
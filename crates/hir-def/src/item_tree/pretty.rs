@@ -81,6 +81,7 @@ impl<'a> Printer<'a> {
             visibility,
             type_ref,
             ast_id,
+            ..
         } = &self.tree[*idx];
         self.push(format!("// {}", ast_id).as_str());
         self.newline();
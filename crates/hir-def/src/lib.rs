@@ -26,14 +26,17 @@ pub mod resolver;
 pub mod src;
 
 pub use ast_id_map::NodePtr;
-pub use data::{MethodmapExtension, PropertyItem};
+pub use data::{MethodmapExtension, MethodmapItemData, ParamData, PropertyItem};
 pub use db::resolve_include_node;
 pub use db::DefDatabase;
 pub use db::{BlockDefMapQuery, BlockItemTreeQuery, BodyQuery, FileDefMapQuery, FileItemTreeQuery};
 pub use diagnostics::DefDiagnostic;
-pub use hir::type_ref::type_string_from_node;
-pub use hir::ExprId;
-pub use infer::{AttributeId, ConstructorDiagnosticKind, InferenceDiagnostic, InferenceResult};
+pub use hir::type_ref::{eval_size, type_string_from_node, TypeRef};
+pub use hir::{Expr, ExprId};
+pub use infer::{
+    closest_names, AttributeId, ConstructorDiagnosticKind, InferenceDiagnostic, InferenceResult,
+    SignatureMismatch,
+};
 pub use item_tree::{
     print_item_tree, FileItem, FunctionKind, Name, RawVisibilityId, SpecialMethod,
 };
@@ -234,6 +234,21 @@ impl Resolver {
         }
     }
 
+    /// Names of the locals and of the items visible from the resolver, without duplicates.
+    pub fn names_in_scope(&self) -> Vec<Name> {
+        self.scopes()
+            .flat_map(|scope| match scope {
+                Scope::Global(def_maps) => def_maps
+                    .iter()
+                    .flat_map(|def_map| def_map.names().cloned())
+                    .collect_vec(),
+                Scope::Expr(it) => it.entries().map(|(name, _)| name.clone()).collect_vec(),
+                Scope::This(_) => Vec::new(),
+            })
+            .unique()
+            .collect()
+    }
+
     pub fn available_defs(&self) -> Vec<ValueNs> {
         self.scopes()
             .flat_map(|scope| match scope {
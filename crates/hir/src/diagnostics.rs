@@ -4,8 +4,10 @@
 //! This probably isn't the best way to do this -- ideally, diagnostics should
 //! be expressed in terms of hir types themselves.
 
-use hir_def::{InFile, Name, NodePtr};
-use sourcepawn_lexer::TextRange;
+use hir_def::{closest_names, DefWithBodyId, InFile, Name, NodePtr, SignatureMismatch, TypeRef};
+use sourcepawn_lexer::{TextRange, TextSize};
+
+use crate::{db::HirDatabase, source_analyzer::SourceAnalyzer, DefResolution, Function};
 
 macro_rules! diagnostics {
     ($($diag:ident,)*) => {
@@ -36,6 +38,12 @@ diagnostics![
     UnresolvedMacro,
     InactiveCode,
     InvalidUseOfThis,
+    UnresolvedIdent,
+    UnusedVariable,
+    TypeMismatch,
+    CallbackSignatureMismatch,
+    UnreachableCode,
+    MissingReturn,
 ];
 
 #[derive(Debug)]
@@ -50,6 +58,7 @@ pub struct UnresolvedField {
     pub receiver: Name,
     pub name: Name,
     pub method_with_same_name_exists: bool,
+    pub candidates: Vec<Name>,
 }
 
 #[derive(Debug)]
@@ -58,6 +67,7 @@ pub struct UnresolvedMethodCall {
     pub receiver: Name,
     pub name: Name,
     pub field_with_same_name_exists: bool,
+    pub candidates: Vec<Name>,
 }
 
 #[derive(Debug)]
@@ -72,6 +82,34 @@ pub struct InvalidUseOfThis {
     pub expr: InFile<NodePtr>,
 }
 
+#[derive(Debug)]
+pub struct UnresolvedIdent {
+    pub expr: InFile<NodePtr>,
+    pub name: Name,
+    pub is_callee: bool,
+    /// Definition of the type of the parameter the identifier is passed to, if any.
+    pub expected_type: Option<DefResolution>,
+    pub(crate) owner: DefWithBodyId,
+}
+
+impl UnresolvedIdent {
+    /// Returns the names in scope which are close to the identifier, the closest first.
+    pub fn candidates(&self, db: &dyn HirDatabase) -> Vec<Name> {
+        let tree = db.parse(self.expr.file_id);
+        let Some(node) = self.expr.value.to_node(&tree) else {
+            return Vec::new();
+        };
+        let offset = TextSize::new(node.start_byte() as u32);
+        let analyzer = SourceAnalyzer::new_for_body_no_infer(
+            db,
+            self.owner,
+            InFile::new(self.expr.file_id, node),
+            Some(offset),
+        );
+        closest_names(&self.name.to_string(), analyzer.resolver.names_in_scope())
+    }
+}
+
 #[derive(Debug)]
 pub enum ConstructorDiagnosticKind {
     Methodmap,
@@ -83,6 +121,7 @@ pub struct UnresolvedNamedArg {
     pub expr: InFile<NodePtr>,
     pub name: Name,
     pub callee: Name,
+    pub candidates: Vec<Name>,
 }
 
 #[derive(Debug)]
@@ -91,6 +130,7 @@ pub struct IncorrectNumberOfArguments {
     pub name: Name,
     pub expected: usize,
     pub actual: usize,
+    pub missing_params: Vec<Name>,
 }
 
 #[derive(Debug)]
@@ -116,3 +156,45 @@ pub struct UnresolvedMacro {
 pub struct InactiveCode {
     pub range: TextRange,
 }
+
+#[derive(Debug)]
+pub struct UnusedVariable {
+    /// Name of the variable where it is declared.
+    pub expr: InFile<NodePtr>,
+    pub name: Name,
+    /// Function of which the variable is a parameter, if it is one.
+    pub param_of: Option<Function>,
+}
+
+#[derive(Debug)]
+pub struct TypeMismatch {
+    pub expr: InFile<NodePtr>,
+    pub expected: TypeRef,
+    pub actual: TypeRef,
+}
+
+#[derive(Debug)]
+pub struct CallbackSignatureMismatch {
+    pub expr: InFile<NodePtr>,
+    pub function: Function,
+    /// Type of the parameter the function is passed to.
+    pub expected: TypeRef,
+    /// Definition of the callback type, i.e. a typedef, a typeset, a functag or a funcenum.
+    pub expected_def: Option<DefResolution>,
+    /// How the signature differs, if the callback type has a single signature.
+    pub mismatch: Option<SignatureMismatch>,
+}
+
+#[derive(Debug)]
+pub struct UnreachableCode {
+    /// Range from the first unreachable statement of a block to the end of its last statement.
+    pub range: TextRange,
+}
+
+#[derive(Debug)]
+pub struct MissingReturn {
+    /// Name of the function.
+    pub expr: InFile<NodePtr>,
+    pub name: Name,
+    pub return_type: TypeRef,
+}
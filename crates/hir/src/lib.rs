@@ -3,12 +3,14 @@ use std::hash::Hash;
 
 use base_db::Tree;
 use db::HirDatabase;
+use fxhash::FxHashSet;
 use hir_def::{
     resolver::{HasResolver, ValueNs},
-    type_string_from_node, DefDiagnostic, DefWithBodyId, EnumId, EnumStructId, ExprId, FuncenumId,
-    FunctagId, FunctionId, FunctionKind, GlobalId, InFile, InferenceDiagnostic, ItemContainerId,
-    LocalFieldId, LocalStructFieldId, Lookup, MacroId, MethodmapExtension, MethodmapId, Name,
-    NodePtr, PropertyId, SpecialMethod, StructId, TypedefId, TypesetId, VariantId,
+    type_string_from_node, DefDiagnostic, DefWithBodyId, EnumId, EnumStructId, Expr, ExprId,
+    FuncenumId, FunctagId, FunctionId, FunctionKind, GlobalId, InFile, InferenceDiagnostic,
+    ItemContainerId, LocalFieldId, LocalStructFieldId, Lookup, MacroId, MethodmapExtension,
+    MethodmapId, MethodmapItemData, Name, NodePtr, PropertyId, RawVisibilityId, SpecialMethod,
+    StructId, TypeRef, TypedefId, TypesetId, VariantId,
 };
 use itertools::Itertools;
 use la_arena::RawIdx;
@@ -18,6 +20,7 @@ use serde::{
     ser::SerializeStruct,
     Deserialize, Deserializer, Serialize, Serializer,
 };
+use sourcepawn_lexer::{TextRange, TextSize};
 use stdx::impl_from;
 use syntax::TSKind;
 use tree_sitter::Node;
@@ -439,12 +442,14 @@ impl DefWithBody {
                     receiver,
                     name,
                     method_with_same_name_exists,
+                    candidates,
                 } => acc.push(
                     UnresolvedField {
                         expr: expr_syntax(*expr),
                         name: name.clone(),
                         receiver: receiver.clone(),
                         method_with_same_name_exists: *method_with_same_name_exists,
+                        candidates: candidates.clone(),
                     }
                     .into(),
                 ),
@@ -453,12 +458,14 @@ impl DefWithBody {
                     receiver,
                     name,
                     field_with_same_name_exists,
+                    candidates,
                 } => acc.push(
                     UnresolvedMethodCall {
                         expr: expr_syntax(*expr),
                         name: name.clone(),
                         receiver: receiver.clone(),
                         field_with_same_name_exists: *field_with_same_name_exists,
+                        candidates: candidates.clone(),
                     }
                     .into(),
                 ),
@@ -485,11 +492,17 @@ impl DefWithBody {
                         .into(),
                     )
                 }
-                InferenceDiagnostic::UnresolvedNamedArg { expr, name, callee } => acc.push(
+                InferenceDiagnostic::UnresolvedNamedArg {
+                    expr,
+                    name,
+                    callee,
+                    candidates,
+                } => acc.push(
                     UnresolvedNamedArg {
                         expr: expr_syntax(*expr),
                         name: name.clone(),
                         callee: callee.clone(),
+                        candidates: candidates.clone(),
                     }
                     .into(),
                 ),
@@ -498,12 +511,14 @@ impl DefWithBody {
                     name,
                     expected,
                     actual,
+                    missing_params,
                 } => acc.push(
                     IncorrectNumberOfArguments {
                         expr: expr_syntax(*expr),
                         name: name.clone(),
                         expected: *expected,
                         actual: *actual,
+                        missing_params: missing_params.clone(),
                     }
                     .into(),
                 ),
@@ -513,11 +528,323 @@ impl DefWithBody {
                     }
                     .into(),
                 ),
+                InferenceDiagnostic::UnresolvedIdent {
+                    expr,
+                    name,
+                    is_callee,
+                    expected_type,
+                } => {
+                    let expr = expr_syntax(*expr);
+                    let preprocessing_results = db.preprocess_file(expr.file_id);
+                    // A missing include already explains why the identifier cannot be found.
+                    if !preprocessing_results
+                        .errors()
+                        .unresolved_include_errors
+                        .is_empty()
+                    {
+                        continue;
+                    }
+                    // The identifier comes from a macro, the user can't fix it where it is used.
+                    if preprocessing_results
+                        .source_map()
+                        .is_in_expansion(TextSize::new(expr.value.start_byte() as u32))
+                    {
+                        continue;
+                    }
+                    let expected_type = expected_type.as_ref().and_then(|type_ref| {
+                        DefWithBodyId::from(self)
+                            .resolver(db.upcast())
+                            .resolve_ident(&type_ref.type_as_string())
+                            .and_then(DefResolution::try_from)
+                    });
+                    acc.push(
+                        UnresolvedIdent {
+                            expr,
+                            name: name.clone(),
+                            is_callee: *is_callee,
+                            expected_type,
+                            owner: self.into(),
+                        }
+                        .into(),
+                    )
+                }
+                InferenceDiagnostic::TypeMismatch {
+                    expr,
+                    expected,
+                    actual,
+                } => {
+                    let expr = expr_syntax(*expr);
+                    // The value comes from a macro, the user can't fix it where it is used.
+                    if db
+                        .preprocess_file(expr.file_id)
+                        .source_map()
+                        .is_in_expansion(TextSize::new(expr.value.start_byte() as u32))
+                    {
+                        continue;
+                    }
+                    acc.push(
+                        TypeMismatch {
+                            expr,
+                            expected: expected.clone(),
+                            actual: actual.clone(),
+                        }
+                        .into(),
+                    )
+                }
+                InferenceDiagnostic::CallbackSignatureMismatch {
+                    expr,
+                    function,
+                    expected,
+                    mismatch,
+                } => {
+                    let expected_def = DefWithBodyId::from(self)
+                        .resolver(db.upcast())
+                        .resolve_ident(&expected.type_as_string())
+                        .and_then(DefResolution::try_from);
+                    acc.push(
+                        CallbackSignatureMismatch {
+                            expr: expr_syntax(*expr),
+                            function: Function::from(*function),
+                            expected: expected.clone(),
+                            expected_def,
+                            mismatch: mismatch.clone(),
+                        }
+                        .into(),
+                    )
+                }
+            }
+        }
+
+        self.unused_variable_diagnostics(db, acc);
+        self.control_flow_diagnostics(db, acc);
+    }
+
+    /// Reports the locals which are never read, and the parameters of the functions which are
+    /// not `public` which are never read.
+    ///
+    /// Writing to a variable with `=` is not reading it. The names starting with `_` are
+    /// exempted.
+    fn unused_variable_diagnostics(self, db: &dyn HirDatabase, acc: &mut Vec<AnyDiagnostic>) {
+        let DefWithBody::Function(function) = self else {
+            return;
+        };
+        let (body, source_map) = db.body_with_source_map(self.into());
+        let Some(body_expr) = body.body_expr else {
+            return;
+        };
+        let infer = db.infer(self.into());
+
+        let written = body
+            .exprs
+            .iter()
+            .filter_map(|(_, expr)| match expr {
+                Expr::BinaryOp {
+                    lhs,
+                    op: Some(TSKind::anon_EQ),
+                    ..
+                } if matches!(body[*lhs], Expr::Ident(_)) => Some(*lhs),
+                _ => None,
+            })
+            .collect::<FxHashSet<_>>();
+        let read = infer
+            .local_resolutions()
+            .filter(|(expr, _)| !written.contains(expr))
+            .map(|(_, binding)| binding)
+            .collect::<FxHashSet<_>>();
+        let params = body
+            .params
+            .iter()
+            .map(|(_, binding)| *binding)
+            .collect::<FxHashSet<_>>();
+        let check_params = !function.is_public(db);
+
+        let Some(body_src) = source_map.expr_source(body_expr) else {
+            return;
+        };
+        let tree = db.parse(body_src.file_id);
+        let source = db.preprocessed_text(body_src.file_id);
+        let preprocessing_results = db.preprocess_file(body_src.file_id);
+        let Some(body_node) = body_src.value.to_node(&tree) else {
+            return;
+        };
+        for (binding, expr) in body.exprs.iter() {
+            let Expr::Binding { ident_id, .. } = expr else {
+                continue;
+            };
+            let is_param = params.contains(&binding);
+            if read.contains(&binding) || (is_param && !check_params) {
+                continue;
+            }
+            let name = body[*ident_id].clone();
+            if name.to_string().starts_with('_') {
+                continue;
+            }
+            let Some(name_node) = source_map
+                .expr_source(binding)
+                .and_then(|it| it.value.to_node(&tree))
+                .and_then(|it| it.child_by_field_name("name"))
+            else {
+                continue;
+            };
+            // The variable comes from a macro, the user can't fix it where it is declared.
+            if preprocessing_results
+                .source_map()
+                .is_in_expansion(TextSize::new(name_node.start_byte() as u32))
+            {
+                continue;
+            }
+            if has_unlowered_occurrence(&body_node, &name.to_string(), &source, |node| {
+                source_map
+                    .node_expr(InFile::new(body_src.file_id, node))
+                    .is_some()
+            }) {
+                continue;
+            }
+            acc.push(
+                UnusedVariable {
+                    expr: InFile::new(body_src.file_id, NodePtr::from(&name_node)),
+                    name,
+                    param_of: is_param.then_some(function),
+                }
+                .into(),
+            );
+        }
+    }
+
+    /// Reports the statements which can never be executed, and the functions which return a
+    /// value but can reach their end, or a `return;`, without returning one.
+    fn control_flow_diagnostics(self, db: &dyn HirDatabase, acc: &mut Vec<AnyDiagnostic>) {
+        let DefWithBody::Function(function) = self else {
+            return;
+        };
+        let (body, source_map) = db.body_with_source_map(self.into());
+        let Some(body_expr) = body.body_expr else {
+            return;
+        };
+        let Some(body_src) = source_map.expr_source(body_expr) else {
+            return;
+        };
+        let tree = db.parse(body_src.file_id);
+        let Some(body_node) = body_src.value.to_node(&tree) else {
+            return;
+        };
+        // Statements which fail to parse are not lowered, the graph would be incomplete.
+        if body_node.has_error() {
+            return;
+        }
+        let cfg = db.control_flow_graph(self.into());
+        let preprocessing_results = db.preprocess_file(body_src.file_id);
+        let from_macro = |start: usize| {
+            preprocessing_results
+                .source_map()
+                .is_in_expansion(TextSize::new(start as u32))
+        };
+
+        for (block, expr) in body.exprs.iter() {
+            let Expr::Block { statements, .. } = expr else {
+                continue;
+            };
+            if !cfg.is_reachable(block) {
+                // The enclosing block reports it.
+                continue;
             }
+            let statements = statements
+                .iter()
+                .filter_map(|it| Some((*it, source_map.expr_source(*it)?.value)))
+                .collect_vec();
+            let Some(first) = statements.iter().position(|(it, _)| !cfg.is_reachable(*it)) else {
+                continue;
+            };
+            let (start, end) = (
+                statements[first].1.start_byte(),
+                statements[statements.len() - 1].1.end_byte(),
+            );
+            if from_macro(start) {
+                continue;
+            }
+            acc.push(
+                UnreachableCode {
+                    range: TextRange::new(TextSize::new(start as u32), TextSize::new(end as u32)),
+                }
+                .into(),
+            );
+        }
+
+        let Some(return_type) = db.function_data(function.id).type_ref.clone() else {
+            return;
+        };
+        if return_type.type_as_string() == "void" {
+            return;
         }
+        let returns_nothing = body.exprs.iter().any(|(expr, it)| {
+            matches!(
+                it,
+                Expr::Control {
+                    keyword: TSKind::anon_return_,
+                    ..
+                }
+            ) && cfg.is_reachable(expr)
+                && source_map
+                    .expr_source(expr)
+                    .and_then(|it| it.value.to_node(&tree))
+                    .is_some_and(|it| it.child_by_field_name("expression").is_none())
+        });
+        if !cfg.falls_through() && !returns_nothing {
+            return;
+        }
+        // The name of a property getter is the `get` keyword, in a child of the method.
+        let Some(name_node) = body_node.parent().and_then(|parent| {
+            parent.child_by_field_name("name").or_else(|| {
+                parent
+                    .named_children(&mut parent.walk())
+                    .find_map(|it| it.child_by_field_name("name"))
+            })
+        }) else {
+            return;
+        };
+        if from_macro(name_node.start_byte()) {
+            return;
+        }
+        acc.push(
+            MissingReturn {
+                expr: InFile::new(body_src.file_id, NodePtr::from(&name_node)),
+                name: function.name(db),
+                return_type,
+            }
+            .into(),
+        );
     }
 }
 
+/// Whether `name` appears in `node` as an identifier which is neither the name of a declaration
+/// nor lowered to an expression, e.g. because the expression is not supported by the lowering.
+///
+/// Such an occurrence may read the variable, which is then not reported as unused.
+fn has_unlowered_occurrence(
+    node: &Node,
+    name: &str,
+    source: &str,
+    is_lowered: impl Fn(&Node) -> bool + Copy,
+) -> bool {
+    if TSKind::from(node) == TSKind::identifier {
+        let is_declaration_name = node.parent().is_some_and(|parent| {
+            matches!(
+                TSKind::from(parent),
+                TSKind::variable_declaration
+                    | TSKind::old_variable_declaration
+                    | TSKind::dynamic_array_declaration
+            ) && parent
+                .child_by_field_name("name")
+                .is_some_and(|it| it.id() == node.id())
+        });
+        return !is_declaration_name
+            && node.utf8_text(source.as_bytes()).ok() == Some(name)
+            && !is_lowered(node);
+    }
+    node.named_children(&mut node.walk())
+        .any(|child| has_unlowered_occurrence(&child, name, source, is_lowered))
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum FunctionType {
     Function,
@@ -542,6 +869,18 @@ impl Function {
         db.function_data(self.id).name.clone()
     }
 
+    /// Returns whether the function is implemented, i.e. is neither a forward nor a native.
+    pub fn is_def(self, db: &dyn HirDatabase) -> bool {
+        db.function_data(self.id).kind == FunctionKind::Def
+    }
+
+    /// Returns whether the function is `public`, i.e. whether SourceMod can call it.
+    pub fn is_public(self, db: &dyn HirDatabase) -> bool {
+        db.function_data(self.id)
+            .visibility
+            .contains(RawVisibilityId::PUBLIC)
+    }
+
     pub fn type_ref(self, db: &dyn HirDatabase) -> Option<String> {
         db.function_data(self.id)
             .type_ref
@@ -847,6 +1186,15 @@ impl Methodmap {
         buf.into()
     }
 
+    /// Returns the constructor of the methodmap, if it has one.
+    pub fn constructor(self, db: &dyn HirDatabase) -> Option<Function> {
+        let data = db.methodmap_data(self.id);
+        match &data.items[data.constructor?] {
+            MethodmapItemData::Constructor(id) => Some(Function::from(*id)),
+            _ => None,
+        }
+    }
+
     pub fn type_def(self, db: &dyn HirDatabase) -> Vec<DefResolution> {
         let mut res = Vec::new();
         if let Some(inherits) = db.methodmap_data(self.id).inherits {
@@ -1154,6 +1502,24 @@ impl Typedef {
         buf.into()
     }
 
+    /// Returns the definition of a function named `name` which matches the typedef.
+    pub fn as_stub(self, db: &dyn HirDatabase, name: &str) -> Option<String> {
+        let loc = self.id.lookup(db.upcast());
+        let source = db.preprocessed_text(loc.id.file_id());
+        let tree = db.parse(loc.id.file_id());
+        let node = self.source(db, &tree)?.value;
+        let typedef_expr = if TSKind::from(&node) == TSKind::typedef_expression {
+            node
+        } else {
+            node.children(&mut node.walk())
+                .find(|n| TSKind::from(n) == TSKind::typedef_expression)?
+        };
+        let params = typedef_expr.child_by_field_name("parameters")?;
+        let return_type = db.typedef_data(self.id).type_ref.clone();
+
+        function_stub(name, Some(&return_type), params, &source).into()
+    }
+
     /// Returns whether the typedef is deprecated.
     ///
     /// This method is "fast" as it does not do a lookup of the node in the tree.
@@ -1200,6 +1566,10 @@ pub struct Functag {
 }
 
 impl Functag {
+    pub fn id(self) -> FunctagId {
+        self.id
+    }
+
     pub fn name(self, db: &dyn HirDatabase) -> Option<Name> {
         db.functag_data(self.id).name.clone()
     }
@@ -1346,6 +1716,18 @@ impl Functag {
         buf.into()
     }
 
+    /// Returns the definition of a function named `name` which matches the functag.
+    pub fn as_stub(self, db: &dyn HirDatabase, name: &str) -> Option<String> {
+        let loc = self.id.lookup(db.upcast());
+        let source = db.preprocessed_text(loc.id.file_id());
+        let tree = db.parse(loc.id.file_id());
+        let node = self.source(db, &tree)?.value;
+        let params = node.child_by_field_name("parameters")?;
+        let return_type = db.functag_data(self.id).type_ref.clone();
+
+        function_stub(name, return_type.as_ref(), params, &source).into()
+    }
+
     /// Returns whether the functag is deprecated.
     ///
     /// This method is "fast" as it does not do a lookup of the node in the tree.
@@ -1354,6 +1736,35 @@ impl Functag {
     }
 }
 
+/// Returns the definition of a public function with the given return type and parameters, whose
+/// body returns a default value.
+fn function_stub(name: &str, return_type: Option<&TypeRef>, params: Node, source: &str) -> String {
+    let params = params
+        .children(&mut params.walk())
+        .filter(|n| {
+            matches!(
+                TSKind::from(n),
+                TSKind::parameter_declaration | TSKind::rest_parameter
+            )
+        })
+        .filter_map(|param| param.utf8_text(source.as_bytes()).ok())
+        .join(", ");
+    let signature = match return_type {
+        // Old syntax tags are followed by a colon.
+        Some(type_ref @ TypeRef::OldName(_)) => format!("public {type_ref}{name}({params})"),
+        Some(type_ref) => format!("public {type_ref} {name}({params})"),
+        None => format!("public {name}({params})"),
+    };
+    let return_value = return_type
+        .map_or(TypeRef::Int, TypeRef::to_new_syntax)
+        .default_value();
+
+    match return_value {
+        Some(value) => format!("{signature}\n{{\n\treturn {value};\n}}"),
+        None => format!("{signature}\n{{\n}}"),
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Funcenum {
     pub(crate) id: FuncenumId,
@@ -1580,6 +1991,13 @@ impl Global {
         db.global_data(self.id).name().clone()
     }
 
+    /// Returns whether the global is `public`, i.e. whether SourceMod can read it.
+    pub fn is_public(self, db: &dyn HirDatabase) -> bool {
+        db.global_data(self.id)
+            .visibility()
+            .contains(RawVisibilityId::PUBLIC)
+    }
+
     pub fn render(self, db: &dyn HirDatabase) -> Option<String> {
         let data = db.global_data(self.id);
 
@@ -1589,17 +2007,34 @@ impl Global {
             buf.push(' ');
         }
         if let Some(type_ref) = data.type_ref() {
+            // The dimensions are rendered after the name, as they are declared.
+            let type_ref = match type_ref {
+                TypeRef::Array((type_ref, _)) if !data.dimensions().is_empty() => type_ref,
+                type_ref => type_ref,
+            };
             buf.push_str(&type_ref.to_string());
             if !buf.ends_with(':') {
                 buf.push(' ');
             }
         }
         buf.push_str(&self.name(db).to_string());
+        for size in data.dimensions() {
+            match size {
+                Some(size) => buf.push_str(&format!("[{size}]")),
+                None => buf.push_str("[]"),
+            }
+        }
         buf.push(';');
 
         buf.into()
     }
 
+    /// Returns the sizes of the dimensions of the global if it is an array, e.g. `[Some(64)]`
+    /// for `char name[64]`.
+    pub fn dimensions(self, db: &dyn HirDatabase) -> Vec<Option<u32>> {
+        db.global_data(self.id).dimensions().to_vec()
+    }
+
     pub fn type_(self, db: &dyn HirDatabase) -> Option<DefResolution> {
         let ty = db.global_data(self.id).type_ref().cloned()?;
         let ty_str = ty.type_as_string();
@@ -1884,6 +2319,15 @@ impl<'tree> Local {
         node.utf8_text(source.as_bytes()).ok().map(Name::from)
     }
 
+    /// Returns the sizes of the dimensions of the local if it is an array, e.g. `[Some(64)]`
+    /// for `char name[64]`.
+    pub fn dimensions(self, db: &dyn HirDatabase) -> Vec<Option<u32>> {
+        match &db.body(self.parent)[self.expr_id] {
+            Expr::Binding { dimensions, .. } => dimensions.to_vec(),
+            _ => Vec::new(),
+        }
+    }
+
     pub fn render(self, db: &dyn HirDatabase) -> Option<String> {
         let file_id = self.parent.file_id(db.upcast());
         let tree = db.parse(file_id);
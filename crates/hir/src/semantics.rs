@@ -6,7 +6,7 @@ use base_db::{is_field_receiver_node, is_name_node, FilePosition, FileRange, Tre
 use hir_def::{
     resolve_include_node,
     resolver::{global_resolver, HasResolver, ValueNs},
-    FileDefId, FunctionId, InFile, Name, NodePtr, PropertyItem,
+    FileDefId, FunctionId, InFile, Name, NodePtr, PropertyItem, TypeRef,
 };
 use itertools::Itertools;
 use lazy_static::lazy_static;
@@ -30,7 +30,7 @@ use crate::{
 /// Primary API to get semantic information, like types, from syntax trees.
 ///
 /// For now, it only allows to get from a node in a tree-sitter CST, to a definition.
-pub struct Semantics<'db, DB> {
+pub struct Semantics<'db, DB: ?Sized> {
     pub db: &'db DB,
     imp: SemanticsImpl<'db>,
 }
@@ -40,13 +40,13 @@ pub struct SemanticsImpl<'db> {
     s2d_cache: RefCell<SourceToDefCache>,
 }
 
-impl<DB> fmt::Debug for Semantics<'_, DB> {
+impl<DB: ?Sized> fmt::Debug for Semantics<'_, DB> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "Semantics {{ ... }}")
     }
 }
 
-impl<'db, DB> ops::Deref for Semantics<'db, DB> {
+impl<'db, DB: ?Sized> ops::Deref for Semantics<'db, DB> {
     type Target = SemanticsImpl<'db>;
 
     fn deref(&self) -> &Self::Target {
@@ -59,7 +59,19 @@ impl<DB: HirDatabase> Semantics<'_, DB> {
         let impl_ = SemanticsImpl::new(db);
         Semantics { db, imp: impl_ }
     }
+}
 
+impl<'db> Semantics<'db, dyn HirDatabase + 'db> {
+    /// Semantics over a database trait object, e.g. in the implementation of a query.
+    pub fn new_dyn(db: &'db dyn HirDatabase) -> Self {
+        Semantics {
+            db,
+            imp: SemanticsImpl::new(db),
+        }
+    }
+}
+
+impl<DB: HirDatabase + ?Sized> Semantics<'_, DB> {
     pub fn parse(&self, file_id: FileId) -> Tree {
         self.db.parse(file_id)
     }
@@ -143,7 +155,7 @@ impl<DB: HirDatabase> Semantics<'_, DB> {
             TSKind::preproc_undefine => {
                 let source = self.file_text(file_id);
                 let ValueNs::MacroId(id) = file_id
-                    .resolver(self.db)
+                    .resolver(self.imp.db)
                     .resolve_ident(node.utf8_text(source.as_bytes()).ok()?)?
                 else {
                     return None;
@@ -228,7 +240,7 @@ impl<DB: HirDatabase> Semantics<'_, DB> {
             }
         }
         self.find_def(file_id, &node)
-            .and_then(|def| def.type_def(self.db))
+            .and_then(|def| def.type_def(self.imp.db))
     }
 
     /// Find a definition given a reference node.
@@ -328,7 +340,7 @@ impl<DB: HirDatabase> Semantics<'_, DB> {
         node: tree_sitter::Node,
         source: Arc<str>,
     ) -> Option<DefResolution> {
-        let resolver = global_resolver(self.db, file_id);
+        let resolver = global_resolver(self.imp.db, file_id);
         let text = node.utf8_text(source.as_ref().as_bytes()).ok()?;
         match resolver.resolve_ident(text)? {
             ValueNs::FunctionId(ids) => ids
@@ -356,7 +368,7 @@ impl<DB: HirDatabase> Semantics<'_, DB> {
         node: tree_sitter::Node,
         source: Arc<str>,
     ) -> Option<DefResolution> {
-        let (id, ..) = resolve_include_node(self.db, file_id, source.as_ref(), node)?;
+        let (id, ..) = resolve_include_node(self.imp.db, file_id, source.as_ref(), node)?;
         id.map(|id| DefResolution::File(id.into()))
     }
 
@@ -472,7 +484,7 @@ impl<DB: HirDatabase> Semantics<'_, DB> {
         let def = hir_def::DefWithBodyId::FunctionId(id);
         let Some(body_node) = container.child_by_field_name("body") else {
             // If the function has no body, try to resolve params and return type.
-            let analyzer = SourceAnalyzer::new_no_body_no_infer(self.db, def, file_id);
+            let analyzer = SourceAnalyzer::new_no_body_no_infer(self.imp.db, def, file_id);
             return DefResolution::try_from(analyzer.resolver.resolve_ident(text)?);
         };
         debug_assert_eq!(TSKind::from(body_node), TSKind::block);
@@ -480,18 +492,18 @@ impl<DB: HirDatabase> Semantics<'_, DB> {
         match TSKind::from(parent) {
             TSKind::field_access | TSKind::scope_access if is_field_receiver_node(&node) => {
                 let analyzer = SourceAnalyzer::new_for_body(
-                    self.db,
+                    self.imp.db,
                     def,
                     InFile::new(file_id, body_node),
                     Some(offset),
                 );
                 if let Some(grand_parent) = parent.parent() {
                     if TSKind::call_expression == TSKind::from(&grand_parent) {
-                        let method = analyzer.resolve_method(self.db, &node, &parent)?;
+                        let method = analyzer.resolve_method(self.imp.db, &node, &parent)?;
                         return Some(DefResolution::Function(method));
                     }
                 }
-                match analyzer.resolve_attribute(self.db, &node, &parent)? {
+                match analyzer.resolve_attribute(self.imp.db, &node, &parent)? {
                     Attribute::Field(field) => return Some(DefResolution::Field(field)),
                     Attribute::Property(property) => {
                         return Some(DefResolution::Property(property))
@@ -500,23 +512,23 @@ impl<DB: HirDatabase> Semantics<'_, DB> {
             }
             TSKind::new_expression => {
                 let analyzer = SourceAnalyzer::new_for_body(
-                    self.db,
+                    self.imp.db,
                     def,
                     InFile::new(file_id, body_node),
                     Some(offset),
                 );
-                let constructor = analyzer.resolve_constructor(self.db, &node, &parent)?;
+                let constructor = analyzer.resolve_constructor(self.imp.db, &node, &parent)?;
                 return Some(DefResolution::Function(constructor));
             }
             TSKind::named_arg => {
                 let analyzer = SourceAnalyzer::new_for_body(
-                    self.db,
+                    self.imp.db,
                     def,
                     InFile::new(file_id, body_node),
                     Some(offset),
                 );
 
-                if let Some(arg) = analyzer.resolve_named_arg(self.db, &node, &parent) {
+                if let Some(arg) = analyzer.resolve_named_arg(self.imp.db, &node, &parent) {
                     // Only return if we find an argument. If we don't we were trying to resolve the value.
                     return Some(arg.into());
                 }
@@ -525,7 +537,7 @@ impl<DB: HirDatabase> Semantics<'_, DB> {
         }
 
         let analyzer = SourceAnalyzer::new_for_body_no_infer(
-            self.db,
+            self.imp.db,
             def,
             InFile::new(file_id, body_node),
             Some(offset),
@@ -572,7 +584,7 @@ impl<DB: HirDatabase> Semantics<'_, DB> {
             FileDefId::TypedefId(id) => {
                 let def = hir_def::DefWithBodyId::TypedefId(id);
                 let text = node.utf8_text(source.as_ref().as_bytes()).ok()?;
-                let analyzer = SourceAnalyzer::new_no_body_no_infer(self.db, def, file_id);
+                let analyzer = SourceAnalyzer::new_no_body_no_infer(self.imp.db, def, file_id);
                 DefResolution::try_from(analyzer.resolver.resolve_ident(text)?)
             }
             _ => None,
@@ -596,7 +608,7 @@ impl<DB: HirDatabase> Semantics<'_, DB> {
             FileDefId::FunctagId(id) => {
                 let def = hir_def::DefWithBodyId::FunctagId(id);
                 let text = node.utf8_text(source.as_ref().as_bytes()).ok()?;
-                let analyzer = SourceAnalyzer::new_no_body_no_infer(self.db, def, file_id);
+                let analyzer = SourceAnalyzer::new_no_body_no_infer(self.imp.db, def, file_id);
                 DefResolution::try_from(analyzer.resolver.resolve_ident(text)?)
             }
             _ => None,
@@ -610,7 +622,7 @@ impl<DB: HirDatabase> Semantics<'_, DB> {
         node: tree_sitter::Node,
         source: Arc<str>,
     ) -> Option<DefResolution> {
-        let resolver = global_resolver(self.db, file_id);
+        let resolver = global_resolver(self.imp.db, file_id);
         let struct_name = container
             .child_by_field_name("type")?
             .utf8_text(source.as_bytes())
@@ -621,7 +633,7 @@ impl<DB: HirDatabase> Semantics<'_, DB> {
         let struct_: Struct = struct_.value.into();
         let name = node.utf8_text(source.as_bytes()).ok()?;
 
-        DefResolution::StructField(struct_.field(self.db, name)?).into()
+        DefResolution::StructField(struct_.field(self.imp.db, name)?).into()
     }
 
     pub fn to_file_def(&self, file_id: FileId) -> File {
@@ -629,7 +641,7 @@ impl<DB: HirDatabase> Semantics<'_, DB> {
     }
 
     pub fn defs_in_scope(&self, file_id: FileId) -> Vec<DefResolution> {
-        let resolver = global_resolver(self.db, file_id);
+        let resolver = global_resolver(self.imp.db, file_id);
         resolver
             .available_defs()
             .into_iter()
@@ -644,7 +656,7 @@ impl<DB: HirDatabase> Semantics<'_, DB> {
         body_node: tree_sitter::Node,
     ) -> Vec<DefResolution> {
         let analyzer = SourceAnalyzer::new_for_body_no_infer(
-            self.db,
+            self.imp.db,
             hir_def::DefWithBodyId::FunctionId(def),
             InFile::new(pos.file_id, body_node),
             Some(pos.offset),
@@ -657,6 +669,39 @@ impl<DB: HirDatabase> Semantics<'_, DB> {
             .collect()
     }
 
+    /// Returns the inferred type of the expression of the node, if it is in the body of a
+    /// function or a method.
+    pub fn type_of_expr(&self, file_id: FileId, node: &tree_sitter::Node) -> Option<TypeRef> {
+        // The accesses are recorded on the name of the field.
+        let node = match TSKind::from(node) {
+            TSKind::field_access | TSKind::scope_access | TSKind::array_scope_access => {
+                node.child_by_field_name("field")?
+            }
+            _ => *node,
+        };
+        let mut container = node.parent()?;
+        while !matches!(
+            TSKind::from(container),
+            TSKind::function_definition
+                | TSKind::enum_struct_method
+                | TSKind::methodmap_method
+                | TSKind::methodmap_method_constructor
+                | TSKind::methodmap_method_destructor
+                | TSKind::methodmap_property_method
+        ) {
+            container = container.parent()?;
+        }
+        let body_node = container.child_by_field_name("body")?;
+        let def = self.fn_to_def(InFile::new(file_id, NodePtr::from(&container)))?;
+        let analyzer = SourceAnalyzer::new_for_body(
+            self.imp.db,
+            hir_def::DefWithBodyId::FunctionId(def),
+            InFile::new(file_id, body_node),
+            None,
+        );
+        analyzer.type_of_expr(self.imp.db, &node)
+    }
+
     /// Find references to the definition at the given [`FilePosition`].
     /// Handles both macros and regular definitions.
     ///
@@ -740,7 +785,7 @@ impl<DB: HirDatabase> Semantics<'_, DB> {
     /// A tuple containing the definition of the macro and a list of [`user seen FileRanges`](FileRange).
     fn find_macro_references(&self, fpos: FilePosition) -> Option<(DefResolution, Vec<FileRange>)> {
         let (_, def) = self.find_macro_def(&fpos)?;
-        let name = def.name(self.db).map(|it| it.to_smolstr())?;
+        let name = def.name(self.imp.db).map(|it| it.to_smolstr())?;
         let graph = self.db.projet_subgraph(fpos.file_id)?;
         let mut res = Vec::new();
         for graph_node in graph.nodes.iter() {
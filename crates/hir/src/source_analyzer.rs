@@ -7,7 +7,7 @@ use hir_def::{
         Body, BodySourceMap,
     },
     resolver::{resolver_for_scope, HasResolver, Resolver},
-    DefWithBodyId, ExprId, InFile, InferenceResult,
+    DefWithBodyId, ExprId, InFile, InferenceResult, TypeRef,
 };
 use sourcepawn_lexer::TextSize;
 use syntax::TSKind;
@@ -159,6 +159,16 @@ impl SourceAnalyzer {
 
         Some(Local { parent, expr_id })
     }
+
+    pub(crate) fn type_of_expr(
+        &self,
+        db: &dyn HirDatabase,
+        node: &tree_sitter::Node,
+    ) -> Option<TypeRef> {
+        let src = InFile::new(self.file_id, node);
+        let expr_id = self.expr_id(db, src)?;
+        self.infer.as_ref()?.type_of_expr(expr_id).cloned()
+    }
 }
 
 fn scope_for(
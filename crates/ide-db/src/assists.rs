@@ -0,0 +1,109 @@
+//! Types shared by the assists of `ide` and the fixes of `ide-diagnostics`.
+
+use line_index::TextRange;
+
+use crate::SourceChange;
+
+#[derive(Debug, Clone)]
+pub struct Assist {
+    pub id: AssistId,
+    /// Short description of the assist, as shown in the UI.
+    pub label: String,
+    /// Target ranges are used to sort assists: the smaller the target range,
+    /// the more specific the assist is, and so it should be sorted first.
+    pub target: TextRange,
+    /// Computing the source change is more costly than computing the other fields, and it is
+    /// only needed when the user applies the assist, so it is computed lazily.
+    pub source_change: Option<SourceChange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssistKind {
+    QuickFix,
+    Generate,
+    Refactor,
+    RefactorExtract,
+    RefactorInline,
+    RefactorRewrite,
+}
+
+impl AssistKind {
+    /// Whether an assist of kind `other` is requested when asking for assists of this kind.
+    pub fn contains(self, other: AssistKind) -> bool {
+        if self == other {
+            return true;
+        }
+
+        match self {
+            AssistKind::Generate => true,
+            AssistKind::Refactor => matches!(
+                other,
+                AssistKind::RefactorExtract
+                    | AssistKind::RefactorInline
+                    | AssistKind::RefactorRewrite
+            ),
+            _ => false,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AssistKind::QuickFix => "QuickFix",
+            AssistKind::Generate => "Generate",
+            AssistKind::Refactor => "Refactor",
+            AssistKind::RefactorExtract => "RefactorExtract",
+            AssistKind::RefactorInline => "RefactorInline",
+            AssistKind::RefactorRewrite => "RefactorRewrite",
+        }
+    }
+}
+
+impl std::str::FromStr for AssistKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "QuickFix" => Ok(AssistKind::QuickFix),
+            "Generate" => Ok(AssistKind::Generate),
+            "Refactor" => Ok(AssistKind::Refactor),
+            "RefactorExtract" => Ok(AssistKind::RefactorExtract),
+            "RefactorInline" => Ok(AssistKind::RefactorInline),
+            "RefactorRewrite" => Ok(AssistKind::RefactorRewrite),
+            unknown => Err(format!("Unknown AssistKind: '{unknown}'")),
+        }
+    }
+}
+
+/// Unique identifier of the assist, should not be shown to the user directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssistId(pub &'static str, pub AssistKind);
+
+/// Which assists should have their source change computed.
+#[derive(Debug, Clone, Default)]
+pub enum AssistResolveStrategy {
+    /// No assist is resolved.
+    #[default]
+    None,
+    /// All assists are resolved.
+    All,
+    /// Only the assist with the given id and kind is resolved.
+    Single(SingleResolve),
+}
+
+#[derive(Debug, Clone)]
+pub struct SingleResolve {
+    pub assist_id: String,
+    pub assist_kind: AssistKind,
+}
+
+impl AssistResolveStrategy {
+    pub fn should_resolve(&self, id: &AssistId) -> bool {
+        match self {
+            AssistResolveStrategy::None => false,
+            AssistResolveStrategy::All => true,
+            AssistResolveStrategy::Single(single) => {
+                single.assist_id == id.0 && single.assist_kind == id.1
+            }
+        }
+    }
+}
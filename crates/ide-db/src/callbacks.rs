@@ -0,0 +1,66 @@
+//! Functions which are used as values, e.g. passed to `CreateTimer`, and whose signature is
+//! therefore imposed by the typedef of the callback.
+
+use std::sync::Arc;
+
+use base_db::Upcast;
+use fxhash::FxHashSet;
+use hir::{db::HirDatabase, DefResolution, Function, Semantics};
+use syntax::TSKind;
+use tree_sitter::Node;
+use vfs::FileId;
+
+#[salsa::query_group(CallbacksDatabaseStorage)]
+pub trait CallbacksDatabase: HirDatabase + Upcast<dyn HirDatabase> {
+    /// The functions referenced by the file elsewhere than in a call or in their declaration.
+    fn file_callbacks(&self, file_id: FileId) -> Arc<FxHashSet<Function>>;
+}
+
+fn file_callbacks(db: &dyn CallbacksDatabase, file_id: FileId) -> Arc<FxHashSet<Function>> {
+    let sema = Semantics::new_dyn(db.upcast());
+    let tree = sema.parse(file_id);
+    let mut res = FxHashSet::default();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if TSKind::from(node) != TSKind::identifier {
+            stack.extend(node.named_children(&mut node.walk()));
+            continue;
+        }
+        if is_call_or_definition(node) {
+            continue;
+        }
+        if let Some(DefResolution::Function(function)) = sema.find_def(file_id, &node) {
+            res.insert(function);
+        }
+    }
+
+    Arc::new(res)
+}
+
+fn is_call_or_definition(node: Node) -> bool {
+    let is_field = |parent: &Node, field: &str| {
+        parent
+            .child_by_field_name(field)
+            .is_some_and(|it| it.id() == node.id())
+    };
+    let Some(parent) = node.parent() else {
+        return false;
+    };
+    match TSKind::from(parent) {
+        TSKind::call_expression => is_field(&parent, "function"),
+        TSKind::field_access => {
+            is_field(&parent, "field")
+                && parent.parent().is_some_and(|grandparent| {
+                    TSKind::from(grandparent) == TSKind::call_expression
+                        && grandparent
+                            .child_by_field_name("function")
+                            .is_some_and(|it| it.id() == parent.id())
+                })
+        }
+        TSKind::function_definition
+        | TSKind::function_declaration
+        | TSKind::enum_struct_method
+        | TSKind::methodmap_method => is_field(&parent, "name"),
+        _ => false,
+    }
+}
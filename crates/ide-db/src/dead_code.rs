@@ -0,0 +1,265 @@
+//! Detection of the declarations which are never referenced in a project.
+//!
+//! What each file references is a salsa query, and so is the union of these for each project,
+//! keyed by the root of the project: checking a file only resolves the identifiers of the files
+//! which changed since the last check.
+
+use std::sync::Arc;
+
+use base_db::{is_name_node, FileExtension, SourceDatabase, SourceDatabaseExt, SubGraph, Upcast};
+use fxhash::FxHashSet;
+use hir::{db::HirDatabase, DefResolution, FileDef, HasSource, Semantics};
+use hir_def::DefDatabase;
+use line_index::TextRange;
+use smol_str::SmolStr;
+use sourcepawn_lexer::{PreprocDir, SourcepawnLexer, TokenKind};
+use syntax::{utils::ts_range_to_text_range, TSKind};
+use vfs::FileId;
+
+use crate::{RootDatabase, SymbolKind};
+
+#[salsa::query_group(DeadCodeDatabaseStorage)]
+pub trait DeadCodeDatabase: HirDatabase + SourceDatabaseExt + Upcast<dyn HirDatabase> {
+    /// The declarations referenced by the file, and the names it uses without resolving them.
+    fn file_references(&self, file_id: FileId) -> Arc<References>;
+
+    /// The [`References`] of all the files of the project whose main file is `root`.
+    fn project_references(&self, root: FileId) -> Arc<References>;
+
+    /// The main files of the projects which include the file.
+    fn project_roots(&self, file_id: FileId) -> Arc<Vec<FileId>>;
+}
+
+/// What is referenced by a file, or by all the files of a project.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct References {
+    /// Functions, globals, enum entries and macros which are resolved from an identifier.
+    defs: FxHashSet<DefResolution>,
+    /// Identifiers of the text of the file, except the names of the macro definitions. The
+    /// macros are referenced by name.
+    names: FxHashSet<SmolStr>,
+    /// Identifiers of the inactive code of the file.
+    inactive_names: FxHashSet<SmolStr>,
+}
+
+impl References {
+    fn contains(&self, def: &DefResolution, dead_code: &DeadCode) -> bool {
+        self.defs.contains(def)
+            || (dead_code.kind == SymbolKind::Macro && self.names.contains(dead_code.name.as_str()))
+            || self.inactive_names.contains(dead_code.name.as_str())
+    }
+}
+
+fn file_references(db: &dyn DeadCodeDatabase, file_id: FileId) -> Arc<References> {
+    let sema = Semantics::new_dyn(db.upcast());
+    let mut res = References::default();
+    let tree = sema.parse(file_id);
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if TSKind::from(node) != TSKind::identifier {
+            stack.extend(node.named_children(&mut node.walk()));
+            continue;
+        }
+        if is_name_node(&node) {
+            continue;
+        }
+        if let Some(
+            def @ (DefResolution::Function(_)
+            | DefResolution::Global(_)
+            | DefResolution::Variant(_)
+            | DefResolution::Macro(_)),
+        ) = sema.find_def(file_id, &node)
+        {
+            res.defs.insert(def);
+        }
+    }
+
+    let file_text = sema.file_text(file_id);
+    let preprocessing_results = sema.preprocess_file(file_id);
+    let inactive_ranges = preprocessing_results.inactive_ranges();
+    let mut after_define = false;
+    for symbol in SourcepawnLexer::new(&file_text) {
+        match symbol.token_kind {
+            TokenKind::PreprocDir(PreprocDir::MDefine) => after_define = true,
+            TokenKind::Identifier => {
+                let is_definition = std::mem::take(&mut after_define);
+                if inactive_ranges
+                    .iter()
+                    .any(|range| range.contains_range(symbol.range))
+                {
+                    res.inactive_names.insert(symbol.text());
+                } else if !is_definition {
+                    res.names.insert(symbol.text());
+                }
+            }
+            _ => (),
+        }
+    }
+
+    Arc::new(res)
+}
+
+fn project_references(db: &dyn DeadCodeDatabase, root: FileId) -> Arc<References> {
+    let mut res = References::default();
+    let Some(subgraph) = db.projet_subgraph(root) else {
+        return Arc::new(res);
+    };
+    for file_id in subgraph.file_ids() {
+        let references = db.file_references(file_id);
+        res.defs.extend(references.defs.iter().cloned());
+        res.names.extend(references.names.iter().cloned());
+        res.inactive_names
+            .extend(references.inactive_names.iter().cloned());
+    }
+
+    Arc::new(res)
+}
+
+fn project_roots(db: &dyn DeadCodeDatabase, file_id: FileId) -> Arc<Vec<FileId>> {
+    let mut res = db
+        .graph()
+        .find_subgraphs()
+        .into_iter()
+        .filter(|it| it.root.extension == FileExtension::Sp && it.contains_file(file_id))
+        .map(|it| it.root.file_id)
+        .collect::<Vec<_>>();
+    res.sort();
+
+    Arc::new(res)
+}
+
+/// A declaration which is never referenced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadCode {
+    pub file_id: FileId,
+    /// Range of the name of the declaration in the preprocessed text.
+    pub s_range: TextRange,
+    pub name: String,
+    pub kind: SymbolKind,
+}
+
+/// Returns the declarations of the file which are never referenced in the projects including
+/// it.
+///
+/// The files which are not included by any plugin and the files of the include directories are
+/// never checked.
+pub fn dead_code(sema: &Semantics<RootDatabase>, file_id: FileId) -> Vec<DeadCode> {
+    if !is_local(sema.db, file_id) {
+        return Vec::new();
+    }
+    // A file can be shared by several plugins, and it is then enough that one of them uses a
+    // declaration.
+    let roots = sema.db.project_roots(file_id);
+    if roots.is_empty() {
+        return Vec::new();
+    }
+    let scope = roots
+        .iter()
+        .filter_map(|&root| sema.db.projet_subgraph(root))
+        .flat_map(|it| it.file_ids())
+        .collect::<FxHashSet<_>>();
+
+    find_dead_code(sema, &scope, &roots, &[file_id])
+}
+
+/// Returns the declarations of the files of the project which are never referenced in it.
+///
+/// The files of the include directories are left out, as they are not part of the project.
+pub fn project_dead_code(sema: &Semantics<RootDatabase>, subgraph: &SubGraph) -> Vec<DeadCode> {
+    let scope = subgraph.file_ids();
+    let mut file_ids = scope
+        .iter()
+        .copied()
+        .filter(|id| is_local(sema.db, *id))
+        .collect::<Vec<_>>();
+    file_ids.sort();
+
+    find_dead_code(sema, &scope, &[subgraph.root.file_id], &file_ids)
+}
+
+/// Returns the declarations of the files which are not referenced in any of the projects.
+///
+/// Only the functions which are neither `public` nor the implementation of a forward, the
+/// globals which are not `public`, the enum entries and the macros are reported.
+fn find_dead_code(
+    sema: &Semantics<RootDatabase>,
+    scope: &FxHashSet<FileId>,
+    roots: &[FileId],
+    file_ids: &[FileId],
+) -> Vec<DeadCode> {
+    let db = sema.db;
+    let mut candidates = Vec::new();
+    for &file_id in file_ids {
+        let tree = sema.parse(file_id);
+        let source = sema.preprocessed_text(file_id);
+        for def in sema.to_file_def(file_id).declarations(db) {
+            let (def, kind) = match def {
+                FileDef::Function(it)
+                    if it.is_def(db)
+                        && !it.is_public(db)
+                        && !implements_declaration(sema, scope, &it.name(db).to_string()) =>
+                {
+                    (DefResolution::Function(it), SymbolKind::Function)
+                }
+                FileDef::Global(it) if !it.is_public(db) => {
+                    (DefResolution::Global(it), SymbolKind::Global)
+                }
+                FileDef::Variant(it) => (DefResolution::Variant(it), SymbolKind::Variant),
+                FileDef::Macro(it) => (DefResolution::Macro(it), SymbolKind::Macro),
+                _ => continue,
+            };
+            let Some(node) = def.clone().source(db, &tree).map(|it| it.value) else {
+                continue;
+            };
+            let name_node = node.child_by_field_name("name").unwrap_or(node);
+            let Ok(name) = name_node.utf8_text(source.as_bytes()) else {
+                continue;
+            };
+            candidates.push((
+                def,
+                DeadCode {
+                    file_id,
+                    s_range: ts_range_to_text_range(&name_node.range()),
+                    name: name.to_string(),
+                    kind,
+                },
+            ));
+        }
+    }
+    if candidates.is_empty() {
+        return Vec::new();
+    }
+
+    let references = roots
+        .iter()
+        .map(|&root| db.project_references(root))
+        .collect::<Vec<_>>();
+    candidates
+        .into_iter()
+        .filter(|(def, it)| !references.iter().any(|refs| refs.contains(def, it)))
+        .map(|(_, it)| it)
+        .collect()
+}
+
+/// Whether a forward or a native of the scope is declared with this name, in which case the
+/// function is called by SourceMod or by another plugin.
+fn implements_declaration(
+    sema: &Semantics<RootDatabase>,
+    scope: &FxHashSet<FileId>,
+    name: &str,
+) -> bool {
+    scope.iter().any(|&file_id| {
+        sema.db
+            .file_def_map(file_id)
+            .get_from_str(name)
+            .is_some_and(|defs| {
+                defs.into_iter().any(
+                    |it| matches!(FileDef::from(it), FileDef::Function(it) if !it.is_def(sema.db)),
+                )
+            })
+    })
+}
+
+fn is_local(db: &RootDatabase, file_id: FileId) -> bool {
+    !db.source_root(db.file_source_root(file_id)).is_include_dir
+}
@@ -0,0 +1,221 @@
+//! Detection of the format strings passed to SourceMod's format natives, e.g. `Format`,
+//! `PrintToChat` or `LogMessage`.
+
+use base_db::Tree;
+use fxhash::FxHashSet;
+use hir::{DefResolution, Function, Semantics};
+use hir_def::{DefDatabase, ParamData, TypeRef};
+use line_index::{TextRange, TextSize};
+use syntax::{utils::ts_range_to_text_range, TSKind};
+use tree_sitter::Node;
+use vfs::FileId;
+
+use crate::RootDatabase;
+
+/// What a format specifier expects as an argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatArg {
+    /// A cell, printed as a number, e.g. with `%d` or `%x`.
+    Int,
+    /// A cell, printed as a character with `%c`.
+    Char,
+    Float,
+    String,
+    /// A client index, e.g. with `%N`.
+    Client,
+    /// The name of a translation phrase.
+    Phrase,
+}
+
+/// A `%` specifier of a format string, e.g. `%-5.2f`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatSpecifier {
+    /// Range of the specifier in the preprocessed text.
+    pub s_range: TextRange,
+    /// Character which ends the specifier, e.g. `f` for `%-5.2f`.
+    pub conversion: char,
+}
+
+impl FormatSpecifier {
+    /// Returns the arguments consumed by the specifier, in order, or [`None`] if the
+    /// specifier is unknown.
+    ///
+    /// `%t` and `%T` also consume the arguments of the phrase, which are not known here.
+    pub fn args(&self) -> Option<&'static [FormatArg]> {
+        let args: &[FormatArg] = match self.conversion {
+            'd' | 'i' | 'u' | 'b' | 'x' | 'X' => &[FormatArg::Int],
+            'c' => &[FormatArg::Char],
+            'f' => &[FormatArg::Float],
+            's' => &[FormatArg::String],
+            'N' | 'L' => &[FormatArg::Client],
+            't' => &[FormatArg::Phrase],
+            // The phrase is translated in the language of the client which follows it.
+            'T' => &[FormatArg::Phrase, FormatArg::Client],
+            '%' => &[],
+            _ => return None,
+        };
+        Some(args)
+    }
+
+    /// Whether the specifier translates a phrase, whose own arguments follow it.
+    pub fn is_translation(&self) -> bool {
+        matches!(self.conversion, 't' | 'T')
+    }
+}
+
+/// A call passing a string literal to the format parameter of a function.
+#[derive(Debug)]
+pub struct FormatCall<'tree> {
+    pub function: Function,
+    /// The string literal.
+    pub format: Node<'tree>,
+    pub specifiers: Vec<FormatSpecifier>,
+    /// The arguments which follow the format string.
+    pub args: Vec<Node<'tree>>,
+}
+
+/// Returns the calls of the file which pass a string literal as a format string.
+///
+/// The format parameter of a function is a `const char[]` parameter followed by `any ...`, as in
+/// `Format`, or the last `char[]` parameter of the functions named in `format_functions`.
+///
+/// If `s_range` is set, only the calls which intersect it are resolved.
+pub fn format_calls<'tree>(
+    sema: &Semantics<RootDatabase>,
+    file_id: FileId,
+    tree: &'tree Tree,
+    s_range: Option<TextRange>,
+    format_functions: &FxHashSet<String>,
+) -> Vec<FormatCall<'tree>> {
+    let source = sema.preprocessed_text(file_id);
+    let mut res = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if s_range.is_some_and(|range| {
+            range
+                .intersect(ts_range_to_text_range(&node.range()))
+                .is_none()
+        }) {
+            continue;
+        }
+        stack.extend(node.children(&mut node.walk()));
+        if TSKind::from(node) != TSKind::call_expression {
+            continue;
+        }
+        if let Some(call) = format_call(sema, file_id, &source, node, format_functions) {
+            res.push(call);
+        }
+    }
+    res.sort_by_key(|it| it.format.start_byte());
+
+    res
+}
+
+fn format_call<'tree>(
+    sema: &Semantics<RootDatabase>,
+    file_id: FileId,
+    source: &str,
+    node: Node<'tree>,
+    format_functions: &FxHashSet<String>,
+) -> Option<FormatCall<'tree>> {
+    let callee = node.child_by_field_name("function")?;
+    let callee = match TSKind::from(callee) {
+        TSKind::field_access => callee.child_by_field_name("field")?,
+        _ => callee,
+    };
+    let Some(DefResolution::Function(function)) = sema.find_def(file_id, &callee) else {
+        return None;
+    };
+    let data = sema.db.function_data(function.id());
+    let format_param = format_param(
+        data.params(),
+        format_functions.contains(&data.name.to_string()),
+    )?;
+
+    let arguments = node.child_by_field_name("arguments")?;
+    let mut args = Vec::new();
+    for arg in arguments.named_children(&mut arguments.walk()) {
+        match TSKind::from(arg) {
+            TSKind::comment => continue,
+            // The positions of the arguments are not known anymore.
+            TSKind::named_arg => return None,
+            _ => args.push(arg),
+        }
+    }
+    let format = *args.get(format_param)?;
+    if TSKind::from(format) != TSKind::string_literal {
+        return None;
+    }
+    let text = format.utf8_text(source.as_bytes()).ok()?;
+    let start = TextSize::new(format.start_byte() as u32);
+    let specifiers = parse_format_string(text)
+        .into_iter()
+        .map(|(range, conversion)| FormatSpecifier {
+            s_range: range + start,
+            conversion,
+        })
+        .collect();
+
+    Some(FormatCall {
+        function,
+        format,
+        specifiers,
+        args: args.split_off(format_param + 1),
+    })
+}
+
+/// Returns the index of the format parameter among `params`.
+fn format_param(params: &[ParamData], is_format_function: bool) -> Option<usize> {
+    if let [.., format, rest] = params {
+        if rest.is_rest
+            && format.is_const
+            && format.type_ref.as_ref().is_some_and(TypeRef::is_string)
+        {
+            return Some(params.len() - 2);
+        }
+    }
+    if !is_format_function {
+        return None;
+    }
+    params
+        .iter()
+        .rposition(|it| !it.is_rest && it.type_ref.as_ref().is_some_and(TypeRef::is_string))
+}
+
+/// Returns the specifiers of the text of a string literal, with their ranges in the text and
+/// their conversion character.
+///
+/// A specifier is a `%`, optional `-` and `0` flags, an optional width, an optional precision,
+/// and a conversion character, e.g. `%-5.2f`.
+pub fn parse_format_string(text: &str) -> Vec<(TextRange, char)> {
+    let mut res = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '%' => {
+                while chars.next_if(|(_, c)| matches!(c, '-' | '0')).is_some() {}
+                while chars.next_if(|(_, c)| c.is_ascii_digit()).is_some() {}
+                if chars.next_if(|(_, c)| *c == '.').is_some() {
+                    while chars.next_if(|(_, c)| c.is_ascii_digit()).is_some() {}
+                }
+                // The closing quote is not part of the string.
+                let Some((end, conversion)) = chars.next_if(|(_, c)| *c != '"') else {
+                    continue;
+                };
+                res.push((
+                    TextRange::new(
+                        TextSize::new(start as u32),
+                        TextSize::new((end + conversion.len_utf8()) as u32),
+                    ),
+                    conversion,
+                ));
+            }
+            _ => (),
+        }
+    }
+
+    res
+}
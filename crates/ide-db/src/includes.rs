@@ -0,0 +1,278 @@
+//! Resolution of the `#include` directives of a file, and of which of them are used.
+
+use std::{collections::VecDeque, sync::Arc};
+
+use base_db::{
+    FileExtension, FileLoader, IncludeKind, IncludeType, SourceDatabase, SourceDatabaseExt, Upcast,
+};
+use fxhash::FxHashSet;
+use hir::{db::HirDatabase, DefResolution, Semantics};
+use hir_def::{resolve_include_node, DefDatabase};
+use lazy_static::lazy_static;
+use line_index::{TextRange, TextSize};
+use regex::Regex;
+use syntax::TSKind;
+use tree_sitter::Node;
+use vfs::FileId;
+
+use crate::RootDatabase;
+
+/// Comment marking an include directive which must be kept even if it seems unused.
+const KEEP_PRAGMA: &str = "IWYU pragma: keep";
+
+lazy_static! {
+    static ref RE_CONDITION: Regex = Regex::new(r"^\s*#\s*(?:if|elseif)\b(.*)$").unwrap();
+    static ref RE_WORD: Regex = Regex::new(r"[A-Za-z_]\w*").unwrap();
+}
+
+#[salsa::query_group(IncludesDatabaseStorage)]
+pub trait IncludesDatabase: HirDatabase + SourceDatabaseExt + Upcast<dyn HirDatabase> {
+    /// The files whose symbols are used by the file, and the names it looks up by name.
+    fn file_uses(&self, file_id: FileId) -> Arc<FileUses>;
+}
+
+/// What a file uses from the files it includes, see [`unused_includes`].
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FileUses {
+    /// Files in which the symbols referenced by the file are declared.
+    files: FxHashSet<FileId>,
+    /// Names whose declaration has to be looked up by name, i.e. the functions defined in the
+    /// file and the words of its `#if` conditions.
+    names: FxHashSet<String>,
+}
+
+/// An `#include` or `#tryinclude` directive of a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncludeDirective {
+    /// Range of the directive in the preprocessed text.
+    pub s_range: TextRange,
+    /// Included file, if the path could be resolved.
+    pub file_id: Option<FileId>,
+    pub kind: IncludeKind,
+    pub type_: IncludeType,
+    /// Path of the included file, without its chevrons or quotes.
+    pub path: String,
+    pub extension: FileExtension,
+}
+
+impl IncludeDirective {
+    /// Whether the directive includes `sourcemod.inc`, which every plugin implicitly includes.
+    pub fn is_sourcemod(&self, db: &RootDatabase) -> bool {
+        self.file_id.is_some() && self.file_id == sourcemod_file_id(db)
+    }
+}
+
+/// Returns the include directives of the file, in the order in which they appear.
+pub fn include_directives(
+    sema: &Semantics<RootDatabase>,
+    file_id: FileId,
+) -> Vec<IncludeDirective> {
+    let tree = sema.parse(file_id);
+    let source = sema.preprocessed_text(file_id);
+    let mut res = Vec::new();
+    collect_include_directives(sema.db, file_id, &source, tree.root_node(), &mut res);
+
+    res
+}
+
+fn collect_include_directives(
+    db: &RootDatabase,
+    file_id: FileId,
+    source: &str,
+    node: Node,
+    acc: &mut Vec<IncludeDirective>,
+) {
+    match TSKind::from(node) {
+        TSKind::preproc_include | TSKind::preproc_tryinclude => {
+            if let Some((include_id, kind, type_, path, _, extension)) =
+                resolve_include_node(db as &dyn DefDatabase, file_id, source, node)
+            {
+                acc.push(IncludeDirective {
+                    s_range: TextRange::new(
+                        TextSize::new(node.start_byte() as u32),
+                        TextSize::new(node.end_byte() as u32),
+                    ),
+                    file_id: include_id,
+                    kind,
+                    type_,
+                    path,
+                    extension,
+                });
+            }
+        }
+        TSKind::source_file => {
+            for child in node.named_children(&mut node.walk()) {
+                collect_include_directives(db, file_id, source, child, acc);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Returns the include directives of the file from which no symbol is used, directly or
+/// through the files they include.
+///
+/// A file counts as used when one of its symbols, types or macros is referenced, when it
+/// declares the forward or the native implemented by a function of the file, or when one of its
+/// macros appears in an `#if` condition. The `.sp` files included by the file share its scope,
+/// so what they use counts as well.
+///
+/// Only the includes of `.sp` files are checked, as `.inc` files often include files for their
+/// own includers. Unresolved includes, `.sp` includes, `sourcemod` and the directives followed
+/// by a `// IWYU pragma: keep` comment, e.g. the ones included for their side effects, are never
+/// reported.
+pub fn unused_includes(sema: &Semantics<RootDatabase>, file_id: FileId) -> Vec<IncludeDirective> {
+    if file_extension(sema.db, file_id) != Some(FileExtension::Sp) {
+        return Vec::new();
+    }
+    let file_text = sema.file_text(file_id);
+    let preprocessing_results = sema.preprocess_file(file_id);
+    let source_map = preprocessing_results.source_map();
+    let candidates = include_directives(sema, file_id)
+        .into_iter()
+        .filter(|it| {
+            it.extension == FileExtension::Inc
+                && it.file_id.is_some_and(|id| id != file_id)
+                && !it.is_sourcemod(sema.db)
+        })
+        .filter(|it| {
+            source_map
+                .closest_u_range(it.s_range)
+                .map(|range| &file_text[usize::from(range.end())..])
+                .and_then(|rest| rest.lines().next())
+                .is_none_or(|rest| !rest.contains(KEEP_PRAGMA))
+        })
+        .collect::<Vec<_>>();
+    if candidates.is_empty() {
+        return candidates;
+    }
+
+    let mut used_files = FxHashSet::default();
+    let mut names = FxHashSet::default();
+    let scope = sp_closure(sema.db, file_id);
+    for &id in scope.iter() {
+        let uses = sema.db.file_uses(id);
+        used_files.extend(uses.files.iter().copied());
+        names.extend(uses.names.iter().cloned());
+    }
+    used_files.retain(|id| !scope.contains(id));
+    candidates
+        .into_iter()
+        .filter(|directive| {
+            let Some(include_id) = directive.file_id else {
+                return false;
+            };
+            !include_closure(sema.db, include_id).into_iter().any(|id| {
+                if used_files.contains(&id) {
+                    return true;
+                }
+                let def_map = sema.db.file_def_map(id);
+                names
+                    .iter()
+                    .any(|name| def_map.get_from_str(name).is_some())
+            })
+        })
+        .collect()
+}
+
+fn file_uses(db: &dyn IncludesDatabase, file_id: FileId) -> Arc<FileUses> {
+    let sema = Semantics::new_dyn(db.upcast());
+    let mut used_files = FxHashSet::default();
+    let mut names = FxHashSet::default();
+    let tree = sema.parse(file_id);
+    let source = sema.preprocessed_text(file_id);
+
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if TSKind::from(node) == TSKind::identifier {
+            match sema.find_def(file_id, &node) {
+                Some(DefResolution::Local(_)) | None => (),
+                Some(def) => {
+                    used_files.insert(def.file_id(sema.db));
+                }
+            }
+            continue;
+        }
+        if TSKind::from(node) == TSKind::function_definition {
+            if let Some(name) = node
+                .child_by_field_name("name")
+                .and_then(|it| it.utf8_text(source.as_bytes()).ok())
+            {
+                names.insert(name.to_string());
+            }
+        }
+        stack.extend(node.named_children(&mut node.walk()));
+    }
+    used_files.extend(
+        sema.preprocess_file(file_id)
+            .source_map()
+            .expanded_symbols()
+            .iter()
+            .map(|it| it.file_id()),
+    );
+
+    let file_text = sema.file_text(file_id);
+    for line in file_text.lines() {
+        if let Some(condition) = RE_CONDITION.captures(line).and_then(|caps| caps.get(1)) {
+            names.extend(
+                RE_WORD
+                    .find_iter(condition.as_str())
+                    .map(|it| it.as_str().to_string())
+                    .filter(|it| it != "defined"),
+            );
+        }
+    }
+
+    Arc::new(FileUses {
+        files: used_files,
+        names,
+    })
+}
+
+/// Returns the file and all the files it includes, recursively.
+///
+/// The files included by `sourcemod.inc` are left out, as they are available to every plugin.
+fn include_closure(db: &RootDatabase, file_id: FileId) -> FxHashSet<FileId> {
+    let sourcemod = sourcemod_file_id(db);
+    let mut res = FxHashSet::default();
+    let mut queue = VecDeque::from([file_id]);
+    while let Some(id) = queue.pop_front() {
+        if Some(id) == sourcemod || !res.insert(id) {
+            continue;
+        }
+        queue.extend(db.file_includes(id).0.iter().map(|it| it.file_id()));
+    }
+
+    res
+}
+
+/// Returns the file and the `.sp` files it includes, recursively.
+fn sp_closure(db: &RootDatabase, file_id: FileId) -> FxHashSet<FileId> {
+    let mut res = FxHashSet::default();
+    let mut queue = VecDeque::from([file_id]);
+    while let Some(id) = queue.pop_front() {
+        if !res.insert(id) {
+            continue;
+        }
+        queue.extend(
+            db.file_includes(id)
+                .0
+                .iter()
+                .filter(|it| it.extension() == FileExtension::Sp)
+                .map(|it| it.file_id()),
+        );
+    }
+
+    res
+}
+
+fn file_extension(db: &RootDatabase, file_id: FileId) -> Option<FileExtension> {
+    let source_root = db.source_root(db.file_source_root(file_id));
+    let (_, extension) = source_root.path_for_file(&file_id)?.name_and_extension()?;
+
+    FileExtension::try_from(extension?).ok()
+}
+
+fn sourcemod_file_id(db: &RootDatabase) -> Option<FileId> {
+    db.resolve_path_relative_to_roots("sourcemod.inc")
+}
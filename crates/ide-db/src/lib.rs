@@ -1,15 +1,21 @@
 //! base_db defines basic database traits. The concrete DB is defined by ide.
 
+mod assists;
 mod call_item;
+mod callbacks;
+mod dead_code;
 mod documentation;
+mod format_string;
+mod includes;
 mod source_change;
+mod symbol_index;
 mod symbols;
 
 use std::{fmt, mem::ManuallyDrop, sync::Arc};
 
 use base_db::{
-    Change, FileLoader, FileLoaderDelegate, SourceDatabaseExt, SourceDatabaseExtStorage,
-    SourceDatabaseStorage, Upcast,
+    Change, FileLoader, FileLoaderDelegate, ParseCache, ParseCacheDatabase, SourceDatabaseExt,
+    SourceDatabaseExtStorage, SourceDatabaseStorage, Upcast,
 };
 use fxhash::FxHashMap;
 use hir::{db::HirDatabase, FunctionType};
@@ -18,9 +24,19 @@ use line_index::LineIndex;
 use salsa::{Cancelled, Durability};
 use vfs::FileId;
 
+pub use assists::{Assist, AssistId, AssistKind, AssistResolveStrategy, SingleResolve};
 pub use call_item::{CallItem, IncomingCallItem, OutgoingCallItem};
+pub use callbacks::CallbacksDatabase;
+pub use dead_code::{dead_code, project_dead_code, DeadCode, DeadCodeDatabase};
 pub use documentation::Documentation;
+pub use format_string::{
+    format_calls, parse_format_string, FormatArg, FormatCall, FormatSpecifier,
+};
+pub use includes::{include_directives, unused_includes, IncludeDirective, IncludesDatabase};
 pub use source_change::{SourceChange, TextEdit};
+pub use symbol_index::{
+    include_dirs_symbols, world_symbols, FileSymbol, Query, SymbolIndex, SymbolsDatabase,
+};
 pub use symbols::{Symbol, SymbolId, Symbols, SymbolsBuilder};
 
 pub type Cancellable<T> = Result<T, Cancelled>;
@@ -36,7 +52,11 @@ pub type FxIndexMap<K, V> =
     hir_def::db::DefDatabaseStorage,
     preprocessor::db::PreprocDatabaseStorage,
     LineIndexDatabaseStorage,
-    hir::db::HirDatabaseStorage
+    hir::db::HirDatabaseStorage,
+    symbol_index::SymbolsDatabaseStorage,
+    includes::IncludesDatabaseStorage,
+    callbacks::CallbacksDatabaseStorage,
+    dead_code::DeadCodeDatabaseStorage
 )]
 pub struct RootDatabase {
     // We use `ManuallyDrop` here because every codegen unit that contains a
@@ -44,6 +64,7 @@ pub struct RootDatabase {
     // which duplicates `Weak::drop` and `Arc::drop` tens of thousands of times, which makes
     // compile times of all `ide_*` and downstream crates suffer greatly.
     storage: ManuallyDrop<salsa::Storage<RootDatabase>>,
+    parse_cache: Arc<ParseCache>,
 }
 
 impl Drop for RootDatabase {
@@ -87,6 +108,12 @@ impl FileLoader for RootDatabase {
     }
 }
 
+impl ParseCacheDatabase for RootDatabase {
+    fn parse_cache(&self) -> &ParseCache {
+        &self.parse_cache
+    }
+}
+
 impl salsa::Database for RootDatabase {}
 
 impl Default for RootDatabase {
@@ -99,6 +126,7 @@ impl RootDatabase {
     pub fn new(lru_capacity: Option<usize>) -> RootDatabase {
         let mut db = RootDatabase {
             storage: ManuallyDrop::new(salsa::Storage::default()),
+            parse_cache: Default::default(),
         };
         db.set_known_files_with_durability(Default::default(), Durability::HIGH);
         db.set_source_roots_with_durability(Default::default(), Durability::HIGH);
@@ -169,6 +197,7 @@ impl salsa::ParallelDatabase for RootDatabase {
     fn snapshot(&self) -> salsa::Snapshot<RootDatabase> {
         salsa::Snapshot::new(RootDatabase {
             storage: ManuallyDrop::new(self.storage.snapshot()),
+            parse_cache: self.parse_cache.clone(),
         })
     }
 }
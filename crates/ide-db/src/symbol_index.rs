@@ -0,0 +1,304 @@
+//! Workspace wide symbol index.
+//!
+//! Every file gets a [`SymbolIndex`] built from its document symbols, and the indices of the
+//! files of a [`SourceRoot`](base_db::SourceRoot) are merged into one. Both are salsa queries:
+//! editing a file only rebuilds the index of that file and the one of its source root, and the
+//! indices of the include directories, which have a high durability, are kept across edits.
+
+use std::{ops::Range, sync::Arc};
+
+use base_db::{FileExtension, SourceDatabaseExt, SourceRootId, Upcast};
+use hir::db::HirDatabase;
+use line_index::TextRange;
+use smol_str::SmolStr;
+use vfs::FileId;
+
+use crate::{SymbolId, SymbolKind, Symbols, SymbolsBuilder};
+
+/// The maximum number of symbols returned by a [`Query`] by default.
+const DEFAULT_QUERY_LIMIT: usize = 128;
+
+#[salsa::query_group(SymbolsDatabaseStorage)]
+pub trait SymbolsDatabase: HirDatabase + SourceDatabaseExt + Upcast<dyn HirDatabase> {
+    /// The symbol index of a file.
+    fn file_symbols(&self, file_id: FileId) -> Arc<SymbolIndex>;
+
+    /// The symbol index of all the files of a source root.
+    fn source_root_symbols(&self, id: SourceRootId) -> Arc<SymbolIndex>;
+}
+
+fn file_symbols(db: &dyn SymbolsDatabase, file_id: FileId) -> Arc<SymbolIndex> {
+    let tree = db.parse(file_id);
+    let preprocessing_results = db.preprocess_file(file_id);
+    let source = preprocessing_results.preprocessed_text();
+    let symbols = SymbolsBuilder::new(preprocessing_results.source_map(), &tree, &source).build();
+
+    let mut res = Vec::new();
+    for id in &symbols {
+        collect_symbols(&symbols, id, file_id, None, &mut res);
+    }
+
+    Arc::new(SymbolIndex::new(res))
+}
+
+fn collect_symbols(
+    symbols: &Symbols,
+    id: &SymbolId,
+    file_id: FileId,
+    container: Option<&FileSymbol>,
+    acc: &mut Vec<FileSymbol>,
+) {
+    let symbol = &symbols[id];
+    let kind = match (symbol.kind(), container) {
+        // Global variables are reported as locals by the document symbols.
+        (SymbolKind::Local, None) => SymbolKind::Global,
+        // Skip the local variables of functions and methods.
+        (SymbolKind::Local, Some(_)) => return,
+        (kind, _) => kind,
+    };
+    let file_symbol = FileSymbol {
+        name: symbol.name().into(),
+        container_name: container.map(|it| it.name.clone()),
+        kind,
+        file_id,
+        full_range: symbol.full_range(),
+        focus_range: symbol.focus_range(),
+        deprecated: symbol.deprecated(),
+    };
+    for child in symbol.children() {
+        collect_symbols(symbols, child, file_id, Some(&file_symbol), acc);
+    }
+    acc.push(file_symbol);
+}
+
+fn source_root_symbols(db: &dyn SymbolsDatabase, id: SourceRootId) -> Arc<SymbolIndex> {
+    let source_root = db.source_root(id);
+    let symbols = source_root
+        .iter()
+        .filter(|file_id| {
+            source_root
+                .path_for_file(file_id)
+                .and_then(|path| path.name_and_extension())
+                .and_then(|(_, ext)| FileExtension::try_from(ext?).ok())
+                .is_some()
+        })
+        .flat_map(|file_id| db.file_symbols(file_id).symbols.clone())
+        .collect::<Vec<_>>();
+
+    Arc::new(SymbolIndex::new(symbols))
+}
+
+/// Search the symbols of all the source roots, local and include directories alike.
+pub fn world_symbols(db: &dyn SymbolsDatabase, query: &Query) -> Vec<FileSymbol> {
+    let indices = (0..db.source_roots().len())
+        .map(|idx| db.source_root_symbols(SourceRootId(idx as u32)))
+        .collect::<Vec<_>>();
+
+    query.search(&indices)
+}
+
+/// Search the symbols of the include directories only.
+pub fn include_dirs_symbols(db: &dyn SymbolsDatabase, query: &Query) -> Vec<FileSymbol> {
+    let indices = db
+        .source_roots()
+        .iter()
+        .enumerate()
+        .filter(|(_, source_root)| source_root.is_include_dir)
+        .map(|(idx, _)| db.source_root_symbols(SourceRootId(idx as u32)))
+        .collect::<Vec<_>>();
+
+    query.search(&indices)
+}
+
+/// A symbol of the index, with the file it is declared in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileSymbol {
+    pub name: SmolStr,
+    /// Name of the methodmap, enum struct, enum, etc. the symbol is declared in.
+    pub container_name: Option<SmolStr>,
+    pub kind: SymbolKind,
+    pub file_id: FileId,
+    pub full_range: TextRange,
+    pub focus_range: Option<TextRange>,
+    pub deprecated: bool,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SymbolIndex {
+    symbols: Vec<FileSymbol>,
+    /// Lowercase names of the symbols, in the same order.
+    names: Vec<String>,
+}
+
+impl SymbolIndex {
+    fn new(mut symbols: Vec<FileSymbol>) -> Self {
+        symbols.sort_by_cached_key(|it| it.name.to_lowercase());
+        let names = symbols.iter().map(|it| it.name.to_lowercase()).collect();
+
+        Self { symbols, names }
+    }
+
+    /// Returns the range of the symbols whose lowercase name starts with `prefix`.
+    fn prefix_range(&self, prefix: &str) -> Range<usize> {
+        let start = self.names.partition_point(|it| it.as_str() < prefix);
+        let len = self.names[start..].partition_point(|it| it.starts_with(prefix));
+
+        start..start + len
+    }
+}
+
+/// A fuzzy query on the symbol indices.
+///
+/// A query of the form `Container.name` (or `Container::name`) matches the symbols named like
+/// `name` which are declared in a container named like `Container`, e.g. `Cl.Get` matches
+/// `ClientList.GetClient`.
+#[derive(Debug, Clone)]
+pub struct Query {
+    container: Option<String>,
+    name: String,
+    limit: usize,
+    top_level: bool,
+}
+
+impl Query {
+    pub fn new(query: &str) -> Self {
+        let query = query.trim().to_lowercase();
+        let (container, name) = match query.rfind(['.', ':']) {
+            Some(idx) => {
+                let container = query[..idx].trim_end_matches(':');
+                (Some(container.to_string()), query[idx + 1..].to_string())
+            }
+            None => (None, query),
+        };
+
+        Self {
+            container,
+            name,
+            limit: DEFAULT_QUERY_LIMIT,
+            top_level: false,
+        }
+    }
+
+    pub fn limit(&mut self, limit: usize) {
+        self.limit = limit;
+    }
+
+    /// Only match the symbols which are not declared in a container.
+    pub fn top_level(&mut self) {
+        self.top_level = true;
+    }
+
+    fn search(&self, indices: &[Arc<SymbolIndex>]) -> Vec<FileSymbol> {
+        let mut res = Vec::new();
+        // Exact and prefix matches rank before all the others, and are found with a binary
+        // search on the sorted names.
+        for index in indices {
+            for idx in index.prefix_range(&self.name) {
+                self.push_match(index, idx, &mut res);
+            }
+        }
+        // Only go through all the symbols if the weaker matches can make it into the results.
+        if res.len() < self.limit {
+            for index in indices {
+                let range = index.prefix_range(&self.name);
+                for idx in (0..range.start).chain(range.end..index.symbols.len()) {
+                    self.push_match(index, idx, &mut res);
+                }
+            }
+        }
+        res.sort_by(|(a_score, a), (b_score, b)| {
+            a_score
+                .cmp(b_score)
+                .then_with(|| a.name.len().cmp(&b.name.len()))
+                .then_with(|| a.name.cmp(&b.name))
+                .then_with(|| a.container_name.cmp(&b.container_name))
+        });
+
+        res.into_iter()
+            .take(self.limit)
+            .map(|(_, symbol)| symbol.clone())
+            .collect()
+    }
+
+    fn push_match<'a>(
+        &self,
+        index: &'a SymbolIndex,
+        idx: usize,
+        acc: &mut Vec<((MatchScore, MatchScore), &'a FileSymbol)>,
+    ) {
+        let (symbol, name) = (&index.symbols[idx], &index.names[idx]);
+        if self.top_level && symbol.container_name.is_some() {
+            return;
+        }
+        let Some(mut name_score) = fuzzy_match(name, &self.name) else {
+            return;
+        };
+        if name_score == MatchScore::Subsequence
+            && initials(&symbol.name).starts_with(self.name.as_str())
+        {
+            name_score = MatchScore::Initials;
+        }
+        let container_score = match (&self.container, &symbol.container_name) {
+            (None, _) => MatchScore::Exact,
+            (Some(_), None) => return,
+            (Some(query), Some(container)) => {
+                let Some(score) = fuzzy_match(&container.to_lowercase(), query) else {
+                    return;
+                };
+                score
+            }
+        };
+        acc.push(((name_score, container_score), symbol));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum MatchScore {
+    Exact,
+    Prefix,
+    /// The pattern matches the start of the words of the candidate, e.g. `gcn` and
+    /// `GetClientName`.
+    Initials,
+    Substring,
+    Subsequence,
+}
+
+/// Match a lowercase candidate against a lowercase pattern.
+fn fuzzy_match(candidate: &str, pattern: &str) -> Option<MatchScore> {
+    if candidate == pattern {
+        return Some(MatchScore::Exact);
+    }
+    if candidate.starts_with(pattern) {
+        return Some(MatchScore::Prefix);
+    }
+    if candidate.contains(pattern) {
+        return Some(MatchScore::Substring);
+    }
+    let mut chars = candidate.chars();
+    if pattern.chars().all(|c| chars.any(|it| it == c)) {
+        return Some(MatchScore::Subsequence);
+    }
+
+    None
+}
+
+/// Lowercase first letters of the words of a `camelCase` or `snake_case` name.
+fn initials(name: &str) -> String {
+    let mut res = String::new();
+    let mut prev: Option<char> = None;
+    for c in name.chars() {
+        let is_word_start = match prev {
+            None => c.is_alphanumeric(),
+            Some(prev) => {
+                (!prev.is_alphanumeric() && c.is_alphanumeric())
+                    || (prev.is_lowercase() && c.is_uppercase())
+            }
+        };
+        if is_word_start {
+            res.extend(c.to_lowercase());
+        }
+        prev = Some(c);
+    }
+
+    res
+}
@@ -161,7 +161,7 @@ impl<'a> SymbolsBuilder<'a> {
             .children(&mut node.walk())
             .flat_map(|child| match TSKind::from(&child) {
                 TSKind::methodmap_property => self.alloc_property(&child),
-                TSKind::methodmap_method => self.alloc_method(&child),
+                TSKind::methodmap_method | TSKind::methodmap_native => self.alloc_method(&child),
                 TSKind::methodmap_method_constructor | TSKind::methodmap_native_constructor => self
                     .alloc_function_(
                         &child,
@@ -263,13 +263,15 @@ impl<'a> SymbolsBuilder<'a> {
         let symbol = Symbol::try_new(
             name,
             None,
-            SymbolKind::Property,
+            SymbolKind::Enum,
             self.s_range_to_u_range(&node.range()),
             name_node.map(|node| self.s_range_to_u_range(&node.range())),
             children,
             self.is_deprecated(node),
         )?;
-        self.alloc(symbol).into()
+        // Enums are top level symbols, otherwise neither they nor their entries are reachable
+        // from the document symbols and the symbol index.
+        self.alloc_top(symbol).into()
     }
 
     fn alloc_variable_declaration(&mut self, node: &Node) -> Option<SymbolId> {
@@ -1,11 +1,21 @@
+pub(crate) mod buffer_size;
+pub(crate) mod callback_signature_mismatch;
+pub(crate) mod dead_code;
+pub(crate) mod format_string;
 pub(crate) mod inactive_code;
 pub(crate) mod incorrect_number_of_arguments;
 pub(crate) mod invalid_use_of_this;
+pub(crate) mod missing_return;
 pub(crate) mod preprocessor_evaluation_error;
+pub(crate) mod type_mismatch;
+pub(crate) mod unreachable_code;
 pub(crate) mod unresolved_constructor;
 pub(crate) mod unresolved_field;
+pub(crate) mod unresolved_ident;
 pub(crate) mod unresolved_include;
 pub(crate) mod unresolved_inherit;
 pub(crate) mod unresolved_macro;
 pub(crate) mod unresolved_method_call;
 pub(crate) mod unresolved_named_arg;
+pub(crate) mod unused_include;
+pub(crate) mod unused_variable;
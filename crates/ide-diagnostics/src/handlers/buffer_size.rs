@@ -0,0 +1,204 @@
+use hir::DefResolution;
+use hir_def::{DefDatabase, FunctionKind, ParamData, TypeRef};
+use line_index::TextRange;
+use syntax::TSKind;
+use tree_sitter::Node;
+
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext, Severity};
+
+// Diagnostic: buffer-size
+//
+// This diagnostic is triggered if the size passed along a buffer to a native, e.g. the `maxlen`
+// of `GetClientName(int client, char[] name, int maxlen)`, is the `sizeof` of another variable,
+// or is a constant larger than the declared size of the buffer.
+pub(crate) fn buffer_size(ctx: &DiagnosticsContext<'_>, acc: &mut Vec<Diagnostic>) {
+    let tree = ctx.sema.parse(ctx.file_id);
+    let mut calls = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        stack.extend(node.children(&mut node.walk()));
+        if TSKind::from(node) == TSKind::call_expression {
+            calls.push(node);
+        }
+    }
+    calls.sort_by_key(|it| it.start_byte());
+    for call in calls {
+        check_call(ctx, call, acc);
+    }
+}
+
+fn check_call(ctx: &DiagnosticsContext<'_>, node: Node, acc: &mut Vec<Diagnostic>) -> Option<()> {
+    let callee = node.child_by_field_name("function")?;
+    let callee = match TSKind::from(callee) {
+        TSKind::field_access => callee.child_by_field_name("field")?,
+        _ => callee,
+    };
+    let Some(DefResolution::Function(function)) = ctx.sema.find_def(ctx.file_id, &callee) else {
+        return None;
+    };
+    let data = ctx.sema.db.function_data(function.id());
+    if data.kind != FunctionKind::Native {
+        return None;
+    }
+
+    let arguments = node.child_by_field_name("arguments")?;
+    let mut args = Vec::new();
+    for arg in arguments.named_children(&mut arguments.walk()) {
+        match TSKind::from(arg) {
+            TSKind::comment => continue,
+            // The positions of the arguments are not known anymore.
+            TSKind::named_arg => return None,
+            _ => args.push(arg),
+        }
+    }
+
+    for (i, params) in data.params().windows(2).enumerate() {
+        if !is_buffer(&params[0]) || !is_size(&params[1]) {
+            continue;
+        }
+        let (Some(buffer), Some(size)) = (args.get(i), args.get(i + 1)) else {
+            break;
+        };
+        if let Some(message) = check_size(ctx, buffer, size) {
+            acc.push(Diagnostic::new_for_s_range(
+                ctx,
+                DiagnosticCode::Lint("buffer-size", Severity::Warning),
+                message,
+                TextRange::new(
+                    (size.start_byte() as u32).into(),
+                    (size.end_byte() as u32).into(),
+                ),
+            ));
+        }
+    }
+
+    Some(())
+}
+
+/// Whether the parameter is a buffer written by the native, e.g. `char[] buffer`.
+fn is_buffer(param: &ParamData) -> bool {
+    !param.is_const && !param.is_rest && param.type_ref.as_ref().is_some_and(TypeRef::is_string)
+}
+
+/// Whether the parameter is an `int` passed by value, e.g. `int maxlength`.
+fn is_size(param: &ParamData) -> bool {
+    if param.is_rest || param.is_ref {
+        return false;
+    }
+    match &param.type_ref {
+        // Untagged parameters of the old syntax are cells.
+        None => true,
+        Some(TypeRef::Array(_)) => false,
+        Some(type_ref) => type_ref.to_new_syntax().type_as_string() == "int",
+    }
+}
+
+/// Returns the message of the diagnostic if `size` is not the size of `buffer`.
+fn check_size(ctx: &DiagnosticsContext<'_>, buffer: &Node, size: &Node) -> Option<String> {
+    let source = ctx.sema.preprocessed_text(ctx.file_id);
+    let text = |node: &Node| node.utf8_text(source.as_bytes()).ok();
+    if TSKind::from(size) == TSKind::sizeof_expression {
+        if !matches!(
+            TSKind::from(buffer),
+            TSKind::identifier
+                | TSKind::array_indexed_access
+                | TSKind::field_access
+                | TSKind::scope_access
+        ) {
+            return None;
+        }
+        let operand = size
+            .children_by_field_name("type", &mut size.walk())
+            .filter_map(|it| text(&it))
+            .collect::<String>();
+        let is_same = match size
+            .child_by_field_name("type")
+            .filter(|it| TSKind::from(it) == TSKind::scope_access)
+        {
+            // `sizeof(Player::name)` is the size of a field of any instance of the enum struct.
+            Some(field) => !is_other_field(ctx, buffer, &field),
+            None => normalize(&operand) == normalize(text(buffer)?),
+        };
+        if is_same {
+            return None;
+        }
+        let buffer = text(buffer)?;
+        return Some(format!(
+            "`sizeof({})` is not the size of `{}`",
+            operand, buffer
+        ));
+    }
+
+    let value = hir_def::eval_size(size, &source)?;
+    let declared = declared_size(ctx, buffer)?;
+    if value <= declared {
+        return None;
+    }
+    Some(format!(
+        "`{}` exceeds the size of `{}` ({})",
+        value,
+        text(buffer)?,
+        declared
+    ))
+}
+
+/// Whether `scope_access`, e.g. `Player::name`, and the buffer resolve to different fields.
+fn is_other_field(ctx: &DiagnosticsContext<'_>, buffer: &Node, scope_access: &Node) -> bool {
+    let resolve = |node: Option<Node>| ctx.sema.find_def(ctx.file_id, &node?);
+    let Some(field) = resolve(scope_access.child_by_field_name("field")) else {
+        return false;
+    };
+    let buffer_field = match TSKind::from(buffer) {
+        TSKind::field_access => resolve(buffer.child_by_field_name("field")),
+        _ => resolve(Some(*buffer)),
+    };
+
+    buffer_field.is_some_and(|it| it != field)
+}
+
+/// Returns the declared size of a buffer which is a variable, or an element of an array, e.g.
+/// `32` for `names[i]` with `char names[4][32]`.
+fn declared_size(ctx: &DiagnosticsContext<'_>, buffer: &Node) -> Option<u32> {
+    let mut node = *buffer;
+    let mut depth = 0;
+    while TSKind::from(node) == TSKind::array_indexed_access {
+        node = node.child_by_field_name("array")?;
+        depth += 1;
+    }
+    if TSKind::from(node) != TSKind::identifier {
+        return None;
+    }
+    let dimensions = match ctx.sema.find_def(ctx.file_id, &node)? {
+        DefResolution::Local(local) => local.def.dimensions(ctx.sema.db),
+        DefResolution::Global(global) => global.dimensions(ctx.sema.db),
+        _ => return None,
+    };
+
+    dimensions.get(depth).copied().flatten()
+}
+
+/// Normalizes the text of a variable so that `names[i]` and `names[]` compare equal.
+fn normalize(text: &str) -> String {
+    let mut res = String::new();
+    let mut depth = 0;
+    for c in text.chars().filter(|c| !c.is_whitespace()) {
+        match c {
+            '[' => {
+                if depth == 0 {
+                    res.push('[');
+                }
+                depth += 1;
+            }
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    res.push(']');
+                }
+            }
+            _ if depth == 0 => res.push(c),
+            _ => (),
+        }
+    }
+
+    res
+}
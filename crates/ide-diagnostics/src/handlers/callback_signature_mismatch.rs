@@ -0,0 +1,98 @@
+use hir::DefResolution;
+use hir_def::SignatureMismatch;
+
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext};
+
+pub(crate) use self::callback_signature_mismatch as f;
+
+// Diagnostic: callback-signature-mismatch
+//
+// This diagnostic is triggered if a function is passed where a typedef, a typeset, a functag or
+// a funcenum is expected, and its signature matches none of the signatures of this type.
+pub(crate) fn callback_signature_mismatch(
+    ctx: &DiagnosticsContext<'_>,
+    d: &hir::CallbackSignatureMismatch,
+) -> Diagnostic {
+    let db = ctx.sema.db;
+    let name = d.function.name(db);
+    let expected = d.expected.type_as_string();
+    let param_name = |idx: usize| {
+        d.function
+            .parameters(db)
+            .get(idx)
+            .map_or_else(|| format!("{}", idx + 1), |it| format!("`{it}`"))
+    };
+    let message = match &d.mismatch {
+        Some(SignatureMismatch::TooManyParams {
+            expected: len,
+            actual,
+        }) => format!(
+            "`{name}` does not match `{expected}`: it has {actual} mandatory parameters but \
+             `{expected}` only passes {len}"
+        ),
+        Some(SignatureMismatch::ParamType {
+            idx,
+            expected: expected_ty,
+            actual,
+        }) => format!(
+            "`{name}` does not match `{expected}`: parameter {} is `{actual}` but `{expected_ty}` \
+             is expected",
+            param_name(*idx)
+        ),
+        Some(SignatureMismatch::ParamRef {
+            idx,
+            expected: true,
+        }) => format!(
+            "`{name}` does not match `{expected}`: parameter {} must be passed by reference",
+            param_name(*idx)
+        ),
+        Some(SignatureMismatch::ParamRef {
+            idx,
+            expected: false,
+        }) => format!(
+            "`{name}` does not match `{expected}`: parameter {} must not be passed by reference",
+            param_name(*idx)
+        ),
+        Some(SignatureMismatch::ReturnType {
+            expected: expected_ty,
+            actual,
+        }) => format!(
+            "`{name}` does not match `{expected}`: it returns `{actual}` but `{expected_ty}` is \
+             expected"
+        ),
+        None => {
+            let mut message =
+                format!("`{name}` does not match any signature of `{expected}`, expected one of:");
+            for signature in signatures(ctx, d.expected_def.as_ref()) {
+                message.push_str("\n    ");
+                message.push_str(&signature);
+            }
+            message
+        }
+    };
+
+    Diagnostic::new_with_syntax_node_ptr(ctx, DiagnosticCode::SpCompError("E0100"), message, d.expr)
+}
+
+/// Returns the rendered signatures of a typeset or of a funcenum.
+fn signatures(ctx: &DiagnosticsContext<'_>, def: Option<&DefResolution>) -> Vec<String> {
+    let db = ctx.sema.db;
+    let renders = match def {
+        Some(DefResolution::Typeset(it)) => it
+            .children(db)
+            .into_iter()
+            .filter_map(|it| it.render(db))
+            .collect(),
+        Some(DefResolution::Funcenum(it)) => it
+            .children(db)
+            .into_iter()
+            .filter_map(|it| it.render(db))
+            .collect(),
+        _ => Vec::new(),
+    };
+    // The renders of the children of a funcenum start with the name of their parent.
+    renders
+        .into_iter()
+        .filter_map(|it| it.lines().last().map(String::from))
+        .collect()
+}
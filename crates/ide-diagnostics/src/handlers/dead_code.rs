@@ -0,0 +1,28 @@
+use ide_db::SymbolKind;
+
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext, Severity};
+
+// Diagnostic: dead-code
+//
+// This diagnostic is triggered if a function which is not `public`, a global which is not
+// `public`, an enum entry or a macro is never referenced in any of the plugins including the
+// file. The files of the include directories are not checked.
+pub(crate) fn dead_code(ctx: &DiagnosticsContext<'_>, acc: &mut Vec<Diagnostic>) {
+    for item in ide_db::dead_code(&ctx.sema, ctx.file_id) {
+        let kind = match item.kind {
+            SymbolKind::Function => "function",
+            SymbolKind::Global => "global variable",
+            SymbolKind::Variant => "enum entry",
+            SymbolKind::Macro => "macro",
+            _ => continue,
+        };
+        let d = Diagnostic::new_for_s_range(
+            ctx,
+            DiagnosticCode::Lint("dead-code", Severity::WeakWarning),
+            format!("{} `{}` is never used", kind, item.name),
+            item.s_range,
+        )
+        .with_unused(true);
+        acc.push(d);
+    }
+}
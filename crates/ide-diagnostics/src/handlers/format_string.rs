@@ -0,0 +1,162 @@
+use hir_def::TypeRef;
+use ide_db::{FormatArg, FormatCall};
+use line_index::TextRange;
+
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext, Severity};
+
+// Diagnostic: format-string
+//
+// This diagnostic is triggered if the specifiers of a format string, e.g. the one of `Format`
+// or `PrintToChat`, do not match the arguments which follow it: an argument is missing or
+// unused, an argument has the wrong type, or a specifier is unknown.
+//
+// The arguments of a translated phrase (`%t` and `%T`) are not known, hence the arguments which
+// follow a translated phrase are only counted.
+pub(crate) fn format_string(ctx: &DiagnosticsContext<'_>, acc: &mut Vec<Diagnostic>) {
+    let tree = ctx.sema.parse(ctx.file_id);
+    for call in ide_db::format_calls(
+        &ctx.sema,
+        ctx.file_id,
+        &tree,
+        None,
+        &ctx.config.format_functions,
+    ) {
+        check_format_call(ctx, &call, acc);
+    }
+}
+
+fn check_format_call(ctx: &DiagnosticsContext<'_>, call: &FormatCall, acc: &mut Vec<Diagnostic>) {
+    let source = ctx.sema.preprocessed_text(ctx.file_id);
+    let mut args = call.args.iter();
+    // Whether a phrase was translated before, whose arguments are not known.
+    let mut translated = false;
+    for specifier in call.specifiers.iter() {
+        // Specifiers coming from a macro can't be fixed where they are used, but they still
+        // consume their arguments.
+        let u_range = ctx.u_range(specifier.s_range);
+        let text = &source[specifier.s_range];
+        let mut report = |severity, message| {
+            if let Some(u_range) = u_range {
+                acc.push(Diagnostic::new_for_u_range(
+                    DiagnosticCode::Lint("format-string", severity),
+                    message,
+                    u_range,
+                ));
+            }
+        };
+        let Some(expected_args) = specifier.args() else {
+            report(
+                Severity::Warning,
+                format!("unknown format specifier `{}`", text),
+            );
+            continue;
+        };
+        for expected in expected_args {
+            let Some(arg) = args.next() else {
+                report(
+                    Severity::Error,
+                    format!("format specifier `{}` has no matching argument", text),
+                );
+                return;
+            };
+            if translated {
+                continue;
+            }
+            let Some(actual) = ctx.sema.type_of_expr(ctx.file_id, arg) else {
+                continue;
+            };
+            if is_mismatch(*expected, &actual) {
+                report(
+                    Severity::Warning,
+                    format!(
+                        "format specifier `{}` expects {}, got `{}`",
+                        text,
+                        describe(*expected),
+                        actual.to_new_syntax()
+                    ),
+                );
+            }
+        }
+        translated |= specifier.is_translation();
+    }
+
+    let unused = args.collect::<Vec<_>>();
+    if translated {
+        return;
+    }
+    let (Some(first), Some(last)) = (unused.first(), unused.last()) else {
+        return;
+    };
+    let s_range = TextRange::new(
+        (first.start_byte() as u32).into(),
+        (last.end_byte() as u32).into(),
+    );
+    let Some(u_range) = ctx.u_range(s_range) else {
+        return;
+    };
+    let message = if unused.len() == 1 {
+        "argument is not used by the format string".to_string()
+    } else {
+        format!(
+            "{} arguments are not used by the format string",
+            unused.len()
+        )
+    };
+    acc.push(
+        Diagnostic::new_for_u_range(
+            DiagnosticCode::Lint("format-string", Severity::Warning),
+            message,
+            u_range,
+        )
+        .with_unused(true),
+    );
+}
+
+/// What a value is passed as to a format native.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArgType {
+    Cell,
+    Float,
+    String,
+}
+
+/// Returns the type of an argument, or [`None`] if it is not known well enough to be checked.
+fn arg_type(type_ref: &TypeRef) -> Option<ArgType> {
+    match type_ref.to_new_syntax() {
+        TypeRef::Int | TypeRef::Bool | TypeRef::Char => Some(ArgType::Cell),
+        TypeRef::Float => Some(ArgType::Float),
+        TypeRef::Array((inner, 1)) if inner.to_new_syntax().type_as_string() == "char" => {
+            Some(ArgType::String)
+        }
+        TypeRef::Name(name) => match name.to_string().as_str() {
+            "int" | "bool" | "char" => Some(ArgType::Cell),
+            "float" => Some(ArgType::Float),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn is_mismatch(expected: FormatArg, actual: &TypeRef) -> bool {
+    let Some(actual) = arg_type(actual) else {
+        return false;
+    };
+    match expected {
+        FormatArg::Int | FormatArg::Client => actual != ArgType::Cell,
+        // The first character of a string is printed.
+        FormatArg::Char => actual == ArgType::Float,
+        FormatArg::Float => actual != ArgType::Float,
+        FormatArg::String | FormatArg::Phrase => actual != ArgType::String,
+    }
+}
+
+fn describe(expected: FormatArg) -> &'static str {
+    match expected {
+        FormatArg::Int => "an integer",
+        FormatArg::Char => "a character",
+        FormatArg::Float => "a float",
+        FormatArg::String => "a string",
+        FormatArg::Client => "a client index",
+        FormatArg::Phrase => "a translation phrase",
+    }
+}
@@ -1,4 +1,8 @@
-use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext};
+use ide_db::{Assist, TextEdit};
+use line_index::TextRange;
+use syntax::{utils::ts_range_to_text_range, TSKind};
+
+use crate::{fix, Diagnostic, DiagnosticCode, DiagnosticsContext};
 
 pub(crate) use self::incorrect_number_of_arguments as f;
 
@@ -21,4 +25,62 @@ pub(crate) fn incorrect_number_of_arguments(
         )
     };
     Diagnostic::new_with_syntax_node_ptr(ctx, DiagnosticCode::SpCompError("E0000"), message, d.expr)
+        .with_fixes(fixes(ctx, d))
+}
+
+fn fixes(ctx: &DiagnosticsContext<'_>, d: &hir::IncorrectNumberOfArguments) -> Option<Vec<Assist>> {
+    if d.missing_params.is_empty() {
+        return None;
+    }
+    let tree = ctx.sema.parse(d.expr.file_id);
+    let node = d.expr.value.to_node(&tree)?;
+    // Without arguments, the diagnostic is on the call, otherwise on its last argument.
+    let (arguments, anchor) = if d.actual == 0 {
+        let arguments = node.child_by_field_name("arguments")?;
+        (arguments, arguments.child(0)?)
+    } else {
+        (node.parent()?, node)
+    };
+    if TSKind::from(arguments) != TSKind::call_arguments {
+        return None;
+    }
+    // The missing parameters are counted from the position of the arguments.
+    let mut cursor = arguments.walk();
+    if arguments
+        .children(&mut cursor)
+        .any(|it| TSKind::from(it) == TSKind::named_arg)
+    {
+        return None;
+    }
+
+    let offset = ctx.u_range(ts_range_to_text_range(&anchor.range()))?.end();
+    let placeholders = d
+        .missing_params
+        .iter()
+        .map(|it| it.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let text = if d.actual == 0 {
+        placeholders
+    } else {
+        format!(", {placeholders}")
+    };
+    let label = if d.missing_params.len() == 1 {
+        "Add the missing argument"
+    } else {
+        "Add the missing arguments"
+    };
+
+    Some(vec![fix(
+        ctx,
+        "add_missing_arguments",
+        label,
+        ctx.u_range(ts_range_to_text_range(&arguments.range()))?,
+        |it| {
+            it.insert(
+                d.expr.file_id,
+                TextEdit::new(TextRange::empty(offset), text),
+            )
+        },
+    )])
 }
@@ -0,0 +1,27 @@
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext};
+
+pub(crate) use self::missing_return as f;
+
+// Diagnostic: missing-return
+//
+// This diagnostic is triggered if a function which returns a value can reach its end, or a
+// `return;`, without returning one. `Action` callbacks must return one of the `Plugin_*` values.
+pub(crate) fn missing_return(ctx: &DiagnosticsContext<'_>, d: &hir::MissingReturn) -> Diagnostic {
+    let message = if d.return_type.type_as_string() == "Action" {
+        format!(
+            "function `{}` should return an `Action` on all paths, e.g. `return Plugin_Continue;`",
+            d.name
+        )
+    } else {
+        format!(
+            "function `{}` should return a value of type `{}` on all paths",
+            d.name, d.return_type
+        )
+    };
+    Diagnostic::new_with_syntax_node_ptr(
+        ctx,
+        DiagnosticCode::SpCompWarning("W0209"),
+        message,
+        d.expr,
+    )
+}
@@ -0,0 +1,19 @@
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext};
+
+pub(crate) use self::type_mismatch as f;
+
+// Diagnostic: type-mismatch
+//
+// This diagnostic is triggered if a value is assigned, passed or returned where a value of an
+// incompatible tag is expected, e.g. a `float` assigned to an `int`.
+pub(crate) fn type_mismatch(ctx: &DiagnosticsContext<'_>, d: &hir::TypeMismatch) -> Diagnostic {
+    Diagnostic::new_with_syntax_node_ptr(
+        ctx,
+        DiagnosticCode::SpCompWarning("W0213"),
+        format!(
+            "tag mismatch (expected \"{}\", got \"{}\")",
+            d.expected, d.actual
+        ),
+        d.expr,
+    )
+}
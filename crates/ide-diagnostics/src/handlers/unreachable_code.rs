@@ -0,0 +1,20 @@
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext};
+
+pub(crate) use self::unreachable_code as f;
+
+// Diagnostic: unreachable-code
+//
+// This diagnostic is triggered for the statements which follow a `return`, a `break` or a
+// `continue`, and can therefore never be executed.
+pub(crate) fn unreachable_code(
+    ctx: &DiagnosticsContext<'_>,
+    d: &hir::UnreachableCode,
+) -> Diagnostic {
+    Diagnostic::new_for_s_range(
+        ctx,
+        DiagnosticCode::SpCompWarning("W0225"),
+        "unreachable code",
+        d.range,
+    )
+    .with_unused(true)
+}
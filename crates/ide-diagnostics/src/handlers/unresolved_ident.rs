@@ -0,0 +1,97 @@
+use hir::DefResolution;
+use ide_db::{Assist, TextEdit};
+use line_index::TextRange;
+use syntax::{utils::ts_range_to_text_range, TSKind};
+
+use crate::{fix, Diagnostic, DiagnosticCode, DiagnosticsContext};
+
+pub(crate) use self::unresolved_ident as f;
+
+// Diagnostic: unresolved-ident
+//
+// This diagnostic is triggered if an identifier or a called function cannot be found in the scope.
+pub(crate) fn unresolved_ident(
+    ctx: &DiagnosticsContext<'_>,
+    d: &hir::UnresolvedIdent,
+) -> Diagnostic {
+    let kind = if d.is_callee { "function" } else { "value" };
+    let candidates = d.candidates(ctx.sema.db);
+    let suggestion = if candidates.is_empty() {
+        "".to_string()
+    } else {
+        format!(
+            ", did you mean {}?",
+            candidates
+                .iter()
+                .map(|it| format!("`{it}`"))
+                .collect::<Vec<_>>()
+                .join(" or ")
+        )
+    };
+    Diagnostic::new_with_syntax_node_ptr(
+        ctx,
+        DiagnosticCode::SpCompError("E0017"),
+        format!("cannot find {kind} `{}` in this scope{suggestion}", d.name),
+        d.expr,
+    )
+    .with_fixes(fixes(ctx, d))
+}
+
+/// Generates the missing function when the identifier is passed as a callback, one fix per
+/// signature the callback can have.
+fn fixes(ctx: &DiagnosticsContext<'_>, d: &hir::UnresolvedIdent) -> Option<Vec<Assist>> {
+    if d.is_callee {
+        return None;
+    }
+    let db = ctx.sema.db;
+    let name = d.name.to_string();
+    let stubs: Vec<_> = match d.expected_type.clone()? {
+        DefResolution::Typedef(it) => vec![it.as_stub(db, &name)?],
+        DefResolution::Typeset(it) => it
+            .children(db)
+            .into_iter()
+            .filter_map(|it| it.as_stub(db, &name))
+            .collect(),
+        DefResolution::Functag(it) => vec![it.as_stub(db, &name)?],
+        DefResolution::Funcenum(it) => it
+            .children(db)
+            .into_iter()
+            .filter_map(|it| it.as_stub(db, &name))
+            .collect(),
+        _ => return None,
+    };
+
+    let tree = ctx.sema.parse(d.expr.file_id);
+    let node = d.expr.value.to_node(&tree)?;
+    let target = ctx.u_range(ts_range_to_text_range(&node.range()))?;
+    // The function is generated below the item the identifier is used in.
+    let mut item = node;
+    while let Some(parent) = item.parent() {
+        if TSKind::from(parent) == TSKind::source_file {
+            break;
+        }
+        item = parent;
+    }
+    let item_range = ctx.u_range(ts_range_to_text_range(&item.range()))?;
+
+    let fixes = stubs
+        .into_iter()
+        .map(|stub| {
+            let signature = stub.lines().next().unwrap_or_default();
+            fix(
+                ctx,
+                "generate_callback",
+                &format!("Generate `{signature}`"),
+                target,
+                |it| {
+                    it.insert(
+                        d.expr.file_id,
+                        TextEdit::new(TextRange::empty(item_range.end()), format!("\n\n{stub}")),
+                    )
+                },
+            )
+        })
+        .collect::<Vec<_>>();
+
+    (!fixes.is_empty()).then_some(fixes)
+}
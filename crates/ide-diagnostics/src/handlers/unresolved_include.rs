@@ -1,4 +1,7 @@
-use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext};
+use base_db::{infer_include_ext, SourceDatabaseExt};
+use ide_db::{Assist, TextEdit};
+
+use crate::{fix, Diagnostic, DiagnosticCode, DiagnosticsContext};
 
 pub(crate) use self::unresolved_include as f;
 
@@ -15,6 +18,61 @@ pub(crate) fn unresolved_include(
         format!("file `{}` was not found", d.path),
         d.range,
     )
-    // .with_fixes(fixes(ctx, d))
+    .with_fixes(fixes(ctx, d))
     // .experimental()
 }
+
+fn fixes(ctx: &DiagnosticsContext<'_>, d: &hir::UnresolvedInclude) -> Option<Vec<Assist>> {
+    let u_range = ctx.u_range(d.range)?;
+    let mut path = d.path.clone();
+    let written_len = path.len();
+    infer_include_ext(&mut path);
+
+    let mut path = case_insensitive_path(ctx, &path)?;
+    // Keep the extension implicit if it was.
+    path.truncate(written_len);
+    if path == d.path {
+        return None;
+    }
+    Some(vec![fix(
+        ctx,
+        "fix_include_case",
+        &format!("Change to `{path}`"),
+        u_range,
+        |it| it.insert(ctx.file_id, TextEdit::new(u_range, path.clone())),
+    )])
+}
+
+/// Returns the path, relative to the directories an include is resolved from, of a known file
+/// which only differs from `path` by its case.
+fn case_insensitive_path(ctx: &DiagnosticsContext<'_>, path: &str) -> Option<String> {
+    let db = ctx.sema.db;
+    let source_roots = db.source_roots();
+    let file_dir = db
+        .source_root(db.file_source_root(ctx.file_id))
+        .path_for_file(&ctx.file_id)?
+        .parent()?;
+    let dirs = [file_dir]
+        .into_iter()
+        .chain(source_roots.iter().map(|it| it.root().clone()))
+        .flat_map(|dir| [dir.join("include"), Some(dir)])
+        .flatten()
+        .collect::<Vec<_>>();
+
+    source_roots.iter().find_map(|source_root| {
+        source_root.iter().find_map(|file_id| {
+            let file_path = source_root.path_for_file(&file_id)?;
+            dirs.iter().find_map(|dir| {
+                let relative_path = file_path
+                    .strip_prefix(dir)?
+                    .components()
+                    .map(|it| it.as_os_str().to_str())
+                    .collect::<Option<Vec<_>>>()?
+                    .join("/");
+                relative_path
+                    .eq_ignore_ascii_case(path)
+                    .then_some(relative_path)
+            })
+        })
+    })
+}
@@ -1,4 +1,7 @@
-use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext};
+use ide_db::Assist;
+use syntax::utils::ts_range_to_text_range;
+
+use crate::{replace_with_candidates_fixes, Diagnostic, DiagnosticCode, DiagnosticsContext};
 
 pub(crate) use self::unresolved_method_call as f;
 
@@ -23,6 +26,20 @@ pub(crate) fn unresolved_method_call(
         ),
         d.expr,
     )
-    // .with_fixes(fixes(ctx, d))
+    .with_fixes(fixes(ctx, d))
     // .experimental()
 }
+
+fn fixes(ctx: &DiagnosticsContext<'_>, d: &hir::UnresolvedMethodCall) -> Option<Vec<Assist>> {
+    let tree = ctx.sema.parse(d.expr.file_id);
+    let method = d.expr.value.to_node(&tree)?;
+    let u_range = ctx.u_range(ts_range_to_text_range(&method.range()))?;
+
+    replace_with_candidates_fixes(
+        ctx,
+        "change_method_name",
+        d.expr.file_id,
+        u_range,
+        &d.candidates,
+    )
+}
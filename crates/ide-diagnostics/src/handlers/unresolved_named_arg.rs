@@ -1,4 +1,7 @@
-use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext};
+use ide_db::Assist;
+use syntax::utils::ts_range_to_text_range;
+
+use crate::{replace_with_candidates_fixes, Diagnostic, DiagnosticCode, DiagnosticsContext};
 
 pub(crate) use self::unresolved_named_arg as f;
 
@@ -15,4 +18,19 @@ pub(crate) fn unresolved_named_arg(
         format!("no parameter `{}` found for `{}`", d.name, d.callee),
         d.expr,
     )
+    .with_fixes(fixes(ctx, d))
+}
+
+fn fixes(ctx: &DiagnosticsContext<'_>, d: &hir::UnresolvedNamedArg) -> Option<Vec<Assist>> {
+    let tree = ctx.sema.parse(d.expr.file_id);
+    let name = d.expr.value.to_node(&tree)?;
+    let u_range = ctx.u_range(ts_range_to_text_range(&name.range()))?;
+
+    replace_with_candidates_fixes(
+        ctx,
+        "change_named_arg",
+        d.expr.file_id,
+        u_range,
+        &d.candidates,
+    )
 }
@@ -0,0 +1,45 @@
+use base_db::SourceDatabaseExt;
+use ide_db::{unused_includes, Assist, IncludeDirective, TextEdit};
+use line_index::{TextRange, TextSize};
+
+use crate::{fix, Diagnostic, DiagnosticCode, DiagnosticsContext, Severity};
+
+// Diagnostic: unused-include
+//
+// This diagnostic is triggered if nothing of an included file, or of the files it includes, is
+// used. It can be silenced for a single include with a `// IWYU pragma: keep` comment after it.
+pub(crate) fn unused_include(ctx: &DiagnosticsContext<'_>, acc: &mut Vec<Diagnostic>) {
+    for directive in unused_includes(&ctx.sema, ctx.file_id) {
+        let d = Diagnostic::new_for_s_range(
+            ctx,
+            DiagnosticCode::Lint("unused-include", Severity::WeakWarning),
+            format!("nothing of `{}` is used", directive.path),
+            directive.s_range,
+        )
+        .with_unused(true)
+        .with_fixes(fixes(ctx, &directive));
+        acc.push(d);
+    }
+}
+
+fn fixes(ctx: &DiagnosticsContext<'_>, directive: &IncludeDirective) -> Option<Vec<Assist>> {
+    let u_range = ctx.u_range(directive.s_range)?;
+    let file_text = ctx.sema.db.file_text(ctx.file_id);
+    let line_start = file_text[..u_range.start().into()]
+        .rfind('\n')
+        .map_or(0, |it| it + 1);
+    let line_end = file_text[u_range.end().into()..]
+        .find('\n')
+        .map_or(file_text.len(), |it| usize::from(u_range.end()) + it + 1);
+    let range = TextRange::new(
+        TextSize::new(line_start as u32),
+        TextSize::new(line_end as u32),
+    );
+    Some(vec![fix(
+        ctx,
+        "remove_unused_include",
+        "Remove unused include",
+        u_range,
+        |it| it.insert(ctx.file_id, TextEdit::new(range, String::new())),
+    )])
+}
@@ -0,0 +1,48 @@
+use base_db::SourceDatabase;
+use ide_db::CallbacksDatabase;
+
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext, Severity};
+
+pub(crate) use self::unused_variable as f;
+
+// Diagnostic: unused-variable, unused-parameter
+//
+// This diagnostic is triggered if a local variable, or a parameter of a function which is not
+// `public`, is never read. It is not triggered for the names starting with `_`, nor for the
+// parameters of the functions used as callbacks, as their signature is imposed.
+pub(crate) fn unused_variable(
+    ctx: &DiagnosticsContext<'_>,
+    d: &hir::UnusedVariable,
+) -> Option<Diagnostic> {
+    let (code, message) = match d.param_of {
+        Some(function) => {
+            if is_callback(ctx, function) {
+                return None;
+            }
+            ("unused-parameter", format!("unused parameter `{}`", d.name))
+        }
+        None => ("unused-variable", format!("unused variable `{}`", d.name)),
+    };
+
+    Diagnostic::new_with_syntax_node_ptr(
+        ctx,
+        DiagnosticCode::Lint(code, Severity::WeakWarning),
+        message,
+        d.expr,
+    )
+    .with_unused(true)
+    .into()
+}
+
+/// Whether the function is referenced elsewhere than in a call, e.g. passed to `CreateTimer`.
+fn is_callback(ctx: &DiagnosticsContext<'_>, function: hir::Function) -> bool {
+    let db = ctx.sema.db;
+    let file_ids = db
+        .projet_subgraph(ctx.file_id)
+        .map(|graph| graph.nodes.iter().map(|it| it.file_id).collect::<Vec<_>>())
+        .unwrap_or_else(|| vec![ctx.file_id]);
+
+    file_ids
+        .into_iter()
+        .any(|file_id| db.file_callbacks(file_id).contains(&function))
+}
@@ -1,8 +1,10 @@
 use base_db::Tree;
 use fxhash::FxHashSet;
 use hir::{AnyDiagnostic, Semantics};
-use hir_def::{InFile, NodePtr};
-use ide_db::RootDatabase;
+use hir_def::{InFile, Name, NodePtr};
+use ide_db::{
+    Assist, AssistId, AssistKind, AssistResolveStrategy, RootDatabase, SourceChange, TextEdit,
+};
 use line_index::{TextRange, TextSize};
 use queries::ERROR_QUERY;
 use streaming_iterator::StreamingIterator;
@@ -38,8 +40,7 @@ pub struct Diagnostic {
     pub severity: Severity,
     pub unused: bool,
     pub experimental: bool,
-    // pub fixes: Option<Vec<Assist>>,
-    // The node that will be affected by `#[allow]` and similar attributes.
+    pub fixes: Option<Vec<Assist>>,
 }
 
 impl Diagnostic {
@@ -90,6 +91,7 @@ impl Diagnostic {
             },
             unused: false,
             experimental: false,
+            fixes: None,
         }
     }
 
@@ -99,10 +101,10 @@ impl Diagnostic {
         self
     }
 
-    // fn with_fixes(mut self, fixes: Option<Vec<Assist>>) -> Diagnostic {
-    //     self.fixes = fixes;
-    //     self
-    // }
+    fn with_fixes(mut self, fixes: Option<Vec<Assist>>) -> Diagnostic {
+        self.fixes = fixes;
+        self
+    }
 
     fn with_unused(mut self, unused: bool) -> Diagnostic {
         self.unused = unused;
@@ -118,22 +120,38 @@ pub enum Severity {
 }
 
 struct DiagnosticsContext<'a> {
-    #[allow(unused)]
     config: &'a DiagnosticsConfig,
+    /// Which fixes have their source change computed.
+    resolve: &'a AssistResolveStrategy,
     sema: Semantics<'a, RootDatabase>,
     file_id: FileId,
 }
 
+impl DiagnosticsContext<'_> {
+    /// See [`SourceMap::editable_u_range`](preprocessor::SourceMap::editable_u_range).
+    fn u_range(&self, s_range: TextRange) -> Option<TextRange> {
+        self.sema
+            .preprocess_file(self.file_id)
+            .source_map()
+            .editable_u_range(s_range)
+    }
+}
+
 pub struct DiagnosticsConfig {
     /// Whether native diagnostics are enabled.
     pub enabled: bool,
     pub disable_experimental: bool,
+    /// Codes of the diagnostics which are not reported, e.g. `unused-include`.
     pub disabled: FxHashSet<String>,
+    /// Names of the functions whose last `char[]` parameter is a format string, in addition to
+    /// the ones declared with `const char[] format, any ...`.
+    pub format_functions: FxHashSet<String>,
 }
 
 pub fn diagnostics(
     db: &RootDatabase,
     config: &DiagnosticsConfig,
+    resolve: &AssistResolveStrategy,
     file_id: FileId,
 ) -> Vec<Diagnostic> {
     let sema = Semantics::new(db);
@@ -144,6 +162,7 @@ pub fn diagnostics(
     let file = sema.file_to_def(file_id);
     let ctx = DiagnosticsContext {
         config,
+        resolve,
         sema,
         file_id,
     };
@@ -171,13 +190,89 @@ pub fn diagnostics(
             AnyDiagnostic::UnresolvedMacro(d) => handlers::unresolved_macro::f(&ctx, &d),
             AnyDiagnostic::InactiveCode(d) => handlers::inactive_code::f(&ctx, &d),
             AnyDiagnostic::InvalidUseOfThis(d) => handlers::invalid_use_of_this::f(&ctx, &d),
+            AnyDiagnostic::UnresolvedIdent(d) => handlers::unresolved_ident::f(&ctx, &d),
+            AnyDiagnostic::TypeMismatch(d) => handlers::type_mismatch::f(&ctx, &d),
+            AnyDiagnostic::CallbackSignatureMismatch(d) => {
+                handlers::callback_signature_mismatch::f(&ctx, &d)
+            }
+            AnyDiagnostic::UnreachableCode(d) => handlers::unreachable_code::f(&ctx, &d),
+            AnyDiagnostic::MissingReturn(d) => handlers::missing_return::f(&ctx, &d),
+            AnyDiagnostic::UnusedVariable(d) => match handlers::unused_variable::f(&ctx, &d) {
+                Some(d) => d,
+                None => continue,
+            },
         };
         res.push(d);
     }
 
+    if !ctx.config.disabled.contains("unused-include") {
+        handlers::unused_include::unused_include(&ctx, &mut res);
+    }
+    if !ctx.config.disabled.contains("dead-code") {
+        handlers::dead_code::dead_code(&ctx, &mut res);
+    }
+    if !ctx.config.disabled.contains("format-string") {
+        handlers::format_string::format_string(&ctx, &mut res);
+    }
+    if !ctx.config.disabled.contains("buffer-size") {
+        handlers::buffer_size::buffer_size(&ctx, &mut res);
+    }
+
+    res.retain(|d| !ctx.config.disabled.contains(d.code.as_str()));
+
     res
 }
 
+/// Returns a quick fix. `f` computes its source change and is only called if the fix has to be
+/// resolved.
+fn fix(
+    ctx: &DiagnosticsContext<'_>,
+    id: &'static str,
+    label: &str,
+    target: TextRange,
+    f: impl FnOnce(&mut SourceChange),
+) -> Assist {
+    let id = AssistId(id, AssistKind::QuickFix);
+    let source_change = ctx.resolve.should_resolve(&id).then(|| {
+        let mut source_change = SourceChange::default();
+        f(&mut source_change);
+        source_change
+    });
+    Assist {
+        id,
+        label: label.to_string(),
+        target,
+        source_change,
+    }
+}
+
+/// Returns a fix replacing `u_range` by each of the candidates.
+fn replace_with_candidates_fixes(
+    ctx: &DiagnosticsContext<'_>,
+    id: &'static str,
+    file_id: FileId,
+    u_range: TextRange,
+    candidates: &[Name],
+) -> Option<Vec<Assist>> {
+    if candidates.is_empty() {
+        return None;
+    }
+    let fixes = candidates
+        .iter()
+        .map(|candidate| {
+            fix(
+                ctx,
+                id,
+                &format!("Change to `{candidate}`"),
+                u_range,
+                |it| it.insert(file_id, TextEdit::new(u_range, candidate.to_string())),
+            )
+        })
+        .collect();
+
+    Some(fixes)
+}
+
 /// Capture all the syntax errors of a document and add them to its Local Diagnostics.
 /// Overrides all previous Local Diagnostics.
 ///
@@ -0,0 +1,199 @@
+//! Assists are the code actions which are not attached to a diagnostic.
+//!
+//! Each assist lives in its own module in `assists/` and is registered in [`HANDLERS`].
+
+use base_db::FileRange;
+use hir::Semantics;
+use ide_db::{Assist, AssistId, AssistResolveStrategy, RootDatabase, SourceChange};
+use line_index::TextRange;
+use syntax::TSKind;
+use tree_sitter::Node;
+use vfs::FileId;
+
+use crate::highlight_related::is_passed_by_ref;
+
+mod convert_to_new_syntax;
+mod extract_function;
+mod generate_methodmap_members;
+mod inline_local_variable;
+mod inline_macro;
+mod organize_includes;
+
+type Handler = fn(&mut Assists, &AssistContext) -> Option<()>;
+
+/// All the assists, in the order in which they are computed.
+const HANDLERS: &[Handler] = &[
+    convert_to_new_syntax::convert_to_new_syntax,
+    extract_function::extract_function,
+    inline_local_variable::inline_local_variable,
+    inline_macro::inline_macro,
+    generate_methodmap_members::generate_property,
+    generate_methodmap_members::generate_constructor,
+    generate_methodmap_members::generate_enum_struct_accessors,
+    organize_includes::organize_includes,
+];
+
+pub(crate) struct AssistContext<'a> {
+    pub(crate) sema: Semantics<'a, RootDatabase>,
+    /// Range of the user's selection.
+    pub(crate) frange: FileRange,
+}
+
+impl AssistContext<'_> {
+    /// Returns the range of the user's selection in the preprocessed text.
+    pub(crate) fn s_range(&self) -> TextRange {
+        let preprocessing_results = self.sema.preprocess_file(self.frange.file_id);
+        let source_map = preprocessing_results.source_map();
+        let start = source_map.closest_s_position_always(self.frange.range.start());
+        let end = source_map.closest_s_position_always(self.frange.range.end());
+        TextRange::new(start, end.max(start))
+    }
+
+    /// Returns the range of `s_range` in the file of the user, or [`None`] if it cannot be edited.
+    pub(crate) fn u_range(&self, s_range: TextRange) -> Option<TextRange> {
+        self.sema
+            .preprocess_file(self.frange.file_id)
+            .source_map()
+            .editable_u_range(s_range)
+    }
+}
+
+/// Accumulator of the assists which apply to an [`AssistContext`].
+pub(crate) struct Assists {
+    resolve: AssistResolveStrategy,
+    buf: Vec<Assist>,
+}
+
+impl Assists {
+    fn new(resolve: AssistResolveStrategy) -> Self {
+        Self {
+            resolve,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Adds an assist. `f` computes its source change and is only called if the assist has to
+    /// be resolved.
+    pub(crate) fn add(
+        &mut self,
+        id: AssistId,
+        label: impl Into<String>,
+        target: TextRange,
+        f: impl FnOnce(&mut SourceChange),
+    ) -> Option<()> {
+        let source_change = self.resolve.should_resolve(&id).then(|| {
+            let mut source_change = SourceChange::default();
+            f(&mut source_change);
+            source_change
+        });
+        self.buf.push(Assist {
+            id,
+            label: label.into(),
+            target,
+            source_change,
+        });
+
+        Some(())
+    }
+
+    fn finish(mut self) -> Vec<Assist> {
+        self.buf.sort_by_key(|assist| assist.target.len());
+        self.buf
+    }
+}
+
+/// Returns the assists which apply to the range.
+pub(crate) fn assists(
+    db: &RootDatabase,
+    resolve: AssistResolveStrategy,
+    frange: FileRange,
+) -> Vec<Assist> {
+    let ctx = AssistContext {
+        sema: Semantics::new(db),
+        frange,
+    };
+    let mut acc = Assists::new(resolve);
+    for handler in HANDLERS {
+        handler(&mut acc, &ctx);
+    }
+
+    acc.finish()
+}
+
+pub(crate) use organize_includes::organize_includes_edits;
+
+/// Returns the item of the file in which `node` is.
+pub(crate) fn top_level_item(node: Node) -> Option<Node> {
+    let mut item = node;
+    while TSKind::from(item.parent()?) != TSKind::source_file {
+        item = item.parent()?;
+    }
+
+    Some(item)
+}
+
+/// Returns the identifiers of the subtree of `node`, in order.
+pub(crate) fn identifiers(node: Node) -> Vec<Node> {
+    let mut res = Vec::new();
+    let mut stack = vec![node];
+    while let Some(node) = stack.pop() {
+        if TSKind::from(node) == TSKind::identifier {
+            res.push(node);
+            continue;
+        }
+        let children = node.named_children(&mut node.walk()).collect::<Vec<_>>();
+        stack.extend(children.into_iter().rev());
+    }
+
+    res
+}
+
+/// Whether the identifier is assigned, e.g. `x = 1` or `x++`, or passed to a non-const
+/// parameter by reference, e.g. `GetValue(x)` with `void GetValue(int &value)`.
+pub(crate) fn is_modified(
+    sema: &Semantics<RootDatabase>,
+    file_id: FileId,
+    identifier: &Node,
+) -> bool {
+    let Some(parent) = identifier.parent() else {
+        return false;
+    };
+    match TSKind::from(parent) {
+        TSKind::assignment_expression => parent
+            .child_by_field_name("left")
+            .is_some_and(|left| left.id() == identifier.id()),
+        TSKind::update_expression => true,
+        TSKind::call_arguments => is_passed_by_ref(sema, file_id, &parent, identifier),
+        TSKind::named_arg => parent
+            .parent()
+            .is_some_and(|arguments| is_passed_by_ref(sema, file_id, &arguments, &parent)),
+        _ => false,
+    }
+}
+
+/// Whether `expr` has to be wrapped in parentheses to keep its grouping once it replaces a
+/// child of `parent`, e.g. `a + b` in `x * a`.
+pub(crate) fn needs_parentheses(expr: &Node, parent: &Node) -> bool {
+    let is_compound = matches!(
+        TSKind::from(expr),
+        TSKind::binary_expression
+            | TSKind::ternary_expression
+            | TSKind::assignment_expression
+            | TSKind::comma_expression
+    );
+    let binds_tighter = matches!(
+        TSKind::from(parent),
+        TSKind::binary_expression
+            | TSKind::unary_expression
+            | TSKind::update_expression
+            | TSKind::ternary_expression
+            | TSKind::field_access
+            | TSKind::array_indexed_access
+            | TSKind::scope_access
+            | TSKind::call_expression
+            | TSKind::view_as
+            | TSKind::old_type_cast
+    );
+
+    is_compound && binds_tighter
+}
@@ -0,0 +1,313 @@
+//! Assist converting the declarations of the old syntax to the new (transitional) syntax.
+//!
+//! ```sourcepawn
+//! decl String:buf[64], count;
+//! public Action:Cmd(client, args)
+//! ```
+//! becomes
+//! ```sourcepawn
+//! char buf[64];
+//! int count;
+//! public Action Cmd(int client, int args)
+//! ```
+
+use std::sync::Arc;
+
+use base_db::SourceDatabaseExt;
+use hir_def::TypeRef;
+use ide_db::{AssistId, AssistKind, TextEdit};
+use itertools::Itertools;
+use line_index::{TextRange, TextSize};
+use syntax::TSKind;
+use tree_sitter::Node;
+
+use super::{AssistContext, Assists};
+
+pub(super) fn convert_to_new_syntax(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let file_id = ctx.frange.file_id;
+    let tree = ctx.sema.parse(file_id);
+    let s_range = ctx.s_range();
+    let mut declarations = Vec::new();
+    if s_range.is_empty() {
+        declarations.extend(declaration_at(tree.root_node(), s_range.start()));
+    } else {
+        declarations_in_range(tree.root_node(), s_range, &mut declarations);
+    }
+
+    let converter = Converter {
+        ctx,
+        file_text: ctx.sema.db.file_text(file_id),
+        source: ctx.sema.preprocessed_text(file_id),
+    };
+    let edits = declarations
+        .iter()
+        .filter_map(|node| converter.convert(node))
+        .flatten()
+        .collect::<Vec<_>>();
+    let target = edits
+        .iter()
+        .map(|edit| *edit.range())
+        .reduce(TextRange::cover)?;
+
+    acc.add(
+        AssistId("convert_to_new_syntax", AssistKind::RefactorRewrite),
+        "Convert to new syntax",
+        target,
+        |source_change| {
+            for edit in edits {
+                source_change.insert(file_id, edit);
+            }
+        },
+    )
+}
+
+/// Returns the old syntax declaration at `pos`, if any.
+fn declaration_at(root: Node, pos: TextSize) -> Option<Node> {
+    let mut node = root.descendant_for_byte_range(pos.into(), pos.into())?;
+    loop {
+        match TSKind::from(node) {
+            TSKind::old_global_variable_declaration
+            | TSKind::old_variable_declaration_statement
+            | TSKind::old_for_loop_variable_declaration_statement
+            | TSKind::function_declaration => return Some(node),
+            TSKind::function_definition => {
+                // Only the signature of the function is converted.
+                return node
+                    .child_by_field_name("body")
+                    .is_none_or(|body| usize::from(pos) <= body.start_byte())
+                    .then_some(node);
+            }
+            _ => node = node.parent()?,
+        }
+    }
+}
+
+/// Collects the old syntax declarations which intersect `range`.
+fn declarations_in_range<'tree>(node: Node<'tree>, range: TextRange, acc: &mut Vec<Node<'tree>>) {
+    let intersects = |start: usize, end: usize| {
+        usize::from(range.start()) < end && start < usize::from(range.end())
+    };
+    for child in node.children(&mut node.walk()) {
+        if !intersects(child.start_byte(), child.end_byte()) {
+            continue;
+        }
+        match TSKind::from(child) {
+            TSKind::old_global_variable_declaration
+            | TSKind::old_variable_declaration_statement
+            | TSKind::old_for_loop_variable_declaration_statement
+            | TSKind::function_declaration => acc.push(child),
+            TSKind::function_definition => {
+                let body = child.child_by_field_name("body");
+                let signature_end = body.map_or(child.end_byte(), |body| body.start_byte());
+                if intersects(child.start_byte(), signature_end) {
+                    acc.push(child);
+                }
+                if let Some(body) = body {
+                    declarations_in_range(body, range, acc);
+                }
+            }
+            _ => declarations_in_range(child, range, acc),
+        }
+    }
+}
+
+struct Converter<'a> {
+    ctx: &'a AssistContext<'a>,
+    file_text: Arc<str>,
+    source: Arc<str>,
+}
+
+impl Converter<'_> {
+    /// Returns the edits converting the declaration to the new syntax, or [`None`] if it cannot
+    /// be converted.
+    fn convert(&self, node: &Node) -> Option<Vec<TextEdit>> {
+        let edits = match TSKind::from(node) {
+            TSKind::old_global_variable_declaration
+            | TSKind::old_variable_declaration_statement => {
+                vec![self.convert_variables(node, true)?]
+            }
+            TSKind::old_for_loop_variable_declaration_statement => {
+                vec![self.convert_variables(node, false)?]
+            }
+            TSKind::function_definition | TSKind::function_declaration => {
+                self.convert_function(node)?
+            }
+            _ => return None,
+        };
+
+        (!edits.is_empty()).then_some(edits)
+    }
+
+    /// Converts a `new` or `decl` declaration.
+    ///
+    /// The declarators of the old syntax each have their own tag, unlike the declarators of the
+    /// new syntax, so the declaration is split into one declaration per type if `splittable`.
+    fn convert_variables(&self, node: &Node, splittable: bool) -> Option<TextEdit> {
+        let mut prefix = String::new();
+        let mut groups: Vec<(String, Vec<&str>)> = Vec::new();
+        let mut semicolon = "";
+        for child in node.children(&mut node.walk()) {
+            match TSKind::from(child) {
+                TSKind::visibility | TSKind::variable_storage_class => {
+                    prefix.push_str(self.text(child.start_byte(), child.end_byte())?);
+                    prefix.push(' ');
+                }
+                TSKind::old_variable_declaration => {
+                    let name = child.child_by_field_name("name")?;
+                    let type_ = self.new_type(child.child_by_field_name("type"));
+                    // Keep the dimensions and the initial value as they were written.
+                    let declarator = self.text(name.start_byte(), child.end_byte())?;
+                    match groups.last_mut() {
+                        Some((last_type, declarators)) if *last_type == type_ => {
+                            declarators.push(declarator)
+                        }
+                        _ => groups.push((type_, vec![declarator])),
+                    }
+                }
+                TSKind::anon_SEMI_ => semicolon = ";",
+                _ => (),
+            }
+        }
+        if groups.is_empty() || (!splittable && groups.len() > 1) {
+            return None;
+        }
+
+        let range = self.u_range(node.start_byte(), node.end_byte())?;
+        let separator = format!("\n{}", self.indent(range.start()));
+        let text = groups
+            .iter()
+            .map(|(type_, declarators)| {
+                format!("{prefix}{type_} {}{semicolon}", declarators.join(", "))
+            })
+            .join(&separator);
+
+        Some(TextEdit::new(range, text))
+    }
+
+    /// Converts the return type and the parameters of a function.
+    fn convert_function(&self, node: &Node) -> Option<Vec<TextEdit>> {
+        let name = node.child_by_field_name("name")?;
+        let mut edits = Vec::new();
+        let return_type = node
+            .children_by_field_name("returnType", &mut node.walk())
+            .find(|child| {
+                matches!(
+                    TSKind::from(child),
+                    TSKind::old_type | TSKind::r#type | TSKind::any_type
+                )
+            });
+        match return_type {
+            Some(return_type) if TSKind::from(return_type) == TSKind::old_type => {
+                edits.push(TextEdit::new(
+                    self.u_range(return_type.start_byte(), name.start_byte())?,
+                    format!("{} ", self.new_type(Some(return_type))),
+                ));
+            }
+            Some(_) => (),
+            None => {
+                // Functions without a return type return an `int`, unless they never return a
+                // value.
+                let type_ = match node.child_by_field_name("body") {
+                    Some(body) if !returns_value(body) => "void",
+                    _ => "int",
+                };
+                edits.push(TextEdit::new(
+                    self.u_range(name.start_byte(), name.start_byte())?,
+                    format!("{type_} "),
+                ));
+            }
+        }
+
+        let parameters = node.child_by_field_name("parameters")?;
+        for parameter in parameters.named_children(&mut parameters.walk()) {
+            match TSKind::from(parameter) {
+                TSKind::parameter_declaration => {
+                    edits.extend(self.convert_parameter(&parameter));
+                }
+                TSKind::rest_parameter => {
+                    edits.extend(self.convert_rest_parameter(&parameter));
+                }
+                _ => (),
+            }
+        }
+
+        Some(edits)
+    }
+
+    /// Converts `&Float:pos[3]` to `float &pos[3]`.
+    fn convert_parameter(&self, node: &Node) -> Option<TextEdit> {
+        let name = node.child_by_field_name("name")?;
+        let mut start = None;
+        let mut old_type = None;
+        let mut is_ref = false;
+        for child in node.children_by_field_name("type", &mut node.walk()) {
+            match TSKind::from(child) {
+                TSKind::anon_AMP => is_ref = true,
+                TSKind::old_type => old_type = Some(child),
+                // Already in the new syntax.
+                _ => return None,
+            }
+            start.get_or_insert(child.start_byte());
+        }
+        let type_ = self.new_type(old_type);
+        let ref_ = if is_ref { "&" } else { "" };
+
+        Some(TextEdit::new(
+            self.u_range(start.unwrap_or(name.start_byte()), name.start_byte())?,
+            format!("{type_} {ref_}"),
+        ))
+    }
+
+    /// Converts `any:...` to `any ...`.
+    fn convert_rest_parameter(&self, node: &Node) -> Option<TextEdit> {
+        let type_ = node.child_by_field_name("type");
+        let start = match type_ {
+            Some(type_) if TSKind::from(type_) == TSKind::old_type => type_.start_byte(),
+            Some(_) => return None,
+            None => node.end_byte() - "...".len(),
+        };
+
+        Some(TextEdit::new(
+            self.u_range(start, node.end_byte())?,
+            format!("{} ...", self.new_type(type_)),
+        ))
+    }
+
+    /// Returns the new syntax type of an old syntax tag, `int` if there is none.
+    fn new_type(&self, old_type: Option<Node>) -> String {
+        old_type
+            .map_or(TypeRef::Int, |it| {
+                TypeRef::from_node(&it, &self.source).to_new_syntax()
+            })
+            .to_string()
+    }
+
+    fn u_range(&self, start: usize, end: usize) -> Option<TextRange> {
+        let s_range = TextRange::new(TextSize::new(start as u32), TextSize::new(end as u32));
+        self.ctx.u_range(s_range)
+    }
+
+    /// Returns the text, as written by the user, between two offsets of the preprocessed text.
+    fn text(&self, start: usize, end: usize) -> Option<&str> {
+        let range = self.u_range(start, end)?;
+        self.file_text.get(std::ops::Range::<usize>::from(range))
+    }
+
+    /// Returns the indentation of the line of `offset`.
+    fn indent(&self, offset: TextSize) -> &str {
+        let line = self.file_text[..offset.into()]
+            .rsplit('\n')
+            .next()
+            .unwrap_or_default();
+        let len = line.len() - line.trim_start().len();
+        &line[..len]
+    }
+}
+
+/// Whether a `return` statement of the function returns a value.
+fn returns_value(node: Node) -> bool {
+    if TSKind::from(node) == TSKind::return_statement {
+        return node.child_by_field_name("expression").is_some();
+    }
+    node.named_children(&mut node.walk()).any(returns_value)
+}
@@ -0,0 +1,405 @@
+//! Assist extracting the selected statements of a function body into a new function.
+//!
+//! ```sourcepawn
+//! public void OnPluginStart()
+//! {
+//!     int count = 0;
+//!     count++;
+//!     PrintToServer("%d", count);
+//! }
+//! ```
+//! becomes, once `count++;` is extracted
+//! ```sourcepawn
+//! public void OnPluginStart()
+//! {
+//!     int count = 0;
+//!     ExtractedFunction(count);
+//!     PrintToServer("%d", count);
+//! }
+//!
+//! void ExtractedFunction(int &count)
+//! {
+//!     count++;
+//! }
+//! ```
+//!
+//! The free variables of the selection are found on the syntax tree rather than on the
+//! [`ExprScopes`](hir_def::body::scope::ExprScopes) of the lowered body: the edits are made on
+//! tree-sitter nodes, and each identifier is resolved with [`Semantics::find_def`], which uses
+//! the `Resolver` built from these scopes. The ones bound to a local declared outside of the selection
+//! become parameters.
+//!
+//! [`Semantics::find_def`]: hir::Semantics::find_def
+
+use base_db::SourceDatabaseExt;
+use hir::{DefResolution, HasSource};
+use hir_def::TypeRef;
+use ide_db::{AssistId, AssistKind, TextEdit};
+use itertools::Itertools;
+use line_index::{TextRange, TextSize};
+use syntax::TSKind;
+use tree_sitter::Node;
+
+use super::{identifiers, is_modified, top_level_item, AssistContext, Assists};
+
+pub(super) fn extract_function(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let file_id = ctx.frange.file_id;
+    let s_range = ctx.s_range();
+    if s_range.is_empty() {
+        return None;
+    }
+    let tree = ctx.sema.parse(file_id);
+    let source = ctx.sema.preprocessed_text(file_id);
+    let file_text = ctx.sema.db.file_text(file_id);
+
+    let statements = selected_statements(tree.root_node(), s_range)?;
+    let first = statements.first()?;
+    let last = statements.last()?;
+    let span = TextRange::new(
+        TextSize::new(first.start_byte() as u32),
+        TextSize::new(last.end_byte() as u32),
+    );
+    if !statements.iter().all(|stmt| can_be_extracted(*stmt)) {
+        return None;
+    }
+    let item = top_level_item(*first)?;
+
+    let selected = statements
+        .iter()
+        .flat_map(|stmt| identifiers(*stmt))
+        .collect::<Vec<_>>();
+    let local_binding = |node: &Node| match ctx.sema.find_def(file_id, node)? {
+        def @ DefResolution::Local(_) => def
+            .source(ctx.sema.db, &tree)
+            .filter(|it| it.file_id == file_id)
+            .map(|it| it.value),
+        _ => None,
+    };
+    let in_span = |node: &Node| {
+        usize::from(span.start()) <= node.start_byte() && node.end_byte() <= usize::from(span.end())
+    };
+
+    // Locals declared before the selection become parameters of the new function.
+    let mut params: Vec<Param> = Vec::new();
+    for identifier in selected.iter() {
+        let Some(binding) = local_binding(identifier) else {
+            continue;
+        };
+        if in_span(&binding) {
+            continue;
+        }
+        let modified = is_modified(&ctx.sema, file_id, identifier);
+        match params.iter_mut().find(|param| param.binding == binding) {
+            Some(param) => param.modified |= modified,
+            None => params.push(Param {
+                binding,
+                name: identifier.utf8_text(source.as_bytes()).ok()?.to_string(),
+                modified,
+            }),
+        }
+    }
+
+    // Locals declared in the selection which are still used after it flow out of the new
+    // function through its return value.
+    let declared = selected
+        .iter()
+        .filter(|identifier| {
+            identifier.parent().is_some_and(|parent| {
+                matches!(
+                    TSKind::from(parent),
+                    TSKind::variable_declaration
+                        | TSKind::old_variable_declaration
+                        | TSKind::dynamic_array_declaration
+                )
+            })
+        })
+        .filter_map(|identifier| identifier.utf8_text(source.as_bytes()).ok())
+        .collect::<Vec<_>>();
+    let out_bindings = identifiers(item)
+        .iter()
+        .filter(|identifier| identifier.start_byte() >= usize::from(span.end()))
+        .filter(|identifier| {
+            identifier
+                .utf8_text(source.as_bytes())
+                .is_ok_and(|text| declared.contains(&text))
+        })
+        .filter_map(local_binding)
+        .filter(in_span)
+        .unique_by(|binding| binding.id())
+        .collect::<Vec<_>>();
+    let out = match out_bindings.as_slice() {
+        [] => None,
+        [binding] => {
+            let type_ = LocalType::from_binding(ctx, *binding, &source)?;
+            if !type_.dims.is_empty() {
+                // Arrays cannot be returned.
+                return None;
+            }
+            let name = binding.child_by_field_name("name")?;
+            Some((type_.base, name.utf8_text(source.as_bytes()).ok()?))
+        }
+        _ => return None,
+    };
+
+    let u_span = ctx.u_range(span)?;
+    let item_end = ctx
+        .u_range(TextRange::empty(TextSize::new(item.end_byte() as u32)))?
+        .end();
+    let indent_unit = body_indent_unit(ctx, &file_text, item).unwrap_or("\t");
+    let name = function_name(&source);
+
+    let params_text = params
+        .iter()
+        .map(|param| param.render(ctx, &source))
+        .collect::<Option<Vec<_>>>()?
+        .join(", ");
+    let args = params.iter().map(|param| param.name.as_str()).join(", ");
+    let call = match &out {
+        Some((type_, out_name)) => format!("{type_} {out_name} = {name}({args});"),
+        None => format!("{name}({args});"),
+    };
+
+    let statement_indent = indent(&file_text, u_span.start());
+    let mut body = file_text[std::ops::Range::<usize>::from(u_span)]
+        .lines()
+        .map(|line| line.strip_prefix(statement_indent).unwrap_or(line))
+        .map(|line| {
+            if line.trim().is_empty() {
+                String::new()
+            } else {
+                format!("{indent_unit}{line}")
+            }
+        })
+        .join("\n");
+    if let Some((_, out_name)) = &out {
+        body.push_str(&format!("\n{indent_unit}return {out_name};"));
+    }
+    let return_type = out.as_ref().map_or("void", |(type_, _)| type_.as_str());
+    let function = format!("\n\n{return_type} {name}({params_text})\n{{\n{body}\n}}");
+
+    acc.add(
+        AssistId("extract_function", AssistKind::RefactorExtract),
+        "Extract into function",
+        u_span,
+        |source_change| {
+            source_change.insert(file_id, TextEdit::new(u_span, call));
+            source_change.insert(file_id, TextEdit::new(TextRange::empty(item_end), function));
+        },
+    )
+}
+
+/// A local, declared before the selection, which is used in the selection.
+struct Param<'tree> {
+    /// Declaration of the local.
+    binding: Node<'tree>,
+    name: String,
+    /// Whether the local is assigned in the selection.
+    modified: bool,
+}
+
+impl Param<'_> {
+    /// Renders the parameter of the new function. Arrays are always passed by reference, other
+    /// locals only if the selection modifies them.
+    fn render(&self, ctx: &AssistContext, source: &str) -> Option<String> {
+        let type_ = LocalType::from_binding(ctx, self.binding, source)?;
+        let res = if !type_.dims.is_empty() {
+            let const_ = if type_.is_const { "const " } else { "" };
+            format!(
+                "{const_}{} {}{}",
+                type_.base,
+                self.name,
+                type_.dims.concat()
+            )
+        } else if self.modified {
+            format!("{} &{}", type_.base, self.name)
+        } else {
+            format!("{} {}", type_.base, self.name)
+        };
+
+        Some(res)
+    }
+}
+
+/// Type of a local, in the new syntax.
+struct LocalType {
+    base: String,
+    /// Dimensions of the array, fixed sizes being kept so that `sizeof` still works.
+    dims: Vec<String>,
+    is_const: bool,
+}
+
+impl LocalType {
+    /// Computes the type of a local from its declaration, which is either a parameter or a
+    /// declarator of a variable declaration.
+    fn from_binding(ctx: &AssistContext, binding: Node, source: &str) -> Option<Self> {
+        let text = |node: Node| node.utf8_text(source.as_bytes()).ok().map(String::from);
+        let mut res = LocalType {
+            base: TypeRef::Int.to_string(),
+            dims: dimensions(ctx, binding),
+            is_const: false,
+        };
+        let (type_nodes, declaration) = match TSKind::from(binding) {
+            TSKind::parameter_declaration => (
+                binding
+                    .children_by_field_name("type", &mut binding.walk())
+                    .collect::<Vec<_>>(),
+                binding,
+            ),
+            TSKind::old_variable_declaration => (
+                binding
+                    .children_by_field_name("type", &mut binding.walk())
+                    .collect(),
+                binding.parent()?,
+            ),
+            _ => {
+                let statement = binding.parent()?;
+                res.dims.extend(dimensions(ctx, statement));
+                (
+                    statement
+                        .children_by_field_name("type", &mut statement.walk())
+                        .collect(),
+                    statement,
+                )
+            }
+        };
+        for type_node in type_nodes {
+            match TSKind::from(type_node) {
+                TSKind::r#type => res.base = text(type_node)?,
+                TSKind::array_type => {
+                    res.dims.extend(dimensions(ctx, type_node));
+                    let inner = type_node
+                        .named_children(&mut type_node.walk())
+                        .find(|child| TSKind::from(child) == TSKind::r#type)?;
+                    res.base = text(inner)?;
+                }
+                TSKind::old_type => {
+                    res.base = TypeRef::from_node(&type_node, source)
+                        .to_new_syntax()
+                        .to_string()
+                }
+                _ => (),
+            }
+        }
+        res.is_const = declaration
+            .children(&mut declaration.walk())
+            .filter(|child| TSKind::from(child) == TSKind::variable_storage_class)
+            .filter_map(text)
+            .any(|it| it.contains("const"));
+
+        Some(res)
+    }
+}
+
+/// Returns the dimensions of a declaration, as written by the user.
+fn dimensions(ctx: &AssistContext, node: Node) -> Vec<String> {
+    let file_text = ctx.sema.db.file_text(ctx.frange.file_id);
+    node.children(&mut node.walk())
+        .filter_map(|child| match TSKind::from(child) {
+            TSKind::dimension => Some("[]".to_string()),
+            TSKind::fixed_dimension => Some(
+                ctx.u_range(TextRange::new(
+                    TextSize::new(child.start_byte() as u32),
+                    TextSize::new(child.end_byte() as u32),
+                ))
+                .and_then(|range| file_text.get(std::ops::Range::<usize>::from(range)))
+                .unwrap_or("[]")
+                .to_string(),
+            ),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the statements of a block which are covered by `range`, or [`None`] if `range` does
+/// not exactly cover statements of the same block.
+fn selected_statements(root: Node, range: TextRange) -> Option<Vec<Node>> {
+    let (start, end) = (usize::from(range.start()), usize::from(range.end()));
+    let mut block = root.descendant_for_byte_range(start, end)?;
+    while TSKind::from(block) != TSKind::block {
+        block = block.parent()?;
+    }
+    let mut res = Vec::new();
+    for child in block.named_children(&mut block.walk()) {
+        if child.end_byte() <= start || end <= child.start_byte() {
+            continue;
+        }
+        if child.start_byte() < start || end < child.end_byte() {
+            return None;
+        }
+        res.push(child);
+    }
+    // Trim the comments surrounding the statements.
+    while res
+        .first()
+        .is_some_and(|it| TSKind::from(it) == TSKind::comment)
+    {
+        res.remove(0);
+    }
+    while res
+        .last()
+        .is_some_and(|it| TSKind::from(it) == TSKind::comment)
+    {
+        res.pop();
+    }
+
+    (!res.is_empty()).then_some(res)
+}
+
+/// Whether the control flow of `node` stays in the selection, i.e. it does not `return` and
+/// only `break`s or `continue`s loops of the selection. The body of a method cannot be extracted
+/// either if it uses `this`.
+fn can_be_extracted(node: Node) -> bool {
+    match TSKind::from(node) {
+        TSKind::return_statement
+        | TSKind::break_statement
+        | TSKind::continue_statement
+        | TSKind::this => return false,
+        TSKind::for_statement | TSKind::while_statement | TSKind::do_while_statement => {
+            return node
+                .named_children(&mut node.walk())
+                .all(|child| !contains_return_or_this(child));
+        }
+        _ => (),
+    }
+    node.named_children(&mut node.walk()).all(can_be_extracted)
+}
+
+fn contains_return_or_this(node: Node) -> bool {
+    matches!(TSKind::from(node), TSKind::return_statement | TSKind::this)
+        || node
+            .named_children(&mut node.walk())
+            .any(contains_return_or_this)
+}
+
+/// Returns a name for the new function which is not used in the file yet.
+fn function_name(source: &str) -> String {
+    let base = "ExtractedFunction";
+    (1..)
+        .map(|i| {
+            if i == 1 {
+                base.to_string()
+            } else {
+                format!("{base}{i}")
+            }
+        })
+        .find(|name| !source.contains(name.as_str()))
+        .unwrap_or_else(|| base.to_string())
+}
+
+/// Returns the indentation of the statements of the body of `item`.
+fn body_indent_unit<'a>(ctx: &AssistContext, file_text: &'a str, item: Node) -> Option<&'a str> {
+    let body = item.child_by_field_name("body")?;
+    let stmt = body.named_children(&mut body.walk()).next()?;
+    let range = ctx.u_range(TextRange::empty(TextSize::new(stmt.start_byte() as u32)))?;
+    Some(indent(file_text, range.start())).filter(|it| !it.is_empty())
+}
+
+/// Returns the indentation of the line of `offset`.
+fn indent(file_text: &str, offset: TextSize) -> &str {
+    let line = file_text[..offset.into()]
+        .rsplit('\n')
+        .next()
+        .unwrap_or_default();
+    let len = line.len() - line.trim_start().len();
+    &line[..len]
+}
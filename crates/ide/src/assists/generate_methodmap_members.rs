@@ -0,0 +1,429 @@
+//! Assists generating the members of a methodmap, mostly useful for the methodmaps which wrap a
+//! `StringMap` or a `KeyValues`:
+//! - a property with its getter and setter;
+//! - a constructor calling the inherited constructor;
+//! - accessors for the fields of an enum struct.
+//!
+//! ```sourcepawn
+//! methodmap Player < StringMap {
+//! }
+//! ```
+//! becomes, after adding an `int` property
+//! ```sourcepawn
+//! methodmap Player < StringMap {
+//!     property int NewProperty {
+//!         public get() {
+//!             int value;
+//!             this.GetValue("NewProperty", value);
+//!             return value;
+//!         }
+//!         public set(int value) {
+//!             this.SetValue("NewProperty", value);
+//!         }
+//!     }
+//! }
+//! ```
+
+use base_db::SourceDatabaseExt;
+use fxhash::FxHashSet;
+use hir::{DefResolution, EnumStruct, HasSource, Methodmap};
+use hir_def::{DefDatabase, TypeRef};
+use ide_db::{AssistId, AssistKind, TextEdit};
+use itertools::Itertools;
+use line_index::{TextRange, TextSize};
+use syntax::TSKind;
+use tree_sitter::Node;
+
+use super::{identifiers, AssistContext, Assists};
+
+/// Types offered for a new property.
+const PROPERTY_TYPES: &[TypeRef] = &[TypeRef::Int, TypeRef::Float, TypeRef::Bool];
+
+pub(super) fn generate_property(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let tree = ctx.sema.parse(ctx.frange.file_id);
+    let target = MethodmapTarget::at(ctx, tree.root_node())?;
+    let name = (1..)
+        .map(|i| match i {
+            1 => "NewProperty".to_string(),
+            _ => format!("NewProperty{i}"),
+        })
+        .find(|name| !target.items.contains(name))?;
+
+    for type_ in PROPERTY_TYPES {
+        let member = target.backing.property(type_, &name, &name);
+        target.add(
+            acc,
+            AssistId("generate_property", AssistKind::Generate),
+            format!("Add `{type_}` property"),
+            &[member],
+        );
+    }
+
+    Some(())
+}
+
+pub(super) fn generate_constructor(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let db = ctx.sema.db;
+    let tree = ctx.sema.parse(ctx.frange.file_id);
+    let target = MethodmapTarget::at(ctx, tree.root_node())?;
+    if db
+        .methodmap_data(target.methodmap.id())
+        .constructor
+        .is_some()
+    {
+        return None;
+    }
+
+    // Constructors are not inherited, use the one of the closest ancestor.
+    let (ancestor, constructor) = std::iter::successors(
+        db.methodmap_data(target.methodmap.id())
+            .inherits
+            .map(Methodmap::from),
+        |it| db.methodmap_data(it.id()).inherits.map(Methodmap::from),
+    )
+    .find_map(|ancestor| Some((ancestor, ancestor.constructor(db)?)))?;
+    let ancestor_file_id = DefResolution::from(ancestor).file_id(db);
+    let ancestor_tree = ctx.sema.parse(ancestor_file_id);
+    let ancestor_source = ctx.sema.preprocessed_text(ancestor_file_id);
+    let parameters = constructor
+        .source(db, &ancestor_tree)?
+        .value
+        .child_by_field_name("parameters")?;
+    let params = parameters
+        .named_children(&mut parameters.walk())
+        .filter(|param| TSKind::from(param) == TSKind::parameter_declaration)
+        .filter_map(|param| param.utf8_text(ancestor_source.as_bytes()).ok())
+        .join(", ");
+    let args = constructor
+        .parameters(db)
+        .into_iter()
+        .filter(|param| param != "...")
+        .join(", ");
+
+    let name = target.methodmap.name(db);
+    let ancestor_name = ancestor.name(db);
+    let member = format!(
+        "public {name}({params}) {{\n\treturn view_as<{name}>(new {ancestor_name}({args}));\n}}"
+    );
+    target.add(
+        acc,
+        AssistId("generate_constructor", AssistKind::Generate),
+        "Generate constructor".to_string(),
+        &[member],
+    )
+}
+
+pub(super) fn generate_enum_struct_accessors(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let db = ctx.sema.db;
+    let tree = ctx.sema.parse(ctx.frange.file_id);
+    let root = tree.root_node();
+    let target = MethodmapTarget::at(ctx, root)?;
+
+    for enum_struct in candidate_enum_structs(ctx, root, target.node) {
+        let file_id = DefResolution::from(enum_struct).file_id(db);
+        let enum_struct_tree = ctx.sema.parse(file_id);
+        let source = ctx.sema.preprocessed_text(file_id);
+        let node = enum_struct.source(db, &enum_struct_tree)?.value;
+        let mut members = Vec::new();
+        for field in node
+            .named_children(&mut node.walk())
+            .filter(|child| TSKind::from(child) == TSKind::enum_struct_field)
+        {
+            let (Some(type_node), Some(name)) = (
+                field.child_by_field_name("type"),
+                field.child_by_field_name("name"),
+            ) else {
+                continue;
+            };
+            let type_ = TypeRef::from_node(&type_node, &source).to_new_syntax();
+            let Ok(key) = name.utf8_text(source.as_bytes()) else {
+                continue;
+            };
+            let dimensions = field
+                .children(&mut field.walk())
+                .filter(|child| {
+                    matches!(
+                        TSKind::from(child),
+                        TSKind::dimension | TSKind::fixed_dimension
+                    )
+                })
+                .count();
+            match (type_.type_as_string().as_str(), dimensions) {
+                (_, 0) if !target.items.contains(key) => {
+                    members.push(target.backing.property(&type_, key, key));
+                }
+                (_, 0) => (),
+                ("char", 1) => {
+                    let getter = format!("Get{}", pascal_case(key));
+                    let setter = format!("Set{}", pascal_case(key));
+                    if !target.items.contains(&getter) && !target.items.contains(&setter) {
+                        members.extend(target.backing.string_methods(&getter, &setter, key));
+                    }
+                }
+                // Other arrays cannot be stored in a single key.
+                _ => (),
+            }
+        }
+        if members.is_empty() {
+            continue;
+        }
+        target.add(
+            acc,
+            AssistId("generate_enum_struct_accessors", AssistKind::Generate),
+            format!(
+                "Generate accessors for the fields of `{}`",
+                enum_struct.name(db)
+            ),
+            &members,
+        );
+    }
+
+    Some(())
+}
+
+/// The methodmap the assists apply to.
+struct MethodmapTarget<'a> {
+    ctx: &'a AssistContext<'a>,
+    node: Node<'a>,
+    methodmap: Methodmap,
+    /// Names of the items of the methodmap, inherited ones included.
+    items: FxHashSet<String>,
+    backing: Backing,
+}
+
+impl<'a> MethodmapTarget<'a> {
+    /// Returns the methodmap at the cursor, if it is not inside the body of one of its methods.
+    fn at(ctx: &'a AssistContext<'a>, root: Node<'a>) -> Option<Self> {
+        let pos = usize::from(ctx.s_range().start());
+        let mut node = root.descendant_for_byte_range(pos, pos)?;
+        while TSKind::from(node) != TSKind::methodmap {
+            if TSKind::from(node) == TSKind::block {
+                return None;
+            }
+            node = node.parent()?;
+        }
+        let name = node.child_by_field_name("name")?;
+        let DefResolution::Methodmap(methodmap) = ctx.sema.find_def(ctx.frange.file_id, &name)?
+        else {
+            return None;
+        };
+        let db = ctx.sema.db;
+        let items = db
+            .methodmap_data(methodmap.id())
+            .items_map
+            .keys()
+            .map(ToString::to_string)
+            .collect();
+        let backing = std::iter::successors(Some(methodmap), |it| {
+            db.methodmap_data(it.id()).inherits.map(Methodmap::from)
+        })
+        .find_map(|it| match it.name(db).to_string().as_str() {
+            "StringMap" => Some(Backing::StringMap),
+            "KeyValues" => Some(Backing::KeyValues),
+            _ => None,
+        })
+        .unwrap_or(Backing::None);
+
+        Some(Self {
+            ctx,
+            node,
+            methodmap,
+            items,
+            backing,
+        })
+    }
+
+    /// Adds an assist inserting `members` at the end of the methodmap.
+    fn add(
+        &self,
+        acc: &mut Assists,
+        id: AssistId,
+        label: String,
+        members: &[String],
+    ) -> Option<()> {
+        let file_id = self.ctx.frange.file_id;
+        let file_text = self.ctx.sema.db.file_text(file_id);
+        let s_range = |node: Node| {
+            TextRange::new(
+                TextSize::new(node.start_byte() as u32),
+                TextSize::new(node.end_byte() as u32),
+            )
+        };
+        let r_brace = self
+            .node
+            .children(&mut self.node.walk())
+            .filter(|child| TSKind::from(child) == TSKind::anon_RBRACE)
+            .last()?;
+        let r_brace = self.ctx.u_range(s_range(r_brace))?.start();
+        let target = self
+            .ctx
+            .u_range(s_range(self.node.child_by_field_name("name")?))?;
+
+        let unit = self.indent_unit(&file_text).unwrap_or("\t");
+        let text = members
+            .iter()
+            .flat_map(|member| member.lines())
+            .map(|line| {
+                let depth = line.len() - line.trim_start_matches('\t').len();
+                format!("{}{}\n", unit.repeat(depth + 1), &line[depth..])
+            })
+            .collect::<String>();
+        let line_start = file_text[..r_brace.into()]
+            .rfind('\n')
+            .map_or(0, |it| it + 1);
+        let edit = if file_text[line_start..r_brace.into()].trim().is_empty() {
+            TextEdit::new(TextRange::empty(TextSize::new(line_start as u32)), text)
+        } else {
+            TextEdit::new(TextRange::empty(r_brace), format!("\n{text}"))
+        };
+
+        acc.add(id, label, target, |source_change| {
+            source_change.insert(file_id, edit)
+        })
+    }
+
+    /// Returns the indentation of the first member of the methodmap.
+    fn indent_unit<'t>(&self, file_text: &'t str) -> Option<&'t str> {
+        let l_brace = self
+            .node
+            .children(&mut self.node.walk())
+            .find(|child| TSKind::from(child) == TSKind::anon_LBRACE)?;
+        let member = self
+            .node
+            .named_children(&mut self.node.walk())
+            .find(|child| child.start_byte() > l_brace.end_byte())?;
+        let start = self
+            .ctx
+            .u_range(TextRange::empty(TextSize::new(member.start_byte() as u32)))?
+            .start();
+        let line = file_text[..start.into()].rsplit('\n').next()?;
+        Some(line).filter(|it| !it.is_empty() && it.trim().is_empty())
+    }
+}
+
+/// Handle wrapped by a methodmap, which determines how its values are stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Backing {
+    StringMap,
+    KeyValues,
+    None,
+}
+
+impl Backing {
+    /// Renders a property storing its value under `key`. Nested lines are indented with tabs.
+    fn property(self, type_: &TypeRef, name: &str, key: &str) -> String {
+        let (getter, setter) = match self {
+            Backing::StringMap => (
+                vec![
+                    format!("{type_} value;"),
+                    format!("this.GetValue(\"{key}\", value);"),
+                    "return value;".to_string(),
+                ],
+                vec![format!("this.SetValue(\"{key}\", value);")],
+            ),
+            Backing::KeyValues => match type_.type_as_string().as_str() {
+                "int" => (
+                    vec![format!("return this.GetNum(\"{key}\");")],
+                    vec![format!("this.SetNum(\"{key}\", value);")],
+                ),
+                "float" => (
+                    vec![format!("return this.GetFloat(\"{key}\");")],
+                    vec![format!("this.SetFloat(\"{key}\", value);")],
+                ),
+                "bool" => (
+                    vec![format!("return this.GetNum(\"{key}\") != 0;")],
+                    vec![format!("this.SetNum(\"{key}\", value);")],
+                ),
+                _ => (
+                    vec![format!("return view_as<{type_}>(this.GetNum(\"{key}\"));")],
+                    vec![format!("this.SetNum(\"{key}\", view_as<int>(value));")],
+                ),
+            },
+            Backing::None => (
+                type_
+                    .default_value()
+                    .map(|value| format!("return {value};"))
+                    .into_iter()
+                    .collect(),
+                Vec::new(),
+            ),
+        };
+
+        format!(
+            "property {type_} {name} {{\n\tpublic get() {}\n\tpublic set({type_} value) {}\n}}",
+            block(&getter, 1),
+            block(&setter, 1)
+        )
+    }
+
+    /// Renders the methods reading and writing a string stored under `key`.
+    fn string_methods(self, getter: &str, setter: &str, key: &str) -> [String; 2] {
+        let (get, set) = match self {
+            Backing::StringMap | Backing::KeyValues => (
+                vec![format!("this.GetString(\"{key}\", buffer, maxlen);")],
+                vec![format!("this.SetString(\"{key}\", value);")],
+            ),
+            Backing::None => (Vec::new(), Vec::new()),
+        };
+
+        [
+            format!(
+                "public void {getter}(char[] buffer, int maxlen) {}",
+                block(&get, 0)
+            ),
+            format!(
+                "public void {setter}(const char[] value) {}",
+                block(&set, 0)
+            ),
+        ]
+    }
+}
+
+/// Renders a block whose closing brace is indented with `depth` tabs.
+fn block(lines: &[String], depth: usize) -> String {
+    if lines.is_empty() {
+        return "{}".to_string();
+    }
+    let indent = "\t".repeat(depth);
+    let body = lines
+        .iter()
+        .map(|line| format!("{indent}\t{line}\n"))
+        .collect::<String>();
+
+    format!("{{\n{body}{indent}}}")
+}
+
+/// Returns the enum structs referenced in the methodmap, followed by the ones declared in the
+/// file.
+fn candidate_enum_structs(ctx: &AssistContext, root: Node, methodmap: Node) -> Vec<EnumStruct> {
+    let mut identifiers = identifiers(methodmap);
+    identifiers.extend(
+        root.named_children(&mut root.walk())
+            .filter(|child| TSKind::from(child) == TSKind::enum_struct)
+            .filter_map(|child| child.child_by_field_name("name")),
+    );
+
+    identifiers
+        .iter()
+        .filter_map(
+            |identifier| match ctx.sema.find_def(ctx.frange.file_id, identifier)? {
+                DefResolution::EnumStruct(it) => Some(it),
+                _ => None,
+            },
+        )
+        .unique()
+        .collect()
+}
+
+/// Converts `player_name` to `PlayerName`.
+fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
@@ -0,0 +1,173 @@
+//! Assist replacing the uses of a local variable by its initializer.
+//!
+//! ```sourcepawn
+//! int damage = GetDamage(client) * 2;
+//! SetEntityHealth(client, health - damage);
+//! ```
+//! becomes
+//! ```sourcepawn
+//! SetEntityHealth(client, health - (GetDamage(client) * 2));
+//! ```
+
+use base_db::SourceDatabaseExt;
+use hir::{DefResolution, HasSource};
+use ide_db::{AssistId, AssistKind, TextEdit};
+use line_index::{TextRange, TextSize};
+use syntax::TSKind;
+use tree_sitter::Node;
+
+use super::{identifiers, is_modified, needs_parentheses, top_level_item, AssistContext, Assists};
+
+pub(super) fn inline_local_variable(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let file_id = ctx.frange.file_id;
+    let tree = ctx.sema.parse(file_id);
+    let source = ctx.sema.preprocessed_text(file_id);
+    let file_text = ctx.sema.db.file_text(file_id);
+    let pos = usize::from(ctx.s_range().start());
+    let identifier = tree.root_node().descendant_for_byte_range(pos, pos)?;
+    if TSKind::from(identifier) != TSKind::identifier {
+        return None;
+    }
+    let local_binding = |node: &Node| match ctx.sema.find_def(file_id, node)? {
+        def @ DefResolution::Local(_) => def
+            .source(ctx.sema.db, &tree)
+            .filter(|it| it.file_id == file_id)
+            .map(|it| it.value),
+        _ => None,
+    };
+    let binding = local_binding(&identifier)?;
+    if !matches!(
+        TSKind::from(binding),
+        TSKind::variable_declaration | TSKind::old_variable_declaration
+    ) || has_dimensions(binding)
+    {
+        return None;
+    }
+    let name = binding.child_by_field_name("name")?;
+    let initializer = binding.child_by_field_name("initialValue")?;
+    let statement = binding.parent()?;
+    if TSKind::from(statement.parent()?) == TSKind::for_statement || is_static(statement, &source) {
+        return None;
+    }
+
+    let name_text = name.utf8_text(source.as_bytes()).ok()?;
+    let references = identifiers(top_level_item(statement)?)
+        .into_iter()
+        .filter(|reference| reference.id() != name.id())
+        .filter(|reference| reference.utf8_text(source.as_bytes()).ok() == Some(name_text))
+        .filter(|reference| local_binding(reference).is_some_and(|it| it == binding))
+        .collect::<Vec<_>>();
+    if references
+        .iter()
+        .any(|reference| is_modified(&ctx.sema, file_id, reference))
+    {
+        return None;
+    }
+
+    let s_range = |node: &Node| {
+        TextRange::new(
+            TextSize::new(node.start_byte() as u32),
+            TextSize::new(node.end_byte() as u32),
+        )
+    };
+    let initializer_text =
+        &file_text[std::ops::Range::<usize>::from(ctx.u_range(s_range(&initializer))?)];
+    let mut edits = Vec::new();
+    for reference in references.iter() {
+        let text = match reference.parent() {
+            Some(parent) if needs_parentheses(&initializer, &parent) => {
+                format!("({initializer_text})")
+            }
+            _ => initializer_text.to_string(),
+        };
+        edits.push(TextEdit::new(ctx.u_range(s_range(reference))?, text));
+    }
+    edits.push(TextEdit::new(
+        declaration_removal_range(ctx, &file_text, statement, binding)?,
+        String::new(),
+    ));
+
+    acc.add(
+        AssistId("inline_local_variable", AssistKind::RefactorInline),
+        "Inline variable",
+        ctx.u_range(s_range(&binding))?,
+        |source_change| {
+            for edit in edits {
+                source_change.insert(file_id, edit);
+            }
+        },
+    )
+}
+
+/// Returns the range to delete to remove the declarator `binding` of `statement`.
+///
+/// The whole statement, with its line, is removed if it only declares `binding`.
+fn declaration_removal_range(
+    ctx: &AssistContext,
+    file_text: &str,
+    statement: Node,
+    binding: Node,
+) -> Option<TextRange> {
+    let declarators = statement
+        .named_children(&mut statement.walk())
+        .filter(|child| TSKind::from(child) == TSKind::from(binding))
+        .count();
+    let s_range = |start: usize, end: usize| {
+        TextRange::new(TextSize::new(start as u32), TextSize::new(end as u32))
+    };
+    if declarators > 1 {
+        // Also remove the comma separating the declarator from its neighbour.
+        let range = match binding.prev_sibling() {
+            Some(prev) if TSKind::from(prev) == TSKind::anon_COMMA => {
+                s_range(prev.start_byte(), binding.end_byte())
+            }
+            _ => s_range(
+                binding.start_byte(),
+                binding.next_named_sibling()?.start_byte(),
+            ),
+        };
+        return ctx.u_range(range);
+    }
+
+    let range = ctx.u_range(s_range(statement.start_byte(), statement.end_byte()))?;
+    let line_start = file_text[..range.start().into()]
+        .rfind('\n')
+        .map_or(0, |it| it + 1);
+    let line_end = file_text[range.end().into()..]
+        .find('\n')
+        .map_or(file_text.len(), |it| usize::from(range.end()) + it + 1);
+    let is_alone = file_text[line_start..range.start().into()]
+        .trim()
+        .is_empty()
+        && file_text[range.end().into()..line_end].trim().is_empty();
+    if is_alone {
+        Some(TextRange::new(
+            TextSize::new(line_start as u32),
+            TextSize::new(line_end as u32),
+        ))
+    } else {
+        Some(range)
+    }
+}
+
+/// Whether the value of a `static` local is kept between calls, and hence cannot be inlined.
+fn is_static(statement: Node, source: &str) -> bool {
+    statement
+        .children(&mut statement.walk())
+        .filter(|child| TSKind::from(child) == TSKind::variable_storage_class)
+        .filter_map(|child| child.utf8_text(source.as_bytes()).ok())
+        .any(|it| it.contains("static"))
+}
+
+fn has_dimensions(node: Node) -> bool {
+    node.children(&mut node.walk()).any(|child| {
+        matches!(
+            TSKind::from(child),
+            TSKind::dimension | TSKind::fixed_dimension
+        )
+    }) || node.parent().is_some_and(|parent| {
+        parent
+            .children(&mut parent.walk())
+            .any(|child| TSKind::from(child) == TSKind::dimension)
+    })
+}
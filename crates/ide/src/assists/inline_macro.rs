@@ -0,0 +1,77 @@
+//! Assist replacing a macro call by its expansion.
+//!
+//! ```sourcepawn
+//! #define MAX_HEALTH(%1) %1 * 100
+//! SetEntityHealth(client, MAX_HEALTH(2));
+//! ```
+//! becomes
+//! ```sourcepawn
+//! #define MAX_HEALTH(%1) %1 * 100
+//! SetEntityHealth(client, 2 * 100);
+//! ```
+
+use base_db::{FilePosition, SourceDatabaseExt};
+use ide_db::{AssistId, AssistKind, TextEdit};
+use preprocessor::expand_macro_call;
+use syntax::TSKind;
+use tree_sitter::Node;
+
+use super::{needs_parentheses, AssistContext, Assists};
+
+pub(super) fn inline_macro(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let file_id = ctx.frange.file_id;
+    let (offset, _) = ctx.sema.find_macro_def(&FilePosition {
+        file_id,
+        offset: ctx.frange.range.start(),
+    })?;
+    let target = *offset.range();
+    let file_text = ctx.sema.db.file_text(file_id);
+    let preprocessing_results = ctx.sema.preprocess_file(file_id);
+    let expansion = expand_macro_call(
+        file_text.get(std::ops::Range::<usize>::from(target))?,
+        preprocessing_results.macros(),
+    )?;
+
+    // The expansion is located in the preprocessed text to find the expression it forms, if
+    // any, and whether it needs parentheses where it is inserted.
+    let preprocessed_text = ctx.sema.preprocessed_text(file_id);
+    let expanded_range = std::ops::Range::<usize>::from(*offset.expanded_range());
+    let expanded_text = preprocessed_text.get(expanded_range.clone())?;
+    let start = expanded_range.start + (expanded_text.len() - expanded_text.trim_start().len());
+    let end = start + expanded_text.trim().len();
+
+    let tree = ctx.sema.parse(file_id);
+    let text = match expression_of_range(tree.root_node(), start, end) {
+        Some(expr)
+            if expr
+                .parent()
+                .is_some_and(|it| needs_parentheses(&expr, &it)) =>
+        {
+            format!("({expansion})")
+        }
+        _ => expansion,
+    };
+
+    acc.add(
+        AssistId("inline_macro", AssistKind::RefactorInline),
+        "Inline macro",
+        target,
+        |source_change| source_change.insert(file_id, TextEdit::new(target, text)),
+    )
+}
+
+/// Returns the outermost node which spans exactly from `start` to `end`, if any.
+fn expression_of_range(root: Node, start: usize, end: usize) -> Option<Node> {
+    let mut node = root.descendant_for_byte_range(start, end)?;
+    if node.start_byte() != start || node.end_byte() != end {
+        return None;
+    }
+    while let Some(parent) = node
+        .parent()
+        .filter(|it| it.start_byte() == start && it.end_byte() == end)
+    {
+        node = parent;
+    }
+
+    (TSKind::from(node) != TSKind::source_file).then_some(node)
+}
@@ -0,0 +1,200 @@
+//! Assist sorting the `#include` directives of a file and removing the redundant ones.
+//!
+//! ```sourcepawn
+//! #include "utils"
+//! #include <sdktools>
+//! #include <sourcemod>
+//! #include <sdktools.inc>
+//! #include <cstrike>
+//! ```
+//! becomes, if nothing of `cstrike` is used
+//! ```sourcepawn
+//! #include <sourcemod>
+//! #include <sdktools>
+//! #include "utils"
+//! ```
+//!
+//! Only the directives on consecutive lines are moved together, so that `#pragma` lines and
+//! blank lines are kept where they are, as the directives after them may depend on them. Within
+//! such a block, `sourcemod` comes first, then the `<...>` includes and then the `"..."` ones,
+//! and the relative order of the includes of a same kind is kept.
+
+use base_db::SourceDatabaseExt;
+use fxhash::FxHashSet;
+use hir::Semantics;
+use ide_db::{
+    include_directives, unused_includes, AssistId, AssistKind, IncludeDirective, RootDatabase,
+    TextEdit,
+};
+use line_index::{TextRange, TextSize};
+use vfs::FileId;
+
+use super::{AssistContext, Assists};
+
+pub(super) fn organize_includes(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let file_id = ctx.frange.file_id;
+    let lines = include_lines(&ctx.sema, file_id);
+    let first = lines.first()?;
+    let last = lines.last()?;
+    let target = TextRange::new(first.range.start(), last.range.end());
+    if !lines
+        .iter()
+        .any(|line| line.range.intersect(ctx.frange.range).is_some())
+    {
+        return None;
+    }
+    let edits = organize_include_lines(&ctx.sema, file_id, lines);
+    if edits.is_empty() {
+        return None;
+    }
+
+    acc.add(
+        AssistId("organize_includes", AssistKind::RefactorRewrite),
+        "Organize includes",
+        target,
+        |source_change| {
+            for edit in edits {
+                source_change.insert(file_id, edit);
+            }
+        },
+    )
+}
+
+/// Returns the edits sorting the include directives of the file and removing the duplicated and
+/// unused ones.
+pub(crate) fn organize_includes_edits(db: &RootDatabase, file_id: FileId) -> Vec<TextEdit> {
+    let sema = Semantics::new(db);
+    let lines = include_lines(&sema, file_id);
+    organize_include_lines(&sema, file_id, lines)
+}
+
+/// A line of the file which only holds an include directive, and maybe a comment.
+struct IncludeLine {
+    directive: IncludeDirective,
+    /// Range of the line in the file, without its line break.
+    range: TextRange,
+}
+
+impl IncludeLine {
+    /// Rank of the directive in a block of includes.
+    fn rank(&self, db: &RootDatabase) -> u8 {
+        if self.directive.is_sourcemod(db) {
+            0
+        } else if self.directive.kind == base_db::IncludeKind::Chevrons {
+            1
+        } else {
+            2
+        }
+    }
+}
+
+fn include_lines(sema: &Semantics<RootDatabase>, file_id: FileId) -> Vec<IncludeLine> {
+    let file_text = sema.db.file_text(file_id);
+    let preprocessing_results = sema.preprocess_file(file_id);
+    let source_map = preprocessing_results.source_map();
+    include_directives(sema, file_id)
+        .into_iter()
+        .filter_map(|directive| {
+            let u_range = source_map.closest_u_range(directive.s_range)?;
+            let start = usize::from(u_range.start());
+            let line_start = file_text[..start].rfind('\n').map_or(0, |it| it + 1);
+            let line_end = file_text[start..]
+                .find('\n')
+                .map_or(file_text.len(), |it| start + it);
+            let line_end = if file_text[..line_end].ends_with('\r') {
+                line_end - 1
+            } else {
+                line_end
+            };
+            if !file_text[line_start..start].trim().is_empty() {
+                return None;
+            }
+            Some(IncludeLine {
+                directive,
+                range: TextRange::new(
+                    TextSize::new(line_start as u32),
+                    TextSize::new(line_end as u32),
+                ),
+            })
+        })
+        .collect()
+}
+
+fn organize_include_lines(
+    sema: &Semantics<RootDatabase>,
+    file_id: FileId,
+    lines: Vec<IncludeLine>,
+) -> Vec<TextEdit> {
+    if lines.is_empty() {
+        return Vec::new();
+    }
+    let file_text = sema.db.file_text(file_id);
+    let line_break = if file_text.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let unused = unused_includes(sema, file_id)
+        .into_iter()
+        .map(|it| it.s_range)
+        .collect::<FxHashSet<_>>();
+
+    // Split the lines in blocks of consecutive lines.
+    let mut blocks: Vec<Vec<IncludeLine>> = Vec::new();
+    for line in lines {
+        match blocks.last_mut() {
+            Some(block)
+                if block.last().is_some_and(|prev| {
+                    let between =
+                        &file_text[usize::from(prev.range.end())..usize::from(line.range.start())];
+                    between == "\n" || between == "\r\n"
+                }) =>
+            {
+                block.push(line)
+            }
+            _ => blocks.push(vec![line]),
+        }
+    }
+
+    let mut seen = FxHashSet::default();
+    let mut edits = Vec::new();
+    for block in blocks {
+        let range = TextRange::new(
+            block.first().unwrap().range.start(),
+            block.last().unwrap().range.end(),
+        );
+        let mut kept = block
+            .into_iter()
+            .filter(|line| !unused.contains(&line.directive.s_range))
+            .filter(|line| {
+                // Unresolved includes are deduplicated on their spelling.
+                let key = match line.directive.file_id {
+                    Some(id) => Ok(id),
+                    None => Err((line.directive.kind, line.directive.path.clone())),
+                };
+                seen.insert(key)
+            })
+            .collect::<Vec<_>>();
+        kept.sort_by_key(|line| line.rank(sema.db));
+
+        let text = kept
+            .iter()
+            .map(|line| &file_text[std::ops::Range::<usize>::from(line.range)])
+            .collect::<Vec<_>>()
+            .join(line_break);
+        if kept.is_empty() {
+            // Also remove the line break of the last line of the block.
+            let end = file_text[usize::from(range.end())..]
+                .find('\n')
+                .map_or(file_text.len(), |it| usize::from(range.end()) + it + 1);
+            edits.push(TextEdit::new(
+                TextRange::new(range.start(), TextSize::new(end as u32)),
+                String::new(),
+            ));
+        } else if text != file_text[std::ops::Range::<usize>::from(range)] {
+            edits.push(TextEdit::new(range, text));
+        }
+    }
+
+    edits
+}
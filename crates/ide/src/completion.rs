@@ -1,3 +1,4 @@
+mod auto_include;
 mod defaults;
 mod documentation;
 mod includes;
@@ -6,6 +7,7 @@ mod item;
 use std::panic::AssertUnwindSafe;
 
 use base_db::FilePosition;
+use fxhash::FxHashSet;
 use hir::{DefResolution, Field, Function, HasSource, LocalDef, Property, Semantics};
 use hir_def::{DefDatabase, FieldId, FunctionKind};
 use ide_db::{Documentation, RootDatabase, SymbolKind};
@@ -17,11 +19,12 @@ use paths::AbsPathBuf;
 use preprocessor::db::PreprocDatabase;
 use regex::Regex;
 use smol_str::{SmolStr, ToSmolStr};
-use syntax::TSKind;
+use syntax::{utils::input_edit, TSKind};
 use vfs::FileId;
 
 use crate::{
     completion::{
+        auto_include::auto_include_completions,
         defaults::get_default_completions,
         documentation::{get_doc_completion, is_documentation_start},
         includes::{get_include_completions, is_include_statement},
@@ -97,13 +100,20 @@ pub fn completions(
         &preprocessed_text[safe_raw_s_pos..],
     ]
     .concat();
-    // TODO: Use the edit to update the tree
-    // tree.edit(&edit);
+    let edit = input_edit(
+        &preprocessed_text,
+        &new_source_code,
+        safe_raw_s_pos,
+        safe_raw_s_pos,
+        safe_raw_s_pos + token.len(),
+    );
+    let mut old_tree = tree.clone();
+    old_tree.edit(&edit);
     let mut parser = tree_sitter::Parser::new();
     parser
         .set_language(&tree_sitter_sourcepawn::language())
         .unwrap();
-    let new_tree = parser.parse(new_source_code.as_bytes(), None)?;
+    let new_tree = parser.parse(new_source_code.as_bytes(), Some(old_tree.tree()))?;
 
     let root_node = new_tree.root_node();
     // get the node before the cursor
@@ -161,6 +171,7 @@ pub fn completions(
         }
     }
     let mut add_defaults = false;
+    let mut add_auto_includes = false;
     let mut local_context = true;
 
     log::debug!("completion container kind: {:?}", container.kind());
@@ -177,6 +188,7 @@ pub fn completions(
             if !is_triggered_by_scope_or_field_access(trigger_character) =>
         {
             add_defaults = true;
+            add_auto_includes = true;
             if let Some(res) = in_function_completion(container, tree, sema, pos) {
                 res
             } else {
@@ -210,6 +222,7 @@ pub fn completions(
         TSKind::comment | TSKind::string_literal => return None,
         _ if !is_triggered_by_scope_or_field_access(trigger_character) => {
             local_context = false;
+            add_auto_includes = true;
             sema.defs_in_scope(pos.file_id)
                 .into_iter()
                 .filter(|it| !matches!(it, DefResolution::Local(_)))
@@ -218,6 +231,9 @@ pub fn completions(
         _ => Default::default(),
     };
 
+    let mut reachable_files: FxHashSet<FileId> = defs.iter().map(|def| def.file_id(db)).collect();
+    reachable_files.insert(pos.file_id);
+
     let mut res = Vec::new();
 
     defs.into_iter().for_each(|def| match &def {
@@ -456,6 +472,22 @@ pub fn completions(
         DefResolution::File(_) => (),
     });
 
+    if add_auto_includes {
+        let word = split_line
+            .0
+            .rsplit(|c: char| !c.is_alphanumeric() && c != '_')
+            .next()
+            .unwrap_or_default();
+        let existing = res.iter().map(|item| item.label.to_string()).collect();
+        res.extend(auto_include_completions(
+            sema,
+            pos.file_id,
+            word,
+            &reachable_files,
+            &existing,
+        ));
+    }
+
     if add_defaults {
         res.extend(get_default_completions(local_context));
     }
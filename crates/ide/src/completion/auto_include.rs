@@ -0,0 +1,117 @@
+//! Completions for the symbols of the include directories which are not included yet.
+//!
+//! Accepting such a completion also adds the `#include` of the file the symbol is declared in.
+
+use base_db::SourceDatabaseExt;
+use fxhash::FxHashSet;
+use hir::{DefResolution, Semantics};
+use ide_db::{include_dirs_symbols, FileSymbol, Query, RootDatabase};
+use line_index::{TextRange, TextSize};
+use preprocessor::db::PreprocDatabase;
+use vfs::FileId;
+
+use super::CompletionItem;
+
+/// Returns the completions of the symbols matching `word` which are declared in an include
+/// directory, in a file which cannot be reached from the file of the completion.
+///
+/// # Arguments
+/// * `reachable_files` - Files in which the symbols are already in scope.
+/// * `existing` - Labels of the completions which are already offered.
+pub(super) fn auto_include_completions(
+    sema: &Semantics<RootDatabase>,
+    file_id: FileId,
+    word: &str,
+    reachable_files: &FxHashSet<FileId>,
+    existing: &FxHashSet<String>,
+) -> Vec<CompletionItem> {
+    if word.is_empty() {
+        return Vec::new();
+    }
+    let db = sema.db;
+    let mut query = Query::new(word);
+    query.top_level();
+
+    let insert_offset = include_insert_offset(&db.file_text(file_id));
+    let mut seen = FxHashSet::default();
+    include_dirs_symbols(db, &query)
+        .into_iter()
+        .filter(|symbol| !reachable_files.contains(&symbol.file_id))
+        .filter(|symbol| !existing.contains(symbol.name.as_str()))
+        .filter_map(|symbol| {
+            let path = include_path(db, symbol.file_id)?;
+            if !seen.insert((symbol.name.clone(), path.clone())) {
+                return None;
+            }
+            let edit = match insert_offset {
+                Some(offset) => (TextRange::empty(offset), format!("\n#include <{path}>")),
+                None => (
+                    TextRange::empty(TextSize::new(0)),
+                    format!("#include <{path}>\n"),
+                ),
+            };
+            Some(CompletionItem {
+                label: symbol.name.clone(),
+                kind: symbol.kind.into(),
+                label_description: Some(format!("#include <{path}>")),
+                deprecated: symbol.deprecated,
+                additional_text_edits: vec![edit],
+                data: symbol_def(sema, &symbol),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+/// Returns the shortest path, relative to an include directory, with which `file_id` can be
+/// included.
+fn include_path(db: &RootDatabase, file_id: FileId) -> Option<String> {
+    let source_root = db.source_root(db.file_source_root(file_id));
+    let path = source_root.path_for_file(&file_id)?;
+    let (_, ext) = path.name_and_extension()?;
+    if ext != Some("inc") {
+        return None;
+    }
+
+    db.source_roots()
+        .iter()
+        .filter(|source_root| source_root.is_include_dir)
+        .filter_map(|source_root| {
+            let relative_path = path
+                .strip_prefix(source_root.root())?
+                .components()
+                .map(|it| it.as_os_str().to_str())
+                .collect::<Option<Vec<_>>>()?
+                .join("/");
+            relative_path.strip_suffix(".inc").map(ToString::to_string)
+        })
+        .min_by_key(|it| it.len())
+}
+
+/// Returns the offset of the end of the last `#include` of the file, if any.
+fn include_insert_offset(text: &str) -> Option<TextSize> {
+    let mut offset = 0;
+    let mut res = None;
+    for line in text.split_inclusive('\n') {
+        let directive = line.trim_start();
+        if directive.starts_with("#include") || directive.starts_with("#tryinclude") {
+            res = Some(offset + line.trim_end().len());
+        }
+        offset += line.len();
+    }
+
+    res.map(|it| TextSize::new(it as u32))
+}
+
+fn symbol_def(sema: &Semantics<RootDatabase>, symbol: &FileSymbol) -> Option<DefResolution> {
+    let tree = sema.parse(symbol.file_id);
+    let range = symbol.focus_range.unwrap_or(symbol.full_range);
+    let offset: usize = sema
+        .db
+        .preprocess_file(symbol.file_id)
+        .source_map()
+        .closest_s_position_always(range.start())
+        .into();
+    let node = tree.root_node().descendant_for_byte_range(offset, offset)?;
+    sema.find_def(symbol.file_id, &node)
+}
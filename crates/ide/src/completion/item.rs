@@ -23,6 +23,9 @@ pub struct CompletionItem {
     /// Additional info to show in the UI pop up.
     pub detail: Option<String>,
 
+    /// Short description shown next to the label, e.g. the include added by the completion.
+    pub label_description: Option<String>,
+
     /// Documentation to show in the UI pop up.
     pub documentation: Option<Documentation>,
 
@@ -35,6 +38,10 @@ pub struct CompletionItem {
 
     pub text_edit: Option<(TextRange, String)>,
 
+    /// Edits applied along with the completion, which do not overlap with the cursor, e.g. to
+    /// add an `#include`.
+    pub additional_text_edits: Vec<(TextRange, String)>,
+
     pub data: Option<DefResolution>,
 }
 
@@ -0,0 +1,61 @@
+use base_db::{FileExtension, FileRange, SourceDatabase};
+use hir::Semantics;
+use ide_db::{project_dead_code, RootDatabase, SymbolKind};
+use preprocessor::db::PreprocDatabase;
+use vfs::FileId;
+
+/// The declarations of a project which are never referenced in it.
+#[derive(Debug, Clone)]
+pub struct ProjectDeadCode {
+    /// Main file of the project.
+    pub root: FileId,
+    pub items: Vec<DeadCodeItem>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DeadCodeItem {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// Range of the name of the declaration.
+    pub frange: FileRange,
+}
+
+pub(crate) fn dead_code(db: &RootDatabase, file_id: Option<FileId>) -> Vec<ProjectDeadCode> {
+    let sema = &Semantics::new(db);
+    let mut subgraphs = db
+        .graph()
+        .find_subgraphs()
+        .into_iter()
+        .filter(|it| it.root.extension == FileExtension::Sp)
+        .filter(|it| file_id.is_none_or(|file_id| it.contains_file(file_id)))
+        .collect::<Vec<_>>();
+    subgraphs.sort_by_key(|it| it.root.file_id);
+
+    subgraphs
+        .iter()
+        .map(|subgraph| {
+            let mut items = project_dead_code(sema, subgraph)
+                .into_iter()
+                .map(|it| {
+                    let range = db
+                        .preprocess_file(it.file_id)
+                        .source_map()
+                        .closest_u_range_always(it.s_range);
+                    DeadCodeItem {
+                        name: it.name,
+                        kind: it.kind,
+                        frange: FileRange {
+                            file_id: it.file_id,
+                            range,
+                        },
+                    }
+                })
+                .collect::<Vec<_>>();
+            items.sort_by_key(|it| (it.frange.file_id, it.frange.range.start()));
+            ProjectDeadCode {
+                root: subgraph.root.file_id,
+                items,
+            }
+        })
+        .collect()
+}
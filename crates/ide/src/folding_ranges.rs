@@ -0,0 +1,216 @@
+//! Folding ranges of SourcePawn files.
+//!
+//! The folds of the code are computed on the tree-sitter tree of the file as it is written on
+//! disk, so that the code of the inactive preprocessor branches can be folded as well. The
+//! `#if`/`#elseif`/`#else` regions come from the preprocessor.
+
+use base_db::SourceDatabaseExt;
+use ide_db::RootDatabase;
+use line_index::{TextRange, TextSize};
+use preprocessor::db::PreprocDatabase;
+use syntax::{utils::ts_range_to_text_range, TSKind};
+use vfs::FileId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FoldKind {
+    /// Body of a function, a methodmap, an enum, a switch, etc.
+    Block,
+    /// Run of consecutive line comments, or a block comment.
+    Comment,
+    /// Branch of a preprocessor condition.
+    Condition,
+    /// User region, delimited by `// #region` and `// #endregion` or `#pragma region` and
+    /// `#pragma endregion`.
+    Region,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fold {
+    pub range: TextRange,
+    pub kind: FoldKind,
+}
+
+pub(crate) fn folding_ranges(db: &RootDatabase, file_id: FileId) -> Vec<Fold> {
+    let text = db.file_text(file_id);
+    let mut res = Vec::new();
+
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(&tree_sitter_sourcepawn::language())
+        .expect("Failed to set language");
+    if let Some(tree) = parser.parse(text.as_bytes(), None) {
+        let mut comments = Vec::new();
+        let mut stack = vec![tree.root_node()];
+        while let Some(node) = stack.pop() {
+            match TSKind::from(&node) {
+                TSKind::comment | TSKind::preproc_pragma => comments.push(node),
+                _ => {
+                    if let Some(range) = block_range(&node) {
+                        res.push(Fold {
+                            range,
+                            kind: FoldKind::Block,
+                        });
+                    }
+                }
+            }
+            stack.extend(node.children(&mut node.walk()));
+        }
+        comments.sort_by_key(|node| node.start_byte());
+        comment_and_region_folds(&text, &comments, &mut res);
+    }
+
+    let preprocessing_results = db.preprocess_file(file_id);
+    let text_len = TextSize::of(text.as_ref());
+    res.extend(
+        preprocessing_results
+            .condition_ranges()
+            .iter()
+            .filter(|range| range.end() <= text_len)
+            .map(|range| Fold {
+                range: *range,
+                kind: FoldKind::Condition,
+            }),
+    );
+
+    res.sort_by_key(|fold| (fold.range.start(), std::cmp::Reverse(fold.range.end())));
+    res
+}
+
+/// Range of the body of a node, from its opening brace to its closing brace, excluded.
+fn block_range(node: &tree_sitter::Node) -> Option<TextRange> {
+    match TSKind::from(node) {
+        TSKind::block
+        | TSKind::methodmap
+        | TSKind::methodmap_property
+        | TSKind::enum_struct
+        | TSKind::enum_entries
+        | TSKind::r#struct
+        | TSKind::typeset
+        | TSKind::funcenum
+        | TSKind::switch_statement => {
+            let mut cursor = node.walk();
+            let mut children = node.children(&mut cursor);
+            let l_brace = children.find(|child| TSKind::from(child) == TSKind::anon_LBRACE)?;
+            let r_brace = children
+                .filter(|child| TSKind::from(child) == TSKind::anon_RBRACE)
+                .last()?;
+            Some(TextRange::new(
+                ts_range_to_text_range(&l_brace.range()).start(),
+                ts_range_to_text_range(&r_brace.range()).start(),
+            ))
+        }
+        TSKind::switch_case => {
+            // Case bodies with braces are already folded as a block.
+            let body = node.child_by_field_name("body")?;
+            if TSKind::from(&body) == TSKind::block {
+                return None;
+            }
+            Some(ts_range_to_text_range(&node.range()))
+        }
+        _ => None,
+    }
+}
+
+enum RegionMarker {
+    Start,
+    End,
+}
+
+fn region_marker(node: &tree_sitter::Node, text: &str) -> Option<RegionMarker> {
+    let node_text = node.utf8_text(text.as_bytes()).ok()?;
+    let marker = match TSKind::from(node) {
+        TSKind::comment => node_text
+            .strip_prefix("//")?
+            .trim_start()
+            .strip_prefix('#')?,
+        TSKind::preproc_pragma => node_text.strip_prefix("#pragma")?.trim_start(),
+        _ => return None,
+    };
+    if marker.starts_with("endregion") {
+        Some(RegionMarker::End)
+    } else if marker.starts_with("region") {
+        Some(RegionMarker::Start)
+    } else {
+        None
+    }
+}
+
+fn comment_and_region_folds(text: &str, comments: &[tree_sitter::Node], acc: &mut Vec<Fold>) {
+    let mut region_starts = Vec::new();
+    let mut run: Option<(tree_sitter::Node, tree_sitter::Node)> = None;
+    for node in comments {
+        match region_marker(node, text) {
+            Some(RegionMarker::Start) => {
+                push_comment_run(run.take(), acc);
+                region_starts.push(*node);
+                continue;
+            }
+            Some(RegionMarker::End) => {
+                push_comment_run(run.take(), acc);
+                if let Some(start) = region_starts.pop() {
+                    acc.push(Fold {
+                        range: TextRange::new(
+                            ts_range_to_text_range(&start.range()).start(),
+                            ts_range_to_text_range(&node.range()).end(),
+                        ),
+                        kind: FoldKind::Region,
+                    });
+                }
+                continue;
+            }
+            None => (),
+        }
+        if TSKind::from(node) != TSKind::comment {
+            continue;
+        }
+        if !is_line_comment(node, text) {
+            push_comment_run(run.take(), acc);
+            if node.start_position().row != node.end_position().row {
+                acc.push(Fold {
+                    range: ts_range_to_text_range(&node.range()),
+                    kind: FoldKind::Comment,
+                });
+            }
+            continue;
+        }
+        run = match run {
+            Some((first, last)) if last.end_position().row + 1 == node.start_position().row => {
+                Some((first, *node))
+            }
+            _ => {
+                push_comment_run(run, acc);
+                Some((*node, *node))
+            }
+        };
+    }
+    push_comment_run(run, acc);
+}
+
+/// Fold a run of line comments, given its first and last comments.
+fn push_comment_run(run: Option<(tree_sitter::Node, tree_sitter::Node)>, acc: &mut Vec<Fold>) {
+    let Some((first, last)) = run else {
+        return;
+    };
+    if first.id() != last.id() {
+        acc.push(Fold {
+            range: TextRange::new(
+                ts_range_to_text_range(&first.range()).start(),
+                ts_range_to_text_range(&last.range()).end(),
+            ),
+            kind: FoldKind::Comment,
+        });
+    }
+}
+
+/// Whether the node is a `//` comment alone on its line.
+fn is_line_comment(node: &tree_sitter::Node, text: &str) -> bool {
+    if !node
+        .utf8_text(text.as_bytes())
+        .is_ok_and(|it| it.starts_with("//"))
+    {
+        return false;
+    }
+    let line_start = node.start_byte() - node.start_position().column;
+    text.get(line_start..node.start_byte())
+        .is_some_and(|it| it.trim().is_empty())
+}
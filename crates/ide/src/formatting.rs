@@ -0,0 +1,625 @@
+//! Formatting of SourcePawn files.
+//!
+//! The formatter walks the tree-sitter tree of the file as it is written on disk (i.e. before
+//! preprocessing) and only rewrites the whitespace between tokens. Comments, strings and
+//! preprocessor directives are kept verbatim, and the whitespace around parts of the file the
+//! parser did not understand is left untouched.
+
+use base_db::SourceDatabaseExt;
+use ide_db::{RootDatabase, TextEdit};
+use itertools::Itertools;
+use line_index::{TextRange, TextSize};
+use syntax::TSKind;
+use vfs::FileId;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormattingConfig {
+    pub indent: IndentStyle,
+    pub brace_style: BraceStyle,
+    /// Maximum number of consecutive blank lines to keep.
+    pub max_blank_lines: usize,
+    /// Whether to end the file with a newline.
+    pub insert_final_newline: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentStyle {
+    Tabs,
+    Spaces(u32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BraceStyle {
+    /// Opening braces go on their own line.
+    NextLine,
+    /// Opening braces stay on the line of the declaration or statement they belong to.
+    SameLine,
+}
+
+/// Returns the edits needed to format the file.
+///
+/// If a `range` is provided, only the edits that touch it are returned.
+pub(crate) fn format(
+    db: &RootDatabase,
+    file_id: FileId,
+    range: Option<TextRange>,
+    config: &FormattingConfig,
+) -> Option<Vec<TextEdit>> {
+    let text = db.file_text(file_id);
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(&tree_sitter_sourcepawn::language())
+        .expect("Failed to set language");
+    let tree = parser.parse(text.as_bytes(), None)?;
+
+    let mut formatter = Formatter::new(&text, config);
+    for child in tree.root_node().children(&mut tree.root_node().walk()) {
+        formatter.statement(child, 0);
+    }
+    let mut edits = formatter.edits();
+    if let Some(range) = range {
+        edits.retain(|edit| {
+            edit.range().start() < range.end() && edit.range().end() >= range.start()
+        });
+    }
+
+    Some(edits)
+}
+
+#[derive(Debug)]
+struct Token<'tree> {
+    node: tree_sitter::Node<'tree>,
+    /// Indentation level of the token, if it starts a line.
+    indent: usize,
+}
+
+impl Token<'_> {
+    fn kind(&self) -> TSKind {
+        TSKind::from(self.node)
+    }
+
+    fn parent_kind(&self) -> Option<TSKind> {
+        self.node.parent().map(TSKind::from)
+    }
+
+    fn range(&self) -> TextRange {
+        TextRange::new(
+            TextSize::new(self.node.start_byte() as u32),
+            TextSize::new(self.node.end_byte() as u32),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Spacing {
+    None,
+    Space,
+    Newlines(usize),
+}
+
+struct Formatter<'a, 'tree> {
+    text: &'a str,
+    config: &'a FormattingConfig,
+    tokens: Vec<Token<'tree>>,
+    /// Whether the next token is the first token of a statement.
+    ///
+    /// Tokens which are not at the start of a statement get an extra level of indentation
+    /// when they start a line.
+    statement_start: bool,
+}
+
+impl<'a, 'tree> Formatter<'a, 'tree> {
+    fn new(text: &'a str, config: &'a FormattingConfig) -> Self {
+        Self {
+            text,
+            config,
+            tokens: Vec::new(),
+            statement_start: false,
+        }
+    }
+
+    fn statement(&mut self, node: tree_sitter::Node<'tree>, level: usize) {
+        self.statement_start = true;
+        self.node(node, level);
+    }
+
+    fn node(&mut self, node: tree_sitter::Node<'tree>, level: usize) {
+        if node.is_missing() || node.start_byte() == node.end_byte() {
+            return;
+        }
+        if is_directive(&node) {
+            // Preprocessor directives always start at the first column.
+            self.push_at(node, 0);
+            return;
+        }
+        if is_verbatim(&node) || node.child_count() == 0 {
+            let indent = if self.statement_start {
+                level
+            } else {
+                level + 1
+            };
+            self.push_at(node, indent);
+            return;
+        }
+        match TSKind::from(node) {
+            kind if is_container(kind) => self.container(node, level),
+            TSKind::condition_statement
+            | TSKind::for_statement
+            | TSKind::while_statement
+            | TSKind::do_while_statement => self.control_flow(node, level),
+            TSKind::switch_case => {
+                for (child, field) in children_with_fields(node) {
+                    match field {
+                        Some("body") if TSKind::from(child) == TSKind::block => {
+                            self.node(child, level)
+                        }
+                        Some("body") => self.statement(child, level + 1),
+                        _ => self.node(child, level),
+                    }
+                }
+            }
+            _ => {
+                for child in node.children(&mut node.walk()) {
+                    self.node(child, level);
+                }
+            }
+        }
+    }
+
+    /// Visit a node whose items are enclosed in braces, like a block or a methodmap.
+    fn container(&mut self, node: tree_sitter::Node<'tree>, level: usize) {
+        let mut inside = false;
+        for child in node.children(&mut node.walk()) {
+            match TSKind::from(child) {
+                TSKind::anon_LBRACE if !inside => {
+                    self.push_at(child, level);
+                    inside = true;
+                }
+                TSKind::anon_RBRACE if inside => {
+                    self.push_at(child, level);
+                    inside = false;
+                }
+                _ if inside => self.statement(child, level + 1),
+                _ => self.node(child, level),
+            }
+        }
+    }
+
+    /// Visit a control flow statement, indenting the bodies which are not blocks.
+    fn control_flow(&mut self, node: tree_sitter::Node<'tree>, level: usize) {
+        for (child, field) in children_with_fields(node) {
+            match (field, TSKind::from(child)) {
+                (Some("truePath" | "falsePath" | "body"), TSKind::block)
+                | (Some("falsePath"), TSKind::condition_statement) => self.node(child, level),
+                (Some("truePath" | "falsePath" | "body"), _) => self.statement(child, level + 1),
+                (_, TSKind::anon_else | TSKind::anon_while)
+                    if TSKind::from(node) != TSKind::while_statement =>
+                {
+                    self.push_at(child, level)
+                }
+                _ => self.node(child, level),
+            }
+        }
+    }
+
+    fn push_at(&mut self, node: tree_sitter::Node<'tree>, indent: usize) {
+        self.statement_start = false;
+        self.tokens.push(Token { node, indent });
+    }
+
+    fn edits(&self) -> Vec<TextEdit> {
+        let mut edits = Vec::new();
+        let Some(first) = self.tokens.first() else {
+            return edits;
+        };
+        let newline = if self.text.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+
+        self.replace_gap(
+            &mut edits,
+            TextRange::up_to(first.range().start()),
+            self.indent(first.indent),
+        );
+        for (prev, next) in self.tokens.iter().tuple_windows() {
+            let gap = TextRange::new(prev.range().end(), next.range().start());
+            if is_ternary_colon(next, &self.text[gap]) {
+                // The `:` of a ternary is spaced like a binary operator.
+                if !self.text[gap].contains('\n') && &self.text[gap] != " : " {
+                    edits.push(TextEdit::new(gap, " : ".to_string()));
+                }
+                continue;
+            }
+            let Some(spacing) = self.spacing(prev, next, &self.text[gap]) else {
+                continue;
+            };
+            let replacement = match spacing {
+                Spacing::None => String::new(),
+                Spacing::Space => " ".to_string(),
+                Spacing::Newlines(count) => newline.repeat(count) + &self.indent(next.indent),
+            };
+            self.reindent_comment(&mut edits, next, &self.text[gap], &replacement);
+            self.replace_gap(&mut edits, gap, replacement);
+        }
+        let last = self.tokens.last().expect("tokens should not be empty");
+        let gap = TextRange::new(last.range().end(), TextSize::of(self.text));
+        let replacement = if self.config.insert_final_newline || self.text[gap].contains('\n') {
+            newline
+        } else {
+            ""
+        };
+        self.replace_gap(&mut edits, gap, replacement.to_string());
+
+        for token in self.tokens.iter().filter(|token| is_directive(&token.node)) {
+            let text = &self.text[token.range()];
+            let trimmed = text.trim_end_matches([' ', '\t']);
+            if trimmed.len() != text.len() {
+                let start = token.range().start() + TextSize::of(trimmed);
+                edits.push(TextEdit::new(
+                    TextRange::new(start, token.range().end()),
+                    String::new(),
+                ));
+            }
+        }
+        edits.sort_by_key(|edit| edit.range().start());
+
+        edits
+    }
+
+    fn replace_gap(&self, edits: &mut Vec<TextEdit>, gap: TextRange, replacement: String) {
+        let text = &self.text[gap];
+        // Hidden tokens of the grammar do not appear in the tree.
+        if text != replacement && text.chars().all(char::is_whitespace) {
+            edits.push(TextEdit::new(gap, replacement));
+        }
+    }
+
+    /// Shift the lines of a multiline block comment along with its first line.
+    fn reindent_comment(
+        &self,
+        edits: &mut Vec<TextEdit>,
+        token: &Token,
+        gap: &str,
+        replacement: &str,
+    ) {
+        if token.kind() != TSKind::comment
+            || !gap.chars().all(char::is_whitespace)
+            || !replacement.contains('\n')
+        {
+            return;
+        }
+        let Some((_, old_indent)) = gap.rsplit_once('\n') else {
+            return;
+        };
+        let new_indent = self.indent(token.indent);
+        if old_indent == new_indent {
+            return;
+        }
+        let text = &self.text[token.range()];
+        for (offset, _) in text.match_indices('\n') {
+            let line_start = offset + 1;
+            if text[line_start..].starts_with(old_indent) {
+                let start = token.range().start() + TextSize::new(line_start as u32);
+                edits.push(TextEdit::new(
+                    TextRange::at(start, TextSize::of(old_indent)),
+                    new_indent.clone(),
+                ));
+            }
+        }
+    }
+
+    fn indent(&self, level: usize) -> String {
+        match self.config.indent {
+            IndentStyle::Tabs => "\t".repeat(level),
+            IndentStyle::Spaces(size) => " ".repeat(size as usize * level),
+        }
+    }
+
+    /// Compute the whitespace to put between two tokens.
+    ///
+    /// Returns `None` if the whitespace should be left untouched.
+    fn spacing(&self, prev: &Token, next: &Token, gap: &str) -> Option<Spacing> {
+        if prev.node.is_error() || next.node.is_error() {
+            return None;
+        }
+        let newlines = gap.matches('\n').count();
+        let keep_lines = |min: usize| {
+            Spacing::Newlines(newlines.clamp(min, (self.config.max_blank_lines + 1).max(min)))
+        };
+        let brace_spacing = match self.config.brace_style {
+            BraceStyle::NextLine => Spacing::Newlines(1),
+            BraceStyle::SameLine => Spacing::Space,
+        };
+        let (prev_kind, next_kind) = (prev.kind(), next.kind());
+
+        // Comments.
+        if is_line_comment(prev, self.text) {
+            return Some(keep_lines(1));
+        }
+        if newlines == 0 && (next_kind == TSKind::comment || prev_kind == TSKind::comment) {
+            if gap.is_empty() && !is_line_comment(next, self.text) {
+                return Some(Spacing::None);
+            }
+            return Some(Spacing::Space);
+        }
+
+        if is_directive(&prev.node) || is_directive(&next.node) {
+            return Some(keep_lines(1));
+        }
+
+        // Braces.
+        if is_container_brace(prev, TSKind::anon_LBRACE) {
+            if next_kind == TSKind::anon_RBRACE
+                && next.node.parent() == prev.node.parent()
+                && newlines == 0
+            {
+                return Some(Spacing::None);
+            }
+            return Some(Spacing::Newlines(1));
+        }
+        if is_container_brace(next, TSKind::anon_RBRACE) {
+            return Some(Spacing::Newlines(1));
+        }
+        if is_container_brace(next, TSKind::anon_LBRACE) {
+            if next.parent_kind() == Some(TSKind::struct_constructor) {
+                return Some(if newlines == 0 {
+                    Spacing::Space
+                } else {
+                    Spacing::Newlines(1)
+                });
+            }
+            return Some(brace_spacing);
+        }
+        if is_container_brace(prev, TSKind::anon_RBRACE) {
+            return Some(match next_kind {
+                TSKind::anon_SEMI_ | TSKind::anon_COMMA | TSKind::anon_RPAREN => Spacing::None,
+                TSKind::anon_else => brace_spacing,
+                TSKind::anon_while if next.parent_kind() == Some(TSKind::do_while_statement) => {
+                    brace_spacing
+                }
+                _ => keep_lines(1),
+            });
+        }
+
+        // Statements and items.
+        if prev_kind == TSKind::anon_SEMI_ && !is_in_for_header(prev.node) {
+            return Some(keep_lines(1));
+        }
+        if prev_kind == TSKind::anon_COMMA
+            && matches!(
+                prev.parent_kind(),
+                Some(TSKind::enum_entries | TSKind::funcenum | TSKind::struct_constructor)
+            )
+        {
+            return Some(keep_lines(1));
+        }
+        if prev_kind == TSKind::anon_else && next_kind == TSKind::anon_if {
+            return Some(Spacing::Space);
+        }
+
+        if newlines > 0 {
+            return Some(keep_lines(1));
+        }
+        Some(inline_spacing(prev, next, gap))
+    }
+}
+
+/// Compute the spacing between two tokens on the same line.
+fn inline_spacing(prev: &Token, next: &Token, gap: &str) -> Spacing {
+    let (prev_kind, next_kind) = (prev.kind(), next.kind());
+    if matches!(
+        next_kind,
+        TSKind::anon_COMMA | TSKind::anon_SEMI_ | TSKind::anon_RPAREN | TSKind::anon_RBRACK
+    ) || matches!(prev_kind, TSKind::anon_LPAREN | TSKind::anon_LBRACK)
+    {
+        return Spacing::None;
+    }
+    if matches!(prev_kind, TSKind::anon_COMMA | TSKind::anon_SEMI_) {
+        return Spacing::Space;
+    }
+    if is_binary_operator(prev) || is_binary_operator(next) {
+        return Spacing::Space;
+    }
+    if next_kind == TSKind::anon_AMP && next.parent_kind() == Some(TSKind::parameter_declaration) {
+        return Spacing::Space;
+    }
+    if is_prefix_operator(prev) || is_postfix_operator(next) {
+        return Spacing::None;
+    }
+    if matches!(prev_kind, TSKind::anon_DOT_ | TSKind::anon_COLON_COLON_)
+        || matches!(
+            next_kind,
+            TSKind::anon_DOT_ | TSKind::anon_COLON_COLON_ | TSKind::anon_LBRACK
+        )
+    {
+        return Spacing::None;
+    }
+    if matches!(next_kind, TSKind::anon_COLON | TSKind::anon_COLON_) {
+        return Spacing::None;
+    }
+    if matches!(prev_kind, TSKind::anon_COLON | TSKind::anon_COLON_) {
+        return if prev.parent_kind() == Some(TSKind::switch_case) {
+            Spacing::Space
+        } else {
+            Spacing::None
+        };
+    }
+    if is_view_as_bracket(prev) || is_view_as_bracket(next) {
+        return Spacing::None;
+    }
+    if next_kind == TSKind::anon_LPAREN {
+        let is_function_type = next.parent_kind() == Some(TSKind::parameter_declarations)
+            && next
+                .node
+                .parent()
+                .and_then(|it| it.parent())
+                .map(TSKind::from)
+                == Some(TSKind::typedef_expression);
+        return if is_function_type
+            || matches!(
+                prev_kind,
+                TSKind::anon_if
+                    | TSKind::anon_for
+                    | TSKind::anon_while
+                    | TSKind::anon_switch
+                    | TSKind::anon_return_
+            ) {
+            Spacing::Space
+        } else {
+            Spacing::None
+        };
+    }
+    if prev_kind == TSKind::anon_RPAREN || !gap.is_empty() {
+        Spacing::Space
+    } else {
+        Spacing::None
+    }
+}
+
+fn children_with_fields(
+    node: tree_sitter::Node<'_>,
+) -> Vec<(tree_sitter::Node<'_>, Option<&'static str>)> {
+    let mut res = Vec::new();
+    let mut cursor = node.walk();
+    if cursor.goto_first_child() {
+        loop {
+            res.push((cursor.node(), cursor.field_name()));
+            if !cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+    res
+}
+
+/// Whether the node's items are enclosed in braces and indented.
+fn is_container(kind: TSKind) -> bool {
+    matches!(
+        kind,
+        TSKind::block
+            | TSKind::methodmap
+            | TSKind::methodmap_property
+            | TSKind::enum_struct
+            | TSKind::enum_entries
+            | TSKind::r#struct
+            | TSKind::struct_constructor
+            | TSKind::typeset
+            | TSKind::funcenum
+            | TSKind::switch_statement
+    )
+}
+
+fn is_container_brace(token: &Token, brace: TSKind) -> bool {
+    token.kind() == brace && token.parent_kind().is_some_and(is_container)
+}
+
+fn is_directive(node: &tree_sitter::Node) -> bool {
+    matches!(
+        TSKind::from(node),
+        TSKind::preproc_include
+            | TSKind::preproc_tryinclude
+            | TSKind::preproc_macro
+            | TSKind::preproc_define
+            | TSKind::preproc_undefine
+            | TSKind::preproc_if
+            | TSKind::preproc_elseif
+            | TSKind::preproc_else
+            | TSKind::preproc_endif
+            | TSKind::preproc_endinput
+            | TSKind::preproc_assert
+            | TSKind::preproc_pragma
+            | TSKind::preproc_error
+            | TSKind::preproc_warning
+    )
+}
+
+/// Whether the node must be kept as is.
+fn is_verbatim(node: &tree_sitter::Node) -> bool {
+    node.is_error()
+        || matches!(
+            TSKind::from(node),
+            TSKind::comment
+                | TSKind::string_literal
+                | TSKind::char_literal
+                | TSKind::system_lib_string
+        )
+}
+
+fn is_line_comment(token: &Token, text: &str) -> bool {
+    token.kind() == TSKind::comment && text[token.range()].starts_with("//")
+}
+
+fn is_in_for_header(node: tree_sitter::Node) -> bool {
+    let Some(for_statement) = std::iter::successors(node.parent(), |it| it.parent())
+        .find(|it| TSKind::from(it) == TSKind::for_statement)
+    else {
+        return false;
+    };
+    for_statement
+        .child_by_field_name("body")
+        .is_none_or(|body| node.end_byte() <= body.start_byte())
+}
+
+fn is_binary_operator(token: &Token) -> bool {
+    if token.node.is_named() {
+        return false;
+    }
+    match token.parent_kind() {
+        Some(
+            TSKind::binary_expression
+            | TSKind::case_binary_expression
+            | TSKind::assignment_expression,
+        ) => true,
+        Some(TSKind::ternary_expression) => token.kind() == TSKind::anon_QMARK_,
+        Some(TSKind::methodmap) => token.kind() == TSKind::anon_LT,
+        _ => token.kind() == TSKind::anon_EQ,
+    }
+}
+
+/// Whether the gap before `next` holds the `:` of a ternary, which is a hidden token of the
+/// grammar.
+fn is_ternary_colon(next: &Token, gap: &str) -> bool {
+    if gap.trim() != ":" {
+        return false;
+    }
+    let mut node = next.node;
+    while let Some(parent) = node.parent() {
+        if TSKind::from(parent) == TSKind::ternary_expression
+            && parent.child_by_field_name("alternative") == Some(node)
+        {
+            return true;
+        }
+        // `next` must be the first token of the alternative.
+        if parent.start_byte() != node.start_byte() {
+            return false;
+        }
+        node = parent;
+    }
+
+    false
+}
+
+fn is_prefix_operator(token: &Token) -> bool {
+    if token.node.is_named() {
+        return false;
+    }
+    match token.parent_kind() {
+        Some(TSKind::unary_expression | TSKind::case_unary_expression) => true,
+        Some(TSKind::update_expression) => token.node.next_sibling().is_some(),
+        Some(TSKind::parameter_declaration) => token.kind() == TSKind::anon_AMP,
+        _ => false,
+    }
+}
+
+fn is_postfix_operator(token: &Token) -> bool {
+    !token.node.is_named()
+        && token.parent_kind() == Some(TSKind::update_expression)
+        && token.node.next_sibling().is_none()
+}
+
+fn is_view_as_bracket(token: &Token) -> bool {
+    matches!(token.kind(), TSKind::anon_LT | TSKind::anon_GT)
+        && token.parent_kind() == Some(TSKind::view_as)
+}
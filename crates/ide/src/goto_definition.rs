@@ -1,7 +1,7 @@
 use std::hash::Hash;
 
 use base_db::FilePosition;
-use hir::{HasSource, Semantics};
+use hir::{DefResolution, HasSource, Semantics};
 
 use line_index::TextRange;
 use smol_str::{SmolStr, ToSmolStr};
@@ -22,6 +22,30 @@ impl NavigationTarget {
     pub fn focus_or_full_range(&self) -> TextRange {
         self.focus_range.unwrap_or(self.full_range)
     }
+
+    /// Build a navigation target to the source of a definition.
+    pub(crate) fn from_def(db: &RootDatabase, def: DefResolution) -> Option<Self> {
+        let sema = Semantics::new(db);
+        let file_id = def.file_id(db);
+        let source_tree = sema.parse(file_id);
+        let name = def.name(db).map(|it| it.to_smolstr()).unwrap_or_default();
+        let def_node = def.source(db, &source_tree)?.value;
+
+        let name_range = find_inner_name_range(&def_node);
+
+        let target_preprocessing_results = sema.preprocess_file(file_id);
+        Some(NavigationTarget {
+            name,
+            file_id,
+            full_range: target_preprocessing_results
+                .source_map()
+                .closest_u_range_always(ts_range_to_text_range(&def_node.range())),
+            focus_range: target_preprocessing_results
+                .source_map()
+                .closest_u_range_always(name_range)
+                .into(),
+        })
+    }
 }
 
 pub(crate) fn goto_definition(
@@ -69,25 +93,7 @@ pub(crate) fn goto_definition(
         .source_map()
         .closest_u_range_always(ts_range);
 
-    let file_id = def.file_id(db);
-    let source_tree = sema.parse(file_id);
-    let name = def.name(db).map(|it| it.to_smolstr()).unwrap_or_default();
-    let def_node = def.source(db, &source_tree)?.value;
-
-    let name_range = find_inner_name_range(&def_node);
-
-    let target_preprocessing_results = sema.preprocess_file(file_id);
-    let navs = vec![NavigationTarget {
-        name,
-        file_id,
-        full_range: target_preprocessing_results
-            .source_map()
-            .closest_u_range_always(ts_range_to_text_range(&def_node.range())),
-        focus_range: target_preprocessing_results
-            .source_map()
-            .closest_u_range_always(name_range)
-            .into(),
-    }];
+    let navs = vec![NavigationTarget::from_def(db, def)?];
 
     RangeInfo::new(u_range, navs).into()
 }
@@ -0,0 +1,202 @@
+use base_db::{FileExtension, FilePosition, SourceDatabaseExt};
+use hir::{DefResolution, File, FileDef, Function, FunctionType, Semantics};
+use hir_def::{DefDatabase, FunctionKind, ParamData, RawVisibilityId};
+use syntax::utils::ts_range_to_text_range;
+use vfs::FileId;
+
+use crate::{NavigationTarget, RangeInfo, RootDatabase};
+
+/// Signature of a callback type, i.e. a typedef or a functag.
+struct CallbackSignature<'a> {
+    ret_type: Option<String>,
+    params: &'a [ParamData],
+}
+
+/// Returns the implementations of the item at the position:
+/// - the `public` functions implementing a `forward`;
+/// - the functions whose signature conforms to a `typedef`, a `typeset`, a `functag` or a
+///   `funcenum`.
+pub(crate) fn goto_implementation(
+    db: &RootDatabase,
+    pos: FilePosition,
+) -> Option<RangeInfo<Vec<NavigationTarget>>> {
+    let sema = &Semantics::new(db);
+    let preprocessing_results = sema.preprocess_file(pos.file_id);
+    let tree = sema.parse(pos.file_id);
+
+    let offset: u32 = preprocessing_results
+        .source_map()
+        .closest_s_position_always(pos.offset)
+        .into();
+    let node = tree
+        .root_node()
+        .descendant_for_byte_range(offset as usize, offset as usize)?;
+    let def = sema.find_def(pos.file_id, &node)?;
+    let u_range = preprocessing_results
+        .source_map()
+        .closest_u_range_always(ts_range_to_text_range(&node.range()));
+
+    let implementations = match def {
+        DefResolution::Function(function) => forward_implementations(db, function)?,
+        DefResolution::Typedef(typedef) => {
+            let data = db.typedef_data(typedef.id());
+            conforming_functions(
+                db,
+                &[CallbackSignature {
+                    ret_type: Some(data.type_ref.type_as_string()),
+                    params: data.params(),
+                }],
+            )
+        }
+        DefResolution::Typeset(typeset) => {
+            let typedefs = typeset
+                .children(db)
+                .into_iter()
+                .map(|typedef| db.typedef_data(typedef.id()))
+                .collect::<Vec<_>>();
+            let signatures = typedefs
+                .iter()
+                .map(|data| CallbackSignature {
+                    ret_type: Some(data.type_ref.type_as_string()),
+                    params: data.params(),
+                })
+                .collect::<Vec<_>>();
+            conforming_functions(db, &signatures)
+        }
+        DefResolution::Functag(functag) => {
+            let data = db.functag_data(functag.id());
+            conforming_functions(
+                db,
+                &[CallbackSignature {
+                    ret_type: data.type_ref.as_ref().map(|it| it.type_as_string()),
+                    params: data.params(),
+                }],
+            )
+        }
+        DefResolution::Funcenum(funcenum) => {
+            let functags = funcenum
+                .children(db)
+                .into_iter()
+                .map(|functag| db.functag_data(functag.id()))
+                .collect::<Vec<_>>();
+            let signatures = functags
+                .iter()
+                .map(|data| CallbackSignature {
+                    ret_type: data.type_ref.as_ref().map(|it| it.type_as_string()),
+                    params: data.params(),
+                })
+                .collect::<Vec<_>>();
+            conforming_functions(db, &signatures)
+        }
+        _ => return None,
+    };
+    let navs = implementations
+        .into_iter()
+        .filter_map(|function| NavigationTarget::from_def(db, function.into()))
+        .collect();
+
+    RangeInfo::new(u_range, navs).into()
+}
+
+/// The `public` functions of the workspace implementing a `forward`.
+fn forward_implementations(db: &RootDatabase, forward: Function) -> Option<Vec<Function>> {
+    let data = db.function_data(forward.id());
+    if data.kind != FunctionKind::Forward {
+        return None;
+    }
+    let name = data.name();
+
+    Some(
+        workspace_functions(db)
+            .into_iter()
+            .filter(|function| {
+                let data = db.function_data(function.id());
+                data.kind == FunctionKind::Def
+                    && data.visibility.contains(RawVisibilityId::PUBLIC)
+                    && data.name == name
+            })
+            .collect(),
+    )
+}
+
+/// The functions of the workspace which can be used as a callback of one of the signatures.
+fn conforming_functions(db: &RootDatabase, signatures: &[CallbackSignature]) -> Vec<Function> {
+    workspace_functions(db)
+        .into_iter()
+        .filter(|function| {
+            let data = db.function_data(function.id());
+            data.kind == FunctionKind::Def
+                && function.kind(db) == FunctionType::Function
+                && signatures.iter().any(|signature| {
+                    conforms_to(
+                        data.type_ref().map(|it| it.type_as_string()),
+                        data.params(),
+                        signature,
+                    )
+                })
+        })
+        .collect()
+}
+
+/// Whether a function can be used as a callback of the signature.
+///
+/// A callback may omit the trailing parameters of the signature. Functions declared without a
+/// return type (old syntax) conform to any return type.
+fn conforms_to(
+    ret_type: Option<String>,
+    params: &[ParamData],
+    signature: &CallbackSignature,
+) -> bool {
+    if let (Some(ret_type), Some(expected)) = (&ret_type, &signature.ret_type) {
+        if ret_type != expected {
+            return false;
+        }
+    }
+    if params.len() > signature.params.len()
+        && !signature.params.last().is_some_and(|param| param.is_rest)
+    {
+        return false;
+    }
+    params
+        .iter()
+        .zip(signature.params.iter())
+        .all(|(param, expected)| {
+            if expected.is_rest {
+                return true;
+            }
+            param.is_ref == expected.is_ref
+                && param.type_ref.as_ref().map(|it| it.type_as_string())
+                    == expected.type_ref.as_ref().map(|it| it.type_as_string())
+        })
+}
+
+/// The functions declared in the files of the workspace, i.e. not in an include directory.
+fn workspace_functions(db: &RootDatabase) -> Vec<Function> {
+    workspace_files(db)
+        .into_iter()
+        .flat_map(|file_id| File::from(file_id).declarations(db))
+        .filter_map(|def| match def {
+            FileDef::Function(function) => Some(function),
+            _ => None,
+        })
+        .collect()
+}
+
+fn workspace_files(db: &RootDatabase) -> Vec<FileId> {
+    db.source_roots()
+        .into_iter()
+        .filter(|source_root| !source_root.is_include_dir)
+        .flat_map(|source_root| {
+            source_root
+                .iter()
+                .filter(|file_id| {
+                    source_root
+                        .path_for_file(file_id)
+                        .and_then(|path| path.name_and_extension())
+                        .and_then(|(_, ext)| FileExtension::try_from(ext?).ok())
+                        .is_some()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
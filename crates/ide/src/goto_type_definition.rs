@@ -0,0 +1,30 @@
+use base_db::FilePosition;
+use hir::Semantics;
+use syntax::utils::ts_range_to_text_range;
+
+use crate::{NavigationTarget, RangeInfo, RootDatabase};
+
+pub(crate) fn goto_type_definition(
+    db: &RootDatabase,
+    pos: FilePosition,
+) -> Option<RangeInfo<Vec<NavigationTarget>>> {
+    let sema = &Semantics::new(db);
+    let preprocessing_results = sema.preprocess_file(pos.file_id);
+    let tree = sema.parse(pos.file_id);
+
+    let offset: u32 = preprocessing_results
+        .source_map()
+        .closest_s_position_always(pos.offset)
+        .into();
+    let node = tree
+        .root_node()
+        .descendant_for_byte_range(offset as usize, offset as usize)?;
+    let type_def = sema.find_def(pos.file_id, &node)?.type_def(db)?;
+    let u_range = preprocessing_results
+        .source_map()
+        .closest_u_range_always(ts_range_to_text_range(&node.range()));
+
+    let navs = vec![NavigationTarget::from_def(db, type_def)?];
+
+    RangeInfo::new(u_range, navs).into()
+}
@@ -0,0 +1,156 @@
+use base_db::FilePosition;
+use hir::{DefResolution, Semantics};
+use hir_def::DefDatabase;
+use ide_db::RootDatabase;
+use line_index::TextRange;
+use syntax::TSKind;
+use vfs::FileId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceCategory {
+    Read,
+    /// The reference is assigned, incremented, deleted or passed by reference.
+    Write,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HighlightedRange {
+    pub range: TextRange,
+    pub category: ReferenceCategory,
+}
+
+/// Returns the occurrences, in the file of the position, of the symbol at the position.
+pub(crate) fn highlight_related(
+    db: &RootDatabase,
+    fpos: FilePosition,
+) -> Option<Vec<HighlightedRange>> {
+    let sema = &Semantics::new(db);
+    let (_, references) = sema.find_references_from_pos(fpos)?;
+    let tree = sema.parse(fpos.file_id);
+    let preprocessing_results = sema.preprocess_file(fpos.file_id);
+    let source_map = preprocessing_results.source_map();
+
+    let mut res = references
+        .into_iter()
+        .filter(|frange| frange.file_id == fpos.file_id)
+        .map(|frange| {
+            let offset: usize = source_map
+                .closest_s_position_always(frange.range.start())
+                .into();
+            let category = tree
+                .root_node()
+                .descendant_for_byte_range(offset, offset)
+                .filter(|node| is_write(sema, fpos, node))
+                .map_or(ReferenceCategory::Read, |_| ReferenceCategory::Write);
+            HighlightedRange {
+                range: frange.range,
+                category,
+            }
+        })
+        .collect::<Vec<_>>();
+    res.sort_by_key(|it| it.range.start());
+    res.dedup();
+
+    Some(res)
+}
+
+fn is_write(sema: &Semantics<RootDatabase>, fpos: FilePosition, node: &tree_sitter::Node) -> bool {
+    let Some(parent) = node.parent() else {
+        return false;
+    };
+    if TSKind::from(parent) == TSKind::variable_declaration {
+        // Variables are always initialized when they are declared.
+        return parent
+            .child_by_field_name("name")
+            .is_some_and(|name| &name == node);
+    }
+
+    // Writing to an element of an array writes to the array, e.g. `arr` in `arr[0] = 1`, and
+    // writing to a field writes to the field only, e.g. `field` but not `es` in `es.field = 1`.
+    let mut place = *node;
+    while let Some(parent) = place.parent() {
+        let field = match TSKind::from(parent) {
+            TSKind::array_indexed_access => "array",
+            TSKind::field_access => "field",
+            _ => break,
+        };
+        if parent.child_by_field_name(field) != Some(place) {
+            break;
+        }
+        place = parent;
+    }
+    let Some(parent) = place.parent() else {
+        return false;
+    };
+    match TSKind::from(parent) {
+        TSKind::assignment_expression => parent.child_by_field_name("left") == Some(place),
+        // `delete` sets the handle to `null`.
+        TSKind::update_expression | TSKind::delete_statement => true,
+        TSKind::call_arguments => is_passed_by_ref(sema, fpos.file_id, &parent, &place),
+        TSKind::named_arg => parent
+            .parent()
+            .is_some_and(|arguments| is_passed_by_ref(sema, fpos.file_id, &arguments, &parent)),
+        _ => false,
+    }
+}
+
+/// Whether the argument of a call is passed to a non-const parameter by reference.
+pub(crate) fn is_passed_by_ref(
+    sema: &Semantics<RootDatabase>,
+    file_id: FileId,
+    arguments: &tree_sitter::Node,
+    arg: &tree_sitter::Node,
+) -> bool {
+    let Some(call) = arguments.parent() else {
+        return false;
+    };
+    let callee = match TSKind::from(call) {
+        TSKind::call_expression => match call.child_by_field_name("function") {
+            Some(function) if TSKind::from(function) == TSKind::field_access => {
+                function.child_by_field_name("field")
+            }
+            function => function,
+        },
+        TSKind::new_expression => call.child_by_field_name("class"),
+        _ => None,
+    };
+    let Some(callee) = callee else {
+        return false;
+    };
+    let function = match sema.find_def(file_id, &callee) {
+        Some(DefResolution::Function(function)) => function,
+        Some(DefResolution::Methodmap(methodmap)) => match methodmap.constructor(sema.db) {
+            Some(function) => function,
+            None => return false,
+        },
+        _ => return false,
+    };
+    let data = sema.db.function_data(function.id());
+    let param = if TSKind::from(arg) == TSKind::named_arg {
+        let source = sema.preprocessed_text(file_id);
+        let Some(name) = arg
+            .child_by_field_name("arg_name")
+            .and_then(|name| name.utf8_text(source.as_bytes()).ok())
+        else {
+            return false;
+        };
+        data.params()
+            .iter()
+            .find(|param| param.name.as_ref().is_some_and(|it| it.to_string() == name))
+    } else {
+        let mut cursor = arguments.walk();
+        let Some(idx) = arguments
+            .children(&mut cursor)
+            .filter(|it| it.is_named() && !it.is_extra())
+            .position(|it| it == *arg)
+        else {
+            return false;
+        };
+        // Arguments after a rest parameter are matched to it.
+        data.params()
+            .get(idx)
+            .or_else(|| data.params().last().filter(|param| param.is_rest))
+    };
+
+    param.is_some_and(|param| param.is_ref && !param.is_const)
+}
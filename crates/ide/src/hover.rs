@@ -7,13 +7,11 @@ use hir::{DefResolution, HasSource, Semantics};
 use ide_db::{Documentation, RootDatabase};
 use itertools::Itertools;
 use preprocessor::{db::PreprocDatabase, PreprocessingResult};
-use smol_str::ToSmolStr;
 use syntax::utils::ts_range_to_text_range;
 use vfs::FileId;
 
 use crate::{
     events::{event_hover, event_name},
-    goto_definition::find_inner_name_range,
     markup::Markup,
     FilePosition, NavigationTarget, RangeInfo,
 };
@@ -54,28 +52,9 @@ impl HoverAction {
         let mut targets = targets
             .into_iter()
             .filter_map(|def| {
-                let sema = Semantics::new(db);
-                let file_id = def.file_id(db);
-                let source_tree = sema.parse(file_id);
-                let name = def.name(db).map(|it| it.to_smolstr()).unwrap_or_default();
-                let def_node = def.source(db, &source_tree)?.value;
-
-                let name_range = find_inner_name_range(&def_node);
-
-                let target_preprocessing_results = sema.preprocess_file(file_id);
                 Some(HoverGotoTypeData {
                     mod_path: Default::default(),
-                    nav: NavigationTarget {
-                        name,
-                        file_id,
-                        full_range: target_preprocessing_results
-                            .source_map()
-                            .closest_u_range_always(ts_range_to_text_range(&def_node.range())),
-                        focus_range: target_preprocessing_results
-                            .source_map()
-                            .closest_u_range_always(name_range)
-                            .into(),
-                    },
+                    nav: NavigationTarget::from_def(db, def)?,
                 })
             })
             .collect_vec();
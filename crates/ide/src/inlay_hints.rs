@@ -0,0 +1,170 @@
+use hir::{DefResolution, Semantics};
+use hir_def::DefDatabase;
+use ide_db::RootDatabase;
+use line_index::TextRange;
+use preprocessor::SourceMap;
+use smol_str::SmolStr;
+use syntax::{utils::ts_range_to_text_range, TSKind};
+use vfs::FileId;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InlayHintsConfig {
+    pub parameter_hints: bool,
+    /// Only show the parameter hints of the arguments which are literals.
+    pub parameter_hints_literals_only: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InlayKind {
+    Parameter,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InlayHint {
+    /// The range of the node the hint is attached to.
+    pub range: TextRange,
+    pub kind: InlayKind,
+    pub label: SmolStr,
+}
+
+pub(crate) fn inlay_hints(
+    db: &RootDatabase,
+    file_id: FileId,
+    range: Option<TextRange>,
+    config: &InlayHintsConfig,
+) -> Vec<InlayHint> {
+    let mut res = Vec::new();
+    if !config.parameter_hints {
+        return res;
+    }
+    let sema = &Semantics::new(db);
+    let tree = sema.parse(file_id);
+    let preprocessing_results = sema.preprocess_file(file_id);
+    let source_map = preprocessing_results.source_map();
+    let source = preprocessing_results.preprocessed_text();
+
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if let Some(range) = range {
+            let Some(u_range) = u_range(source_map, &node) else {
+                continue;
+            };
+            if u_range.intersect(range).is_none() {
+                continue;
+            }
+        }
+        if matches!(
+            TSKind::from(&node),
+            TSKind::call_expression | TSKind::new_expression
+        ) {
+            param_name_hints(sema, file_id, source_map, &source, &node, config, &mut res);
+        }
+        stack.extend(node.children(&mut node.walk()));
+    }
+    res.sort_by_key(|hint| hint.range.start());
+
+    res
+}
+
+fn param_name_hints(
+    sema: &Semantics<RootDatabase>,
+    file_id: FileId,
+    source_map: &SourceMap,
+    source: &str,
+    node: &tree_sitter::Node,
+    config: &InlayHintsConfig,
+    acc: &mut Vec<InlayHint>,
+) -> Option<()> {
+    let callee = match TSKind::from(node) {
+        TSKind::call_expression => {
+            let function = node.child_by_field_name("function")?;
+            match TSKind::from(&function) {
+                TSKind::identifier => function,
+                TSKind::field_access => function.child_by_field_name("field")?,
+                _ => return None,
+            }
+        }
+        TSKind::new_expression => node.child_by_field_name("class")?,
+        _ => return None,
+    };
+    let function = match sema.find_def(file_id, &callee)? {
+        DefResolution::Function(function) => function,
+        DefResolution::Methodmap(methodmap) => methodmap.constructor(sema.db)?,
+        _ => return None,
+    };
+    let arguments = node.child_by_field_name("arguments")?;
+    let data = sema.db.function_data(function.id());
+
+    let params = data.params().iter();
+    let mut cursor = arguments.walk();
+    let args = arguments
+        .children(&mut cursor)
+        .filter(|arg| arg.is_named() && !arg.is_extra());
+    for (param, arg) in params.zip(args) {
+        if param.is_rest || TSKind::from(&arg) == TSKind::named_arg {
+            // Named arguments already show the name of the parameter, and the
+            // arguments after them can't be matched to a parameter by position.
+            break;
+        }
+        let Some(name) = &param.name else {
+            continue;
+        };
+        if config.parameter_hints_literals_only && !is_literal(&arg) {
+            continue;
+        }
+        if argument_matches_name(&arg, source, name.to_string().as_str()) {
+            continue;
+        }
+        // Arguments coming from a macro expansion have no position in the file.
+        if source_map
+            .expanded_symbol_from_s_pos(ts_range_to_text_range(&arg.range()).start())
+            .is_some()
+        {
+            continue;
+        }
+        let Some(range) = u_range(source_map, &arg) else {
+            continue;
+        };
+        acc.push(InlayHint {
+            range,
+            kind: InlayKind::Parameter,
+            label: name.to_string().into(),
+        });
+    }
+
+    Some(())
+}
+
+fn u_range(source_map: &SourceMap, node: &tree_sitter::Node) -> Option<TextRange> {
+    source_map.closest_u_range(ts_range_to_text_range(&node.range()))
+}
+
+fn is_literal(node: &tree_sitter::Node) -> bool {
+    match TSKind::from(node) {
+        TSKind::int_literal
+        | TSKind::float_literal
+        | TSKind::char_literal
+        | TSKind::string_literal
+        | TSKind::bool_literal
+        | TSKind::null => true,
+        TSKind::unary_expression => node
+            .child_by_field_name("argument")
+            .is_some_and(|arg| is_literal(&arg)),
+        _ => false,
+    }
+}
+
+/// Whether the argument is a reference to something named like the parameter, e.g. `client`
+/// for a `client` parameter.
+fn argument_matches_name(arg: &tree_sitter::Node, source: &str, name: &str) -> bool {
+    let node = match TSKind::from(arg) {
+        TSKind::identifier => *arg,
+        TSKind::field_access => match arg.child_by_field_name("field") {
+            Some(field) => field,
+            None => return false,
+        },
+        _ => return false,
+    };
+    node.utf8_text(source.as_bytes())
+        .is_ok_and(|text| text.eq_ignore_ascii_case(name))
+}
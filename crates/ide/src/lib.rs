@@ -1,10 +1,18 @@
 //! base_db defines basic database traits. The concrete DB is defined by ide.
 
+mod assists;
 mod call_hierarchy;
 mod completion;
+mod dead_code;
 mod events;
+mod folding_ranges;
+mod formatting;
 mod goto_definition;
+mod goto_implementation;
+mod goto_type_definition;
+mod highlight_related;
 mod hover;
+mod inlay_hints;
 mod markup;
 mod prime_caches;
 mod references;
@@ -25,7 +33,7 @@ use hir_def::{print_item_tree, DefDatabase};
 use hover::HoverResult;
 use ide_db::{
     CallItem, IncomingCallItem, LineIndexDatabase, OutgoingCallItem, RootDatabase, SourceChange,
-    Symbols,
+    Symbols, TextEdit,
 };
 use itertools::Itertools;
 use line_index::TextRange;
@@ -37,15 +45,23 @@ use serde_json::Value;
 use vfs::FileId;
 
 pub use completion::{CompletionItem, CompletionKind};
+pub use dead_code::{DeadCodeItem, ProjectDeadCode};
+pub use folding_ranges::{Fold, FoldKind};
+pub use formatting::{BraceStyle, FormattingConfig, IndentStyle};
 pub use goto_definition::NavigationTarget;
+pub use highlight_related::{HighlightedRange, ReferenceCategory};
 pub use hover::{HoverAction, HoverConfig, HoverDocFormat, HoverGotoTypeData};
-pub use ide_db::Cancellable;
+pub use ide_db::{
+    Assist, AssistId, AssistKind, AssistResolveStrategy, Cancellable, FileSymbol, Query,
+    SingleResolve,
+};
 pub use ide_diagnostics::{Diagnostic, DiagnosticsConfig, Severity};
+pub use inlay_hints::{InlayHint, InlayHintsConfig, InlayKind};
 pub use line_index::{LineCol, LineIndex, WideEncoding, WideLineCol};
 pub use markup::Markup;
 pub use prime_caches::ParallelPrimeCachesProgress;
 pub use signature_help::SignatureHelp;
-pub use syntax_highlighting::{Highlight, HlMod, HlMods, HlRange, HlTag};
+pub use syntax_highlighting::{Highlight, HighlightConfig, HlMod, HlMods, HlRange, HlTag};
 
 /// Info associated with a [`range`](TextRange).
 #[derive(Debug)]
@@ -217,7 +233,26 @@ impl Analysis {
         config: &DiagnosticsConfig,
         file_id: FileId,
     ) -> Cancellable<Vec<Diagnostic>> {
-        self.with_db(|db| ide_diagnostics::diagnostics(db, config, file_id))
+        self.with_db(|db| {
+            ide_diagnostics::diagnostics(db, config, &AssistResolveStrategy::None, file_id)
+        })
+    }
+
+    /// Computes the assists and the fixes of the diagnostics which apply to the range.
+    pub fn assists_with_fixes(
+        &self,
+        diagnostics_config: &DiagnosticsConfig,
+        resolve: AssistResolveStrategy,
+        frange: FileRange,
+    ) -> Cancellable<Vec<Assist>> {
+        self.with_db(|db| {
+            ide_diagnostics::diagnostics(db, diagnostics_config, &resolve, frange.file_id)
+                .into_iter()
+                .filter(|it| it.u_range.intersect(frange.range).is_some())
+                .flat_map(|it| it.fixes.unwrap_or_default())
+                .chain(assists::assists(db, resolve, frange))
+                .collect()
+        })
     }
 
     /// Returns the definitions from the symbol at `position`.
@@ -228,6 +263,30 @@ impl Analysis {
         self.with_db(|db| goto_definition::goto_definition(db, pos))
     }
 
+    /// Returns the type definitions of the symbol at `position`.
+    pub fn goto_type_definition(
+        &self,
+        pos: FilePosition,
+    ) -> Cancellable<Option<RangeInfo<Vec<NavigationTarget>>>> {
+        self.with_db(|db| goto_type_definition::goto_type_definition(db, pos))
+    }
+
+    /// Returns the implementations of the forward, typedef or functag at `position`.
+    pub fn goto_implementation(
+        &self,
+        pos: FilePosition,
+    ) -> Cancellable<Option<RangeInfo<Vec<NavigationTarget>>>> {
+        self.with_db(|db| goto_implementation::goto_implementation(db, pos))
+    }
+
+    /// Returns the occurrences, in the same file, of the symbol at `position`.
+    pub fn highlight_related(
+        &self,
+        pos: FilePosition,
+    ) -> Cancellable<Option<Vec<HighlightedRange>>> {
+        self.with_db(|db| highlight_related::highlight_related(db, pos))
+    }
+
     /// Returns the references for the symbol at `position`.
     pub fn references(&self, pos: FilePosition) -> Cancellable<Option<Vec<FileRange>>> {
         self.with_db(|db| references::references(db, pos))
@@ -243,6 +302,11 @@ impl Analysis {
         self.with_db(|db| symbols::symbols(db, file_id))
     }
 
+    /// Fuzzy searches the symbols of all the files of the workspace and include directories.
+    pub fn symbol_search(&self, query: Query) -> Cancellable<Vec<FileSymbol>> {
+        self.with_db(|db| ide_db::world_symbols(db, &query))
+    }
+
     /// Returns the hover information at `position`.
     pub fn hover(
         &self,
@@ -291,13 +355,68 @@ impl Analysis {
     }
 
     /// Returns the highlighted ranges for the file.
-    pub fn highlight(&self, file_id: FileId) -> Cancellable<Vec<syntax_highlighting::HlRange>> {
-        self.with_db(|db| syntax_highlighting::highlight(db, file_id, None))
+    pub fn highlight(
+        &self,
+        config: &HighlightConfig,
+        file_id: FileId,
+    ) -> Cancellable<Vec<syntax_highlighting::HlRange>> {
+        self.with_db(|db| syntax_highlighting::highlight(db, config, file_id, None))
     }
 
     /// Computes syntax highlighting for the given file range.
-    pub fn highlight_range(&self, frange: FileRange) -> Cancellable<Vec<HlRange>> {
-        self.with_db(|db| syntax_highlighting::highlight(db, frange.file_id, Some(frange.range)))
+    pub fn highlight_range(
+        &self,
+        config: &HighlightConfig,
+        frange: FileRange,
+    ) -> Cancellable<Vec<HlRange>> {
+        self.with_db(|db| {
+            syntax_highlighting::highlight(db, config, frange.file_id, Some(frange.range))
+        })
+    }
+
+    /// Returns the inlay hints of the given file range.
+    pub fn inlay_hints(
+        &self,
+        config: &InlayHintsConfig,
+        file_id: FileId,
+        range: Option<TextRange>,
+    ) -> Cancellable<Vec<InlayHint>> {
+        self.with_db(|db| inlay_hints::inlay_hints(db, file_id, range, config))
+    }
+
+    /// Returns the folding ranges of the file.
+    pub fn folding_ranges(&self, file_id: FileId) -> Cancellable<Vec<Fold>> {
+        self.with_db(|db| folding_ranges::folding_ranges(db, file_id))
+    }
+
+    /// Returns the edits to format the file.
+    pub fn format(
+        &self,
+        file_id: FileId,
+        config: &FormattingConfig,
+    ) -> Cancellable<Option<Vec<TextEdit>>> {
+        self.with_db(|db| formatting::format(db, file_id, None, config))
+    }
+
+    /// Returns the edits to format the given file range.
+    pub fn format_range(
+        &self,
+        frange: FileRange,
+        config: &FormattingConfig,
+    ) -> Cancellable<Option<Vec<TextEdit>>> {
+        self.with_db(|db| formatting::format(db, frange.file_id, Some(frange.range), config))
+    }
+
+    /// Returns the edits to sort the include directives of the file and to remove the duplicated
+    /// and unused ones.
+    pub fn organize_includes(&self, file_id: FileId) -> Cancellable<Vec<TextEdit>> {
+        self.with_db(|db| assists::organize_includes_edits(db, file_id))
+    }
+
+    /// Returns the declarations which are never referenced, for each project, or only for the
+    /// projects including the file if one is given.
+    pub fn dead_code(&self, file_id: Option<FileId>) -> Cancellable<Vec<ProjectDeadCode>> {
+        self.with_db(|db| dead_code::dead_code(db, file_id))
     }
 
     pub fn call_hierarchy_prepare(&self, fpos: FilePosition) -> Cancellable<Option<Vec<CallItem>>> {
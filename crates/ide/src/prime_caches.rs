@@ -4,7 +4,7 @@ use base_db::{FileExtension, SourceDatabase, SubGraph};
 use crossbeam::channel::Sender;
 use fxhash::{FxHashMap, FxHashSet};
 use hir_def::DefDatabase;
-use ide_db::{FxIndexMap, RootDatabase};
+use ide_db::{FxIndexMap, RootDatabase, SymbolsDatabase};
 use salsa::{Cancelled, Database, ParallelDatabase, Snapshot};
 use vfs::FileId;
 
@@ -63,6 +63,7 @@ pub(crate) fn parallel_prime_caches<F>(
 
                 subgraph.nodes.iter().for_each(|node| {
                     db.file_def_map(node.file_id);
+                    db.file_symbols(node.file_id);
                 });
 
                 progress_sender.send(ParallelPrimeCacheWorkerProgress::EndProject { file_id })?;
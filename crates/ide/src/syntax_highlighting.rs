@@ -1,5 +1,6 @@
 use std::fmt::{self, Debug, Write};
 
+use fxhash::FxHashSet;
 use hir::Semantics;
 use hir_def::resolver::{HasResolver, ValueNs};
 use ide_db::{RootDatabase, SymbolKind};
@@ -62,6 +63,8 @@ pub enum HlTag {
     FloatLiteral,
     IntLiteral,
     Comment,
+    /// A specifier of a format string, e.g. `%d`.
+    FormatSpecifier,
 
     // For things which don't have a specific highlight.
     None,
@@ -107,6 +110,13 @@ impl HlMods {
     }
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HighlightConfig {
+    /// Names of the functions whose last `char[]` parameter is a format string, in addition to
+    /// the ones declared with `const char[] format, any ...`.
+    pub format_functions: FxHashSet<String>,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct HlRange {
     pub range: TextRange,
@@ -116,7 +126,7 @@ pub struct HlRange {
 
 pub(crate) fn highlight(
     db: &RootDatabase,
-    // config: HighlightConfig,
+    config: &HighlightConfig,
     file_id: FileId,
     range_to_highlight: Option<TextRange>,
 ) -> Vec<HlRange> {
@@ -133,7 +143,7 @@ pub(crate) fn highlight(
     This would allow us to get proper modifiers for the symbols such as declarations or references.
     However, the current implementation is much simpler and should be good enough for now.
      */
-    lexer
+    let mut res = lexer
         .filter(|symbol| range_to_highlight.intersect(symbol.range).is_some())
         .flat_map(|symbol| match symbol.token_kind {
             TokenKind::Identifier => {
@@ -185,5 +195,47 @@ pub(crate) fn highlight(
 
             _ => None,
         })
-        .collect_vec()
+        .collect_vec();
+
+    // The string literals are not highlighted, the specifiers of the format strings can be
+    // added without overlapping other ranges.
+    res.extend(
+        format_specifiers(&sema, config, file_id, range_to_highlight)
+            .into_iter()
+            .filter(|range| range_to_highlight.intersect(*range).is_some())
+            .map(|range| HlRange {
+                range,
+                highlight: Highlight::new(HlTag::FormatSpecifier),
+            }),
+    );
+    res.sort_by_key(|it| it.range.start());
+
+    res
+}
+
+/// Returns the ranges of the specifiers of the format strings of the calls in `u_range`.
+fn format_specifiers(
+    sema: &Semantics<RootDatabase>,
+    config: &HighlightConfig,
+    file_id: FileId,
+    u_range: TextRange,
+) -> Vec<TextRange> {
+    let tree = sema.parse(file_id);
+    let preprocessing_results = sema.preprocess_file(file_id);
+    let source_map = preprocessing_results.source_map();
+    let start = source_map.closest_s_position_always(u_range.start());
+    let end = source_map.closest_s_position_always(u_range.end());
+    let s_range = TextRange::new(start, end.max(start));
+    ide_db::format_calls(
+        sema,
+        file_id,
+        &tree,
+        Some(s_range),
+        &config.format_functions,
+    )
+    .into_iter()
+    .flat_map(|call| call.specifiers)
+    // Specifiers coming from a macro are not in the file.
+    .filter_map(|specifier| source_map.editable_u_range(specifier.s_range))
+    .collect()
 }
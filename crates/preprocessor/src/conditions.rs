@@ -47,6 +47,10 @@ impl ConditionStack {
 pub struct ConditionOffsetStack {
     stack: Vec<TextSize>,
     skipped_ranges: Vec<TextRange>,
+    /// Start offsets of the branches of the conditions being processed, active or not.
+    branch_starts: Vec<TextSize>,
+    /// Ranges of all the branches of the conditions, from their directive to the next one.
+    branch_ranges: Vec<TextRange>,
 }
 
 impl ConditionOffsetStack {
@@ -72,6 +76,32 @@ impl ConditionOffsetStack {
         &self.skipped_ranges
     }
 
+    /// Start a new branch at an `#if` directive.
+    pub fn push_branch(&mut self, start: TextSize) {
+        self.branch_starts.push(start);
+    }
+
+    /// End the current branch and start a new one at an `#elseif` or `#else` directive.
+    pub fn split_branch(&mut self, at: TextSize) {
+        if let Some(start) = self.branch_starts.pop() {
+            self.branch_ranges.push(TextRange::new(start, at));
+            self.branch_starts.push(at);
+        }
+    }
+
+    /// End the current branch at an `#endif` directive.
+    pub fn pop_branch(&mut self, end: TextSize) {
+        if let Some(start) = self.branch_starts.pop() {
+            self.branch_ranges.push(TextRange::new(start, end));
+        }
+    }
+
+    pub fn take_branch_ranges(&mut self) -> Vec<TextRange> {
+        let mut ranges = std::mem::take(&mut self.branch_ranges);
+        ranges.sort_unstable_by(|a, b| a.start().cmp(&b.start()).then(b.end().cmp(&a.end())));
+        ranges
+    }
+
     pub fn sort_skipped_ranges(&mut self) {
         self.skipped_ranges
             .sort_unstable_by(|a, b| a.start().cmp(&b.start()).then(a.end().cmp(&b.end())));
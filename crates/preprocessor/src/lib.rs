@@ -28,7 +28,7 @@ mod symbol;
 use buffer::PreprocessorBuffer;
 pub use errors::{EvaluationError, PreprocessorError};
 pub(crate) use macros::MacroStore;
-pub use macros::{HMacrosMap, Macro, MacrosMap};
+pub use macros::{expand_macro_call, HMacrosMap, Macro, MacrosMap};
 pub use offset::{ExpandedSymbolOffset, SourceMap};
 pub use result::PreprocessingResult;
 
@@ -87,6 +87,7 @@ where
 
     pub fn result(mut self) -> PreprocessingResult {
         let inactive_ranges = self.get_inactive_ranges();
+        let condition_ranges = self.condition_offsets_stack.take_branch_ranges();
         let preprocessed_text: Arc<str> = self.buffer.contents().into();
         let mut res = PreprocessingResult::new(
             preprocessed_text.clone(),
@@ -94,6 +95,7 @@ where
             self.buffer.into_source_map(self.input, &preprocessed_text),
             self.errors,
             inactive_ranges,
+            condition_ranges,
         );
         res.shrink_to_fit();
         res
@@ -101,6 +103,7 @@ where
 
     pub fn error_result(mut self) -> PreprocessingResult {
         let inactive_ranges = self.get_inactive_ranges();
+        let condition_ranges = self.condition_offsets_stack.take_branch_ranges();
         let preprocessed_text: Arc<str> = self.buffer.contents().into();
         let mut res = PreprocessingResult::new(
             preprocessed_text.clone(),
@@ -108,6 +111,7 @@ where
             self.buffer.into_source_map(self.input, &preprocessed_text),
             self.errors,
             inactive_ranges,
+            condition_ranges,
         );
         res.shrink_to_fit();
         res
@@ -209,6 +213,9 @@ where
             }
             self.lexer.next()
         } {
+            if let TokenKind::PreprocDir(dir) = &symbol.token_kind {
+                self.track_condition_branch(dir, &symbol);
+            }
             if self.conditions_stack.top_is_activated_or_not_activated() {
                 if self.process_negative_condition(&symbol).is_err() {
                     return self.error_result();
@@ -323,6 +330,23 @@ where
         self.result()
     }
 
+    /// Keep track of the ranges of the branches of the conditions, whether they are active or not.
+    fn track_condition_branch(&mut self, dir: &PreprocDir, symbol: &Symbol) {
+        // Directives coming from a macro expansion are not part of the file.
+        if !self.expansion_stack.is_empty() {
+            return;
+        }
+        let offset = symbol.range.start();
+        match dir {
+            PreprocDir::MIf => self.condition_offsets_stack.push_branch(offset),
+            PreprocDir::MElseif | PreprocDir::MElse => {
+                self.condition_offsets_stack.split_branch(offset)
+            }
+            PreprocDir::MEndif => self.condition_offsets_stack.pop_branch(offset),
+            _ => (),
+        }
+    }
+
     fn process_if_directive(&mut self, symbol: &Symbol) {
         self.condition_offsets_stack.push(symbol.range.start());
         let mut if_condition =
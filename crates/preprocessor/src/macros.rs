@@ -3,7 +3,9 @@ use std::{cmp::Ordering, collections::VecDeque, sync::Arc};
 use deepsize::DeepSizeOf;
 use fxhash::{FxHashMap, FxHashSet};
 use smol_str::SmolStr;
-use sourcepawn_lexer::{Literal, Operator, Symbol, TextRange, TextSize, TokenKind};
+use sourcepawn_lexer::{
+    Literal, Operator, SourcepawnLexer, Symbol, TextRange, TextSize, TokenKind,
+};
 use stdx::hashable_hash_map::HashableHashMap;
 use vfs::FileId;
 
@@ -227,7 +229,7 @@ where
                 let new_context = if macro_.params.is_none() {
                     expand_non_macro_define(
                         macro_,
-                        symbol.delta,
+                        queued_symbol.delta,
                         queued_symbol.symbol.range.to_owned(),
                     )
                 } else {
@@ -247,7 +249,7 @@ where
                     if context_stack.is_empty() {
                         r_paren_offset = r_paren_offset_.to_owned().into();
                     }
-                    expand_macro(args, macro_, &queued_symbol.symbol, &symbol.delta)?
+                    expand_macro(args, macro_, &queued_symbol.symbol, &queued_symbol.delta)?
                 };
                 context_stack.push(current_context);
                 context_stack.push(new_context);
@@ -273,6 +275,42 @@ where
     Ok(r_paren_offset)
 }
 
+/// Expand the macro call at the start of `text` and return the text of its expansion.
+///
+/// # Arguments
+///
+/// * `text` - Text of the macro call, e.g. `FOO(1, 2)`.
+/// * `macros` - The macros which are defined at the call site.
+pub fn expand_macro_call(text: &str, macros: &MacrosMap) -> Option<String> {
+    let mut lexer = SourcepawnLexer::new(text);
+    let symbol = lexer.next()?;
+    if symbol.token_kind != TokenKind::Identifier {
+        return None;
+    }
+    let mut macro_store = MacroStore::default();
+    macro_store.extend(macros.clone());
+    let mut expansion_stack = Vec::new();
+    expand_identifier(
+        &mut lexer,
+        &mut macro_store,
+        &symbol,
+        &mut expansion_stack,
+        true,
+    )
+    .ok()?;
+
+    let mut res = String::new();
+    for symbol in expansion_stack.iter().rev() {
+        if symbol.token_kind == TokenKind::Eof {
+            continue;
+        }
+        res.push_str(&" ".repeat(symbol.delta.unsigned_abs() as usize));
+        res.push_str(&symbol.text());
+    }
+
+    Some(res.trim().to_string())
+}
+
 /// Expand a non macro define by returning a new [context](MacroContext) of all the [symbols](Symbol)
 /// in the [macro](Macro)'s body.
 ///
@@ -392,7 +430,7 @@ fn expand_macro(
                         new_context.push_back(QueuedSymbol::new(symbol, delta));
                     } else {
                         for (j, sub_child) in args[arg_idx].iter().enumerate() {
-                            let delta = if i == 1 {
+                            let delta = if i == 1 && j == 0 {
                                 symbol.delta
                             } else if j == 0 {
                                 percent_symbol.delta
@@ -159,6 +159,21 @@ impl SourceMap {
         Some(self.expanded_symbols[idx].clone())
     }
 
+    /// Whether the position of the preprocessed text is in the expansion of a macro.
+    pub fn is_in_expansion(&self, s_pos: TextSize) -> bool {
+        self.expanded_symbol_from_s_pos(s_pos)
+            .is_some_and(|it| it.expanded_range().contains(s_pos))
+    }
+
+    /// Returns the range of `s_range` in the source, or [`None`] if it starts in the expansion of
+    /// a macro, where it cannot be edited.
+    pub fn editable_u_range(&self, s_range: TextRange) -> Option<TextRange> {
+        if self.is_in_expansion(s_range.start()) {
+            return None;
+        }
+        self.closest_u_range(s_range)
+    }
+
     pub fn closest_s_position(&self, u_pos: TextSize) -> Option<TextSize> {
         if let Some(symbol) = self.expanded_symbol_from_u_pos(u_pos) {
             return Some(symbol.expanded_range.start());
@@ -12,6 +12,7 @@ pub struct PreprocessingResult {
     source_map: SourceMap,
     errors: PreprocessorErrors,
     inactive_ranges: Vec<TextRange>,
+    condition_ranges: Vec<TextRange>,
 }
 
 impl PreprocessingResult {
@@ -21,6 +22,7 @@ impl PreprocessingResult {
         mut source_map: SourceMap,
         errors: PreprocessorErrors,
         inactive_ranges: Vec<TextRange>,
+        condition_ranges: Vec<TextRange>,
     ) -> Self {
         source_map.sort();
         Self {
@@ -29,6 +31,7 @@ impl PreprocessingResult {
             source_map,
             errors,
             inactive_ranges,
+            condition_ranges,
         }
     }
 
@@ -37,6 +40,7 @@ impl PreprocessingResult {
         self.source_map.shrink_to_fit();
         self.errors.shrink_to_fit();
         self.inactive_ranges.shrink_to_fit();
+        self.condition_ranges.shrink_to_fit();
     }
 
     pub fn default(text: &str) -> Self {
@@ -49,6 +53,7 @@ impl PreprocessingResult {
             source_map,
             errors: Default::default(),
             inactive_ranges: Default::default(),
+            condition_ranges: Default::default(),
         }
     }
 
@@ -71,4 +76,10 @@ impl PreprocessingResult {
     pub fn inactive_ranges(&self) -> &[TextRange] {
         &self.inactive_ranges
     }
+
+    /// Ranges of the branches of the `#if`/`#elseif`/`#else` conditions of the file, active or
+    /// not. Each range spans from the directive opening the branch to the one closing it.
+    pub fn condition_ranges(&self) -> &[TextRange] {
+        &self.condition_ranges
+    }
 }
@@ -1,10 +1,13 @@
 use ide::WideEncoding;
 use lsp_types::{
-    CallHierarchyOptions, CallHierarchyServerCapability, ClientCapabilities, CompletionOptions,
-    CompletionOptionsCompletionItem, DocumentSymbolOptions, HoverProviderCapability, MarkupKind,
-    OneOf, PositionEncodingKind, ReferencesOptions, RenameOptions, SemanticTokensFullOptions,
+    CallHierarchyOptions, CallHierarchyServerCapability, ClientCapabilities, CodeActionKind,
+    CodeActionOptions, CodeActionProviderCapability, CompletionOptions,
+    CompletionOptionsCompletionItem, DocumentSymbolOptions, FoldingRangeProviderCapability,
+    HoverProviderCapability, ImplementationProviderCapability, MarkupKind, OneOf,
+    PositionEncodingKind, ReferencesOptions, RenameOptions, SemanticTokensFullOptions,
     SemanticTokensLegend, SemanticTokensOptions, ServerCapabilities, SignatureHelpOptions,
-    TextDocumentSyncCapability, TextDocumentSyncKind, WorkDoneProgressOptions,
+    TextDocumentSyncCapability, TextDocumentSyncKind, TypeDefinitionProviderCapability,
+    WorkDoneProgressOptions,
 };
 
 use crate::{
@@ -89,6 +92,26 @@ pub fn server_capabilities(config: &Config) -> ServerCapabilities {
                 },
             },
         )),
+        workspace_symbol_provider: Some(OneOf::Left(true)),
+        inlay_hint_provider: Some(OneOf::Left(true)),
+        document_formatting_provider: Some(OneOf::Left(true)),
+        document_range_formatting_provider: Some(OneOf::Left(true)),
+        folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
+        document_highlight_provider: Some(OneOf::Left(true)),
+        type_definition_provider: Some(TypeDefinitionProviderCapability::Simple(true)),
+        implementation_provider: Some(ImplementationProviderCapability::Simple(true)),
+        code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
+            code_action_kinds: Some(vec![
+                CodeActionKind::EMPTY,
+                CodeActionKind::QUICKFIX,
+                CodeActionKind::REFACTOR,
+                CodeActionKind::REFACTOR_EXTRACT,
+                CodeActionKind::REFACTOR_INLINE,
+                CodeActionKind::REFACTOR_REWRITE,
+            ]),
+            resolve_provider: Some(true),
+            work_done_progress_options: Default::default(),
+        })),
         ..Default::default()
     }
 }
@@ -108,6 +131,8 @@ pub trait ClientCapabilitiesExt {
     fn has_push_configuration_support(&self) -> bool;
 
     fn has_file_watching_support(&self) -> bool;
+
+    fn has_relative_pattern_file_watching_support(&self) -> bool;
 }
 
 impl ClientCapabilitiesExt for ClientCapabilities {
@@ -159,6 +184,14 @@ impl ClientCapabilitiesExt for ClientCapabilities {
             .and_then(|cap| cap.dynamic_registration)
             == Some(true)
     }
+
+    fn has_relative_pattern_file_watching_support(&self) -> bool {
+        self.workspace
+            .as_ref()
+            .and_then(|cap| cap.did_change_watched_files)
+            .and_then(|cap| cap.relative_pattern_support)
+            == Some(true)
+    }
 }
 
 fn completion_item(config: &Config) -> Option<CompletionOptionsCompletionItem> {
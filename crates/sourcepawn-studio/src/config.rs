@@ -4,17 +4,25 @@
 //! We currently get this config from `initialize` LSP request, which is not the
 //! best way to do it, but was the simplest thing we could implement.
 
-use ide::{DiagnosticsConfig, HoverConfig, HoverDocFormat};
+use fxhash::FxHashSet;
+use ide::{
+    BraceStyle, DiagnosticsConfig, FormattingConfig, HighlightConfig, HoverConfig, HoverDocFormat,
+    IndentStyle, InlayHintsConfig,
+};
 use itertools::Itertools;
 use lsp_types::{ClientCapabilities, MarkupKind};
-use paths::AbsPathBuf;
-use serde::de::DeserializeOwned;
+use paths::{AbsPath, AbsPathBuf};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::iter;
-use std::{collections::HashSet, fmt, path::PathBuf};
+use std::{fmt, path::PathBuf};
 
 use crate::lsp;
 use crate::{line_index::PositionEncoding, lsp::ext::negotiated_encoding};
 
+use self::editorconfig::EditorConfig;
+
+mod editorconfig;
+
 macro_rules! try_ {
     ($expr:expr) => {
         || -> _ { Some($expr) }()
@@ -54,10 +62,26 @@ config_data! {
         /// Path to the SourcePawn compiler (spcomp).
         compiler_path: Option<String> = "null",
 
+        /// List of the native diagnostics to disable, e.g. `unused-include`.
+        diagnostics_disabled: FxHashSet<String> = "[]",
+
         /// Name of the game we want the events for, as it appears on the Alliedmodders website.
         /// For example, "Counter-Strike: Global Offensive" or "Team Fortress 2".
         eventsGameName: Option<String> = "null",
 
+        /// Names of the functions whose last `char[]` parameter is a format string, in addition to the
+        /// ones declared with a `const char[] format, any ...` signature.
+        /// Their format strings are checked against their arguments, and their specifiers are highlighted.
+        formatFunctions: FxHashSet<String> = "[]",
+
+        /// Where to put the opening brace of blocks, methodmaps, enums, etc. when formatting.
+        formatting_braceStyle: BraceStyleDef = "\"nextLine\"",
+        /// Read the indentation settings of the formatter from `.editorconfig` files.
+        /// These settings take precedence over the ones of the editor.
+        formatting_editorconfig_enable: bool = "true",
+        /// Maximum number of consecutive empty lines to keep when formatting.
+        formatting_maxBlankLines: usize = "1",
+
         /// Whether to show `Debug` action. Only applies when
         /// `#SourcePawnLanguageServer.hover.actions.enable#` is set.
         hover_actions_debug_enable: bool           = "true",
@@ -79,6 +103,11 @@ config_data! {
         /// Include directories paths for the compiler and the linter.
         includeDirectories: Vec<PathBuf> = "[]",
 
+        /// Whether to show the names of the parameters at the call sites.
+        inlayHints_parameterHints_enable: bool = "true",
+        /// Only show the parameter name hints of the arguments which are literals, e.g. `100` or `"m_iHealth"`.
+        inlayHints_parameterHints_literalsOnly: bool = "false",
+
         /// Disable the language server's syntax linter. This is independant from spcomp.
         linter_disable: bool = "false",
         /// How many worker threads in the main loop. The default `null` means to pick automatically.
@@ -198,7 +227,8 @@ impl Config {
         DiagnosticsConfig {
             enabled: true,
             disable_experimental: false,
-            disabled: HashSet::default(),
+            disabled: self.data.diagnostics_disabled.clone(),
+            format_functions: self.data.formatFunctions.clone(),
         }
     }
 
@@ -293,10 +323,36 @@ impl Config {
         .is_some()
     }
 
+    pub fn code_action_resolve(&self) -> bool {
+        try_or_def!(self
+            .caps
+            .text_document
+            .as_ref()?
+            .code_action
+            .as_ref()?
+            .resolve_support
+            .as_ref()?
+            .properties
+            .as_slice())
+        .iter()
+        .any(|it| it == "edit")
+    }
+
     pub fn location_link(&self) -> bool {
         try_or_def!(self.caps.text_document.as_ref()?.definition?.link_support?)
     }
 
+    pub fn line_folding_only(&self) -> bool {
+        try_or_def!(
+            self.caps
+                .text_document
+                .as_ref()?
+                .folding_range
+                .as_ref()?
+                .line_folding_only?
+        )
+    }
+
     #[allow(unused)]
     pub fn semantics_tokens_augments_syntax_tokens(&self) -> bool {
         try_!(
@@ -330,6 +386,60 @@ impl Config {
         self.data.compiler_onSave
     }
 
+    pub fn formatting(
+        &self,
+        options: &lsp_types::FormattingOptions,
+        path: Option<&AbsPath>,
+    ) -> FormattingConfig {
+        let mut insert_spaces = options.insert_spaces;
+        let mut indent_size = options.tab_size;
+        let mut insert_final_newline = options.insert_final_newline.unwrap_or_default();
+        if let Some(path) = path.filter(|_| self.data.formatting_editorconfig_enable) {
+            let editorconfig = EditorConfig::for_file(path.as_ref());
+            if let Some(style) = editorconfig.indent_style {
+                insert_spaces = style == editorconfig::IndentStyle::Space;
+            }
+            match editorconfig.indent_size {
+                Some(editorconfig::IndentSize::Columns(size)) => indent_size = size,
+                Some(editorconfig::IndentSize::Tab) | None => {
+                    if let Some(width) = editorconfig.tab_width {
+                        indent_size = width;
+                    }
+                }
+            }
+            if let Some(insert) = editorconfig.insert_final_newline {
+                insert_final_newline = insert;
+            }
+        }
+
+        FormattingConfig {
+            indent: if insert_spaces {
+                IndentStyle::Spaces(indent_size)
+            } else {
+                IndentStyle::Tabs
+            },
+            brace_style: match self.data.formatting_braceStyle {
+                BraceStyleDef::NextLine => BraceStyle::NextLine,
+                BraceStyleDef::SameLine => BraceStyle::SameLine,
+            },
+            max_blank_lines: self.data.formatting_maxBlankLines,
+            insert_final_newline,
+        }
+    }
+
+    pub fn highlighting_config(&self) -> HighlightConfig {
+        HighlightConfig {
+            format_functions: self.data.formatFunctions.clone(),
+        }
+    }
+
+    pub fn inlay_hints(&self) -> InlayHintsConfig {
+        InlayHintsConfig {
+            parameter_hints: self.data.inlayHints_parameterHints_enable,
+            parameter_hints_literals_only: self.data.inlayHints_parameterHints_literalsOnly,
+        }
+    }
+
     pub fn hover(&self) -> HoverConfig {
         HoverConfig {
             // TODO: Impl these configs
@@ -362,6 +472,13 @@ impl Config {
 
 type ParallelCachePrimingNumThreads = u8;
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum BraceStyleDef {
+    NextLine,
+    SameLine,
+}
+
 pub struct ClientCommandsConfig {
     // pub run_single: bool,
     // pub debug_single: bool,
@@ -572,6 +689,14 @@ fn field_props(field: &str, ty: &str, doc: &[&str], default: &str) -> serde_json
             "type": ["null", "array"],
             "items": { "type": "string" },
         },
+        "BraceStyleDef" => set! {
+            "type": "string",
+            "enum": ["nextLine", "sameLine"],
+            "enumDescriptions": [
+                "Put opening braces on their own line.",
+                "Put opening braces on the same line as the declaration or statement."
+            ],
+        },
         "ParallelCachePrimingNumThreads" => set! {
             "type": "number",
             "minimum": 0,
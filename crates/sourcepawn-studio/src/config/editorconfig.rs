@@ -0,0 +1,215 @@
+//! Minimal support for [EditorConfig](https://editorconfig.org) files.
+//!
+//! Only the properties which are relevant to the formatter are read.
+
+use std::path::Path;
+
+use regex::Regex;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct EditorConfig {
+    pub(crate) indent_style: Option<IndentStyle>,
+    pub(crate) indent_size: Option<IndentSize>,
+    pub(crate) tab_width: Option<u32>,
+    pub(crate) insert_final_newline: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum IndentStyle {
+    Tab,
+    Space,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum IndentSize {
+    Tab,
+    Columns(u32),
+}
+
+impl EditorConfig {
+    /// Collect the properties that apply to the file at `path`.
+    ///
+    /// The `.editorconfig` files of the parent directories are read up to the first one
+    /// marked with `root = true`, and the closest files take precedence.
+    pub(crate) fn for_file(path: &Path) -> Self {
+        let mut files = Vec::new();
+        for dir in path.ancestors().skip(1) {
+            let Ok(text) = std::fs::read_to_string(dir.join(".editorconfig")) else {
+                continue;
+            };
+            let is_root = is_root(&text);
+            files.push((dir, text));
+            if is_root {
+                break;
+            }
+        }
+
+        let mut config = EditorConfig::default();
+        for (dir, text) in files.iter().rev() {
+            let Ok(relative_path) = path.strip_prefix(dir) else {
+                continue;
+            };
+            let relative_path = relative_path.to_string_lossy().replace('\\', "/");
+            config.apply(text, &relative_path);
+        }
+
+        config
+    }
+
+    fn apply(&mut self, text: &str, relative_path: &str) {
+        let mut matches = false;
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with(['#', ';']) {
+                continue;
+            }
+            if let Some(section) = line.strip_prefix('[').and_then(|it| it.strip_suffix(']')) {
+                matches = section_matches(section, relative_path);
+                continue;
+            }
+            if !matches {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim().to_lowercase();
+            match key.trim().to_lowercase().as_str() {
+                "indent_style" => {
+                    self.indent_style = match value.as_str() {
+                        "tab" => Some(IndentStyle::Tab),
+                        "space" => Some(IndentStyle::Space),
+                        _ => None,
+                    }
+                }
+                "indent_size" => {
+                    self.indent_size = match value.as_str() {
+                        "tab" => Some(IndentSize::Tab),
+                        _ => value.parse().ok().map(IndentSize::Columns),
+                    }
+                }
+                "tab_width" => self.tab_width = value.parse().ok(),
+                "insert_final_newline" => self.insert_final_newline = value.parse().ok(),
+                _ => (),
+            }
+        }
+    }
+}
+
+fn is_root(text: &str) -> bool {
+    text.lines()
+        .map(str::trim)
+        .take_while(|line| !line.starts_with('['))
+        .filter_map(|line| line.split_once('='))
+        .any(|(key, value)| {
+            key.trim().eq_ignore_ascii_case("root") && value.trim().eq_ignore_ascii_case("true")
+        })
+}
+
+/// Whether the glob of a section header matches the path, relative to the `.editorconfig` file.
+fn section_matches(glob: &str, relative_path: &str) -> bool {
+    let glob = match glob.strip_prefix('/') {
+        Some(glob) => glob.to_string(),
+        None if glob.contains('/') => glob.to_string(),
+        None => format!("**/{glob}"),
+    };
+    Regex::new(&glob_to_regex(&glob)).is_ok_and(|re| re.is_match(relative_path))
+}
+
+fn glob_to_regex(glob: &str) -> String {
+    let mut re = String::from("^");
+    let mut chars = glob.chars().peekable();
+    let mut in_braces = 0;
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    // `**/` also matches the current directory.
+                    chars.next();
+                    re.push_str("(?:.*/)?");
+                } else {
+                    re.push_str(".*");
+                }
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            '[' => {
+                let mut class = String::new();
+                for c in chars.by_ref() {
+                    if c == ']' {
+                        break;
+                    }
+                    class.push(c);
+                }
+                let class = class
+                    .strip_prefix('!')
+                    .map_or(class.clone(), |it| format!("^{it}"));
+                re.push_str(&format!("[{}]", class.replace('\\', "\\\\")));
+            }
+            '{' => {
+                in_braces += 1;
+                re.push_str("(?:");
+            }
+            '}' if in_braces > 0 => {
+                in_braces -= 1;
+                re.push(')');
+            }
+            ',' if in_braces > 0 => re.push('|'),
+            '\\' => {
+                if let Some(c) = chars.next() {
+                    re.push_str(&regex::escape(&c.to_string()));
+                }
+            }
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    re.push('$');
+
+    re
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn section_globs() {
+        assert!(section_matches("*", "plugin.sp"));
+        assert!(section_matches("*", "scripting/plugin.sp"));
+        assert!(section_matches("*.{sp,inc}", "include/plugin.inc"));
+        assert!(!section_matches("*.{sp,inc}", "plugin.txt"));
+        assert!(section_matches("scripting/*.sp", "scripting/plugin.sp"));
+        assert!(!section_matches("/*.sp", "scripting/plugin.sp"));
+        assert!(section_matches("**.sp", "a/b/plugin.sp"));
+        assert!(section_matches("plugin.[!i]p", "plugin.sp"));
+    }
+
+    #[test]
+    fn closest_section_wins() {
+        let mut config = EditorConfig::default();
+        config.apply(
+            r#"
+root = true
+
+[*]
+indent_style = space
+indent_size = 2
+insert_final_newline = true
+
+[*.sp]
+indent_style = tab
+indent_size = tab
+"#,
+            "plugin.sp",
+        );
+        assert_eq!(
+            config,
+            EditorConfig {
+                indent_style: Some(IndentStyle::Tab),
+                indent_size: Some(IndentSize::Tab),
+                tab_width: None,
+                insert_final_newline: Some(true),
+            }
+        );
+    }
+}
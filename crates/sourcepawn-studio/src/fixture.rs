@@ -3,13 +3,16 @@ use crossbeam::channel::Receiver;
 use itertools::Itertools;
 use lsp_server::{Connection, Response};
 use lsp_types::{
-    notification::{DidOpenTextDocument, Exit, Initialized},
+    notification::{DidChangeTextDocument, DidOpenTextDocument, Exit, Initialized},
     request::{Completion, Initialize, ResolveCompletionItem, Shutdown},
     ClientCapabilities, CompletionContext, CompletionItem, CompletionItemKind, CompletionParams,
-    CompletionResponse, CompletionTriggerKind, DidOpenTextDocumentParams, InitializeParams,
-    InitializedParams, Location, LocationLink, Position, Range, SignatureHelp,
-    TextDocumentIdentifier, TextDocumentItem, TextDocumentPositionParams, Url, WorkspaceFolder,
+    CompletionResponse, CompletionTriggerKind, DidChangeTextDocumentParams,
+    DidOpenTextDocumentParams, InitializeParams, InitializedParams, Location, LocationLink,
+    Position, Range, SignatureHelp, TextDocumentContentChangeEvent, TextDocumentIdentifier,
+    TextDocumentItem, TextDocumentPositionParams, Url, VersionedTextDocumentIdentifier,
+    WorkspaceFolder,
 };
+use serde::Serialize;
 use std::{
     env,
     fs::File,
@@ -23,13 +26,14 @@ use std::{
 use tempfile::{tempdir, TempDir};
 use zip::ZipArchive;
 
-use crate::{config::ConfigData, lsp};
+use crate::{config::ConfigData, line_index::PositionEncoding, lsp};
 
 use super::{GlobalState, LspClient};
 
 #[derive(Debug)]
 pub enum InternalMessage {
     OptionsRequested,
+    PublishDiagnostics(lsp_types::PublishDiagnosticsParams),
 }
 
 #[derive(Debug)]
@@ -57,19 +61,19 @@ impl Fixture {
         Self { documents }
     }
 
-    pub fn write_files(&self, dir: &Path) {
+    pub fn write_files(&self, dir: &Path, include_dir: &Path) {
         for document in &self.documents {
             let text = String::from(&document.text);
-            let path = dir.join(&document.path);
+            let path = document.absolute_path(dir, include_dir);
             std::fs::create_dir_all(path.parent().unwrap()).unwrap();
             std::fs::write(&path, &text).unwrap();
         }
     }
 
-    pub fn setup(&self, client: &LspClient, dir: &Path) {
+    pub fn setup(&self, client: &LspClient, dir: &Path, include_dir: &Path) {
         for document in &self.documents {
             let text = String::from(&document.text);
-            let path = dir.join(&document.path);
+            let path = document.absolute_path(dir, include_dir);
 
             let uri = Url::from_file_path(&path).unwrap();
             let language_id = "sourcepawn".to_string();
@@ -137,6 +141,19 @@ impl Document {
             ranges,
         }
     }
+
+    /// Returns the path of the document, which is in the include directory if its path starts
+    /// with `$include/`, and in the workspace otherwise.
+    pub fn absolute_path(&self, dir: &Path, include_dir: &Path) -> PathBuf {
+        match self.path.strip_prefix("$include") {
+            Ok(path) => include_dir.join(path),
+            Err(_) => dir.join(&self.path),
+        }
+    }
+
+    fn is_in_include_dir(&self) -> bool {
+        self.path.starts_with("$include")
+    }
 }
 
 static LOGGER: Once = Once::new();
@@ -147,7 +164,7 @@ pub struct TestBed {
     locations: Vec<Location>,
     _temp_dir: TempDir,
     temp_dir_path: PathBuf,
-    #[allow(unused)]
+    _temp_sm_dir: TempDir,
     temp_sm_dir_path: PathBuf,
     pub internal_rx: Receiver<InternalMessage>,
     client: LspClient,
@@ -185,12 +202,18 @@ impl TestBed {
         let temp_sm_dir = tempdir()?;
         let temp_sm_dir_path = dunce::canonicalize(temp_sm_dir.path())?;
         let temp_sm_dir_path_ = temp_sm_dir_path.clone(); // Copy the value to be able to move it into the closure
+        let include_dir = temp_sm_dir_path.join("include");
+        let has_include_dir_documents = fixture
+            .documents
+            .iter()
+            .any(|document| document.is_in_include_dir());
 
         let locations: Vec<Location> = fixture
             .documents
             .iter()
             .flat_map(|document| {
-                let uri = Url::from_file_path(temp_dir_path.join(&document.path)).unwrap();
+                let uri = Url::from_file_path(document.absolute_path(&temp_dir_path, &include_dir))
+                    .unwrap();
                 document
                     .ranges
                     .iter()
@@ -207,6 +230,7 @@ impl TestBed {
             std::thread::spawn(move || GlobalState::new(server_conn, false).run().unwrap());
         let client_thread = {
             let client = client.clone();
+            let include_dir = include_dir.clone();
             std::thread::spawn(move || {
                 let destination = temp_sm_dir_path_;
                 for message in &client_conn.receiver {
@@ -229,6 +253,11 @@ impl TestBed {
                                         .includeDirectories
                                         .push(destination.clone().join("include/"));
                                 }
+                                let mut config = serde_json::to_value(config).unwrap();
+                                if has_include_dir_documents {
+                                    config["includeDirectories"] =
+                                        serde_json::json!([include_dir.clone()]);
+                                }
                                 client
                                     .send_response(Response::new_ok(request.id, vec![config]))
                                     .unwrap();
@@ -247,18 +276,25 @@ impl TestBed {
                         lsp_server::Message::Response(response) => {
                             client.recv_response(response).unwrap();
                         }
-                        lsp_server::Message::Notification(_) => {}
+                        lsp_server::Message::Notification(notification) => {
+                            if notification.method == "textDocument/publishDiagnostics" {
+                                let params = serde_json::from_value(notification.params).unwrap();
+                                let _ =
+                                    internal_tx.send(InternalMessage::PublishDiagnostics(params));
+                            }
+                        }
                     }
                 }
             })
         };
-        fixture.write_files(&temp_dir_path);
+        fixture.write_files(&temp_dir_path, &include_dir);
 
         Ok(TestBed {
             fixture,
             locations,
             _temp_dir: temp_dir,
             temp_dir_path,
+            _temp_sm_dir: temp_sm_dir,
             temp_sm_dir_path,
             client,
             internal_rx,
@@ -283,7 +319,22 @@ impl TestBed {
             .send_notification::<Initialized>(InitializedParams {})
             .unwrap();
 
-        self.fixture.setup(&self.client, self.directory());
+        self.fixture
+            .setup(&self.client, self.directory(), &self.include_directory());
+
+        if self
+            .fixture
+            .documents
+            .iter()
+            .any(|document| document.is_in_include_dir())
+        {
+            // Wait for the include directory to be configured.
+            while let Ok(message) = self.internal_rx.recv_timeout(Duration::from_secs(10)) {
+                if matches!(message, InternalMessage::OptionsRequested) {
+                    break;
+                }
+            }
+        }
 
         Ok(())
     }
@@ -299,7 +350,10 @@ impl TestBed {
             .iter()
             .find_map(|document| document.cursor.map(|cursor| (document, cursor)))?;
 
-        let uri = Url::from_file_path(self.temp_dir_path.join(&document.path)).unwrap();
+        let uri = Url::from_file_path(
+            document.absolute_path(&self.temp_dir_path, &self.include_directory()),
+        )
+        .unwrap();
         let id = TextDocumentIdentifier::new(uri);
         Some(TextDocumentPositionParams::new(id, cursor))
     }
@@ -313,6 +367,11 @@ impl TestBed {
         &self.temp_dir_path
     }
 
+    /// Include directory of the server, in which the `$include/` documents are written.
+    pub fn include_directory(&self) -> PathBuf {
+        self.temp_sm_dir_path.join("include")
+    }
+
     #[allow(unused)]
     pub fn documents(&self) -> &[Document] {
         &self.fixture.documents
@@ -472,6 +531,178 @@ pub fn signature_help(fixture: &str) -> SignatureHelp {
         .unwrap()
 }
 
+/// Format the first document of the fixture and return its formatted text.
+///
+/// If the document has a range, only that range is formatted.
+pub fn formatting(fixture: &str) -> String {
+    let test_bed = TestBed::new(fixture, false).unwrap();
+    test_bed
+        .initialize(
+            serde_json::from_value(serde_json::json!({
+                "workspace": {
+                    "configuration": true,
+                    "workspace_folders": true
+                }
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+    let document = &test_bed.documents()[0];
+    let text_document = TextDocumentIdentifier::new(
+        Url::from_file_path(test_bed.directory().join(&document.path)).unwrap(),
+    );
+    let options = lsp_types::FormattingOptions {
+        tab_size: 4,
+        insert_spaces: true,
+        insert_final_newline: Some(true),
+        ..Default::default()
+    };
+    let edits = match document.ranges.first() {
+        Some(range) => test_bed
+            .client()
+            .send_request::<lsp_types::request::RangeFormatting>(
+                lsp_types::DocumentRangeFormattingParams {
+                    text_document,
+                    range: *range,
+                    options,
+                    work_done_progress_params: Default::default(),
+                },
+            )
+            .unwrap(),
+        None => test_bed
+            .client()
+            .send_request::<lsp_types::request::Formatting>(lsp_types::DocumentFormattingParams {
+                text_document,
+                options,
+                work_done_progress_params: Default::default(),
+            })
+            .unwrap(),
+    };
+
+    // The edits are applied sequentially, so start from the end of the document.
+    let changes = edits
+        .unwrap_or_default()
+        .into_iter()
+        .sorted_by_key(|edit| edit.range.start)
+        .rev()
+        .map(|edit| lsp_types::TextDocumentContentChangeEvent {
+            range: Some(edit.range),
+            range_length: None,
+            text: edit.new_text,
+        })
+        .collect_vec();
+    lsp::utils::apply_document_changes(
+        PositionEncoding::Wide(ide::WideEncoding::Utf16),
+        || document.text.clone(),
+        changes,
+        &mut Vec::new(),
+    )
+}
+
+pub fn inlay_hints(fixture: &str) -> Vec<lsp_types::InlayHint> {
+    let test_bed = TestBed::new(fixture, false).unwrap();
+    test_bed
+        .initialize(
+            serde_json::from_value(serde_json::json!({
+                "textDocument": {
+                    "inlayHint": {}
+                },
+                "workspace": {
+                    "configuration": true,
+                    "workspace_folders": true
+                }
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+    let document = &test_bed.documents()[0];
+    let text_document = TextDocumentIdentifier::new(
+        Url::from_file_path(test_bed.directory().join(&document.path)).unwrap(),
+    );
+    let range = document.ranges.first().copied().unwrap_or_else(|| {
+        let last_line = document.text.lines().count().saturating_sub(1);
+        let last_line_len = document.text.lines().last().unwrap_or_default().len();
+        Range::new(
+            Position::new(0, 0),
+            Position::new(last_line as u32, last_line_len as u32),
+        )
+    });
+    let params = lsp_types::InlayHintParams {
+        text_document,
+        range,
+        work_done_progress_params: Default::default(),
+    };
+
+    test_bed
+        .client()
+        .send_request::<lsp_types::request::InlayHintRequest>(params)
+        .unwrap()
+        .unwrap_or_default()
+}
+
+pub fn workspace_symbol(fixture: &str, query: &str) -> Vec<lsp_types::SymbolInformation> {
+    let test_bed = TestBed::new(fixture, false).unwrap();
+    test_bed
+        .initialize(
+            serde_json::from_value(serde_json::json!({
+                "workspace": {
+                    "configuration": true,
+                    "workspace_folders": true
+                }
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+    let params = lsp_types::WorkspaceSymbolParams {
+        query: query.to_string(),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let mut symbols = match test_bed
+        .client()
+        .send_request::<lsp_types::request::WorkspaceSymbolRequest>(params)
+        .unwrap()
+    {
+        Some(lsp_types::WorkspaceSymbolResponse::Flat(symbols)) => symbols,
+        _ => panic!("Expected a flat workspace symbol response."),
+    };
+    for symbol in symbols.iter_mut() {
+        test_bed.anonymize_uri(&mut symbol.location.uri);
+    }
+
+    symbols
+}
+
+pub fn dead_code(fixture: &str) -> Vec<lsp::ext::ProjectDeadCode> {
+    let test_bed = TestBed::new(fixture, false).unwrap();
+    test_bed
+        .initialize(
+            serde_json::from_value(serde_json::json!({
+                "workspace": {
+                    "configuration": true,
+                    "workspace_folders": true
+                }
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+    let params = lsp::ext::DeadCodeParams {
+        text_document: None,
+    };
+    let mut projects = test_bed
+        .client()
+        .send_request::<lsp::ext::DeadCode>(params)
+        .unwrap();
+    for project in projects.iter_mut() {
+        test_bed.anonymize_uri(&mut project.uri);
+        for item in project.items.iter_mut() {
+            test_bed.anonymize_uri(&mut item.location.uri);
+        }
+    }
+
+    projects
+}
+
 pub fn unzip_file(zip_file_path: &Path, destination: &Path) -> Result<(), io::Error> {
     let file = File::open(zip_file_path)?;
     let mut archive = ZipArchive::new(file)?;
@@ -551,3 +782,532 @@ pub fn hover(fixture: &str) -> lsp::ext::Hover {
 
     res
 }
+
+pub fn folding_range(fixture: &str) -> Vec<lsp_types::FoldingRange> {
+    let test_bed = TestBed::new(fixture, false).unwrap();
+    test_bed
+        .initialize(
+            serde_json::from_value(serde_json::json!({
+                "textDocument": {
+                    "foldingRange": {
+                        "lineFoldingOnly": true
+                    }
+                },
+                "workspace": {
+                    "configuration": true,
+                    "workspace_folders": true
+                }
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+    let document = &test_bed.documents()[0];
+    let params = lsp_types::FoldingRangeParams {
+        text_document: TextDocumentIdentifier::new(
+            Url::from_file_path(test_bed.directory().join(&document.path)).unwrap(),
+        ),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+
+    test_bed
+        .client()
+        .send_request::<lsp_types::request::FoldingRangeRequest>(params)
+        .unwrap()
+        .unwrap_or_default()
+}
+
+/// A semantic token of the first document, with its absolute range.
+#[derive(Debug, Serialize)]
+pub struct SemanticToken {
+    pub range: Range,
+    pub token_type: String,
+}
+
+/// Returns the semantic tokens of the first document, decoded from their relative positions.
+pub fn semantic_tokens(fixture: &str) -> Vec<SemanticToken> {
+    let test_bed = TestBed::new(fixture, false).unwrap();
+    test_bed
+        .initialize(
+            serde_json::from_value(serde_json::json!({
+                "textDocument": {
+                    "semanticTokens": {
+                        "requests": {
+                            "full": true
+                        },
+                        "tokenTypes": [],
+                        "tokenModifiers": [],
+                        "formats": ["relative"]
+                    }
+                },
+                "workspace": {
+                    "configuration": true,
+                    "workspace_folders": true
+                }
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+    let document = &test_bed.documents()[0];
+    let params = lsp_types::SemanticTokensParams {
+        text_document: TextDocumentIdentifier::new(
+            Url::from_file_path(test_bed.directory().join(&document.path)).unwrap(),
+        ),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+
+    let Some(lsp_types::SemanticTokensResult::Tokens(tokens)) = test_bed
+        .client()
+        .send_request::<lsp_types::request::SemanticTokensFullRequest>(params)
+        .unwrap()
+    else {
+        return Vec::new();
+    };
+    let (mut line, mut character) = (0, 0);
+    tokens
+        .data
+        .into_iter()
+        .map(|token| {
+            if token.delta_line > 0 {
+                character = 0;
+            }
+            line += token.delta_line;
+            character += token.delta_start;
+            SemanticToken {
+                range: Range::new(
+                    Position::new(line, character),
+                    Position::new(line, character + token.length),
+                ),
+                token_type: lsp::semantic_tokens::SUPPORTED_TYPES[token.token_type as usize]
+                    .as_str()
+                    .to_string(),
+            }
+        })
+        .collect()
+}
+
+pub fn document_highlight(fixture: &str) -> Vec<lsp_types::DocumentHighlight> {
+    let test_bed = TestBed::new(fixture, false).unwrap();
+    test_bed
+        .initialize(
+            serde_json::from_value(serde_json::json!({
+                "textDocument": {
+                    "documentHighlight": {}
+                },
+                "workspace": {
+                    "configuration": true,
+                    "workspace_folders": true
+                }
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+    let params = lsp_types::DocumentHighlightParams {
+        text_document_position_params: test_bed.cursor().unwrap(),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+
+    test_bed
+        .client()
+        .send_request::<lsp_types::request::DocumentHighlightRequest>(params)
+        .unwrap()
+        .unwrap_or_default()
+}
+
+pub fn goto_type_definition(fixture: &str) -> Vec<LocationLink> {
+    let test_bed = TestBed::new(fixture, false).unwrap();
+    test_bed
+        .initialize(
+            serde_json::from_value(serde_json::json!({
+                "textDocument": {
+                    "definition": {
+                        "linkSupport": true
+                    }
+                },
+                "workspace": {
+                    "configuration": true,
+                    "workspace_folders": true
+                }
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+    let params = lsp_types::request::GotoTypeDefinitionParams {
+        text_document_position_params: test_bed.cursor().unwrap(),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let mut locations = match test_bed
+        .client()
+        .send_request::<lsp_types::request::GotoTypeDefinition>(params)
+        .unwrap()
+    {
+        Some(lsp_types::GotoDefinitionResponse::Link(locations)) => locations,
+        // An empty list of links is deserialized as an empty list of locations.
+        Some(lsp_types::GotoDefinitionResponse::Array(locations)) if locations.is_empty() => {
+            Vec::new()
+        }
+        None => Vec::new(),
+        _ => unreachable!("Expected a link response."),
+    };
+
+    locations.iter_mut().for_each(|location| {
+        test_bed.anonymize_uri(&mut location.target_uri);
+    });
+
+    locations
+}
+
+pub fn goto_implementation(fixture: &str) -> Vec<LocationLink> {
+    let test_bed = TestBed::new(fixture, false).unwrap();
+    test_bed
+        .initialize(
+            serde_json::from_value(serde_json::json!({
+                "textDocument": {
+                    "definition": {
+                        "linkSupport": true
+                    }
+                },
+                "workspace": {
+                    "configuration": true,
+                    "workspace_folders": true
+                }
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+    let params = lsp_types::request::GotoImplementationParams {
+        text_document_position_params: test_bed.cursor().unwrap(),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let mut locations = match test_bed
+        .client()
+        .send_request::<lsp_types::request::GotoImplementation>(params)
+        .unwrap()
+    {
+        Some(lsp_types::GotoDefinitionResponse::Link(locations)) => locations,
+        // An empty list of links is deserialized as an empty list of locations.
+        Some(lsp_types::GotoDefinitionResponse::Array(locations)) if locations.is_empty() => {
+            Vec::new()
+        }
+        None => Vec::new(),
+        _ => unreachable!("Expected a link response."),
+    };
+
+    locations.iter_mut().for_each(|location| {
+        test_bed.anonymize_uri(&mut location.target_uri);
+    });
+    locations.sort_by(|a, b| {
+        (a.target_uri.as_str(), a.target_range.start.line)
+            .cmp(&(b.target_uri.as_str(), b.target_range.start.line))
+    });
+
+    locations
+}
+
+/// Applies each batch of changes to the first document in its own `textDocument/didChange`
+/// notification, and goes to the definition at the cursor after each of them.
+///
+/// Returns the definitions found after the last batch of changes.
+pub fn goto_definition_after_changes(
+    fixture: &str,
+    changes: &[Vec<(Range, &str)>],
+) -> Vec<LocationLink> {
+    let test_bed = TestBed::new(fixture, false).unwrap();
+    test_bed
+        .initialize(
+            serde_json::from_value(serde_json::json!({
+                "textDocument": {
+                    "definition": {
+                        "linkSupport": true
+                    }
+                },
+                "workspace": {
+                    "configuration": true,
+                    "workspace_folders": true
+                }
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+    let uri =
+        Url::from_file_path(test_bed.directory().join(&test_bed.documents()[0].path)).unwrap();
+    let mut locations = Vec::new();
+    for (version, batch) in changes.iter().enumerate() {
+        test_bed
+            .client()
+            .send_notification::<DidChangeTextDocument>(DidChangeTextDocumentParams {
+                text_document: VersionedTextDocumentIdentifier::new(
+                    uri.clone(),
+                    version as i32 + 1,
+                ),
+                content_changes: batch
+                    .iter()
+                    .map(|(range, text)| TextDocumentContentChangeEvent {
+                        range: Some(*range),
+                        range_length: None,
+                        text: text.to_string(),
+                    })
+                    .collect(),
+            })
+            .unwrap();
+        let params = lsp_types::GotoDefinitionParams {
+            text_document_position_params: test_bed.cursor().unwrap(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        locations = match test_bed
+            .client()
+            .send_request::<lsp_types::request::GotoDefinition>(params)
+            .unwrap()
+        {
+            Some(lsp_types::GotoDefinitionResponse::Link(locations)) => locations,
+            // An empty list of links is deserialized as an empty list of locations.
+            Some(lsp_types::GotoDefinitionResponse::Array(locations)) if locations.is_empty() => {
+                Vec::new()
+            }
+            None => Vec::new(),
+            _ => unreachable!("Expected a link response."),
+        };
+    }
+
+    locations.iter_mut().for_each(|location| {
+        test_bed.anonymize_uri(&mut location.target_uri);
+    });
+
+    locations
+}
+
+/// Applies each batch of file changes on disk and notifies them with a
+/// `workspace/didChangeWatchedFiles` notification. A `None` content deletes the file or the
+/// directory.
+///
+/// As the files are loaded asynchronously, the definition at the cursor is requested until it
+/// changes after each batch. Returns the definitions found after the last batch.
+pub fn goto_definition_after_file_changes(
+    fixture: &str,
+    changes: &[Vec<(&str, Option<&str>)>],
+) -> Vec<LocationLink> {
+    let test_bed = TestBed::new(fixture, false).unwrap();
+    test_bed
+        .initialize(
+            serde_json::from_value(serde_json::json!({
+                "textDocument": {
+                    "definition": {
+                        "linkSupport": true
+                    }
+                },
+                "workspace": {
+                    "configuration": true,
+                    "workspace_folders": true,
+                    "didChangeWatchedFiles": {
+                        "dynamicRegistration": true
+                    }
+                }
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+    let goto_definition = || {
+        let params = lsp_types::GotoDefinitionParams {
+            text_document_position_params: test_bed.cursor().unwrap(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        match test_bed
+            .client()
+            .send_request::<lsp_types::request::GotoDefinition>(params)
+            .unwrap()
+        {
+            Some(lsp_types::GotoDefinitionResponse::Link(locations)) => locations,
+            // An empty list of links is deserialized as an empty list of locations.
+            Some(lsp_types::GotoDefinitionResponse::Array(locations)) if locations.is_empty() => {
+                Vec::new()
+            }
+            None => Vec::new(),
+            _ => unreachable!("Expected a link response."),
+        }
+    };
+
+    let mut locations = goto_definition();
+    for batch in changes {
+        let events = batch
+            .iter()
+            .map(|(path, contents)| {
+                let path = test_bed.directory().join(path);
+                let typ = match contents {
+                    Some(contents) => {
+                        let typ = if path.exists() {
+                            lsp_types::FileChangeType::CHANGED
+                        } else {
+                            lsp_types::FileChangeType::CREATED
+                        };
+                        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                        std::fs::write(&path, contents).unwrap();
+                        typ
+                    }
+                    None => {
+                        if path.is_dir() {
+                            std::fs::remove_dir_all(&path).unwrap();
+                        } else {
+                            std::fs::remove_file(&path).unwrap();
+                        }
+                        lsp_types::FileChangeType::DELETED
+                    }
+                };
+                lsp_types::FileEvent::new(Url::from_file_path(path).unwrap(), typ)
+            })
+            .collect();
+        test_bed
+            .client()
+            .send_notification::<lsp_types::notification::DidChangeWatchedFiles>(
+                lsp_types::DidChangeWatchedFilesParams { changes: events },
+            )
+            .unwrap();
+
+        let previous = locations.clone();
+        for _ in 0..200 {
+            locations = goto_definition();
+            if locations != previous {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+
+    locations.iter_mut().for_each(|location| {
+        test_bed.anonymize_uri(&mut location.target_uri);
+    });
+
+    locations
+}
+
+/// Returns the diagnostics published for the first document of the fixture.
+///
+/// The diagnostics are published again as the files are loaded, so they are collected until no
+/// new diagnostics are published for a while.
+pub fn diagnostics(fixture: &str) -> Vec<lsp_types::Diagnostic> {
+    diagnostics_without(fixture, &[])
+}
+
+/// Returns the diagnostics published for the first document of the fixture, except for the ones
+/// whose code is in `codes`.
+pub fn diagnostics_without(fixture: &str, codes: &[&str]) -> Vec<lsp_types::Diagnostic> {
+    let test_bed = TestBed::new(fixture, false).unwrap();
+    test_bed
+        .initialize(
+            serde_json::from_value(serde_json::json!({
+                "textDocument": {
+                    "publishDiagnostics": {}
+                },
+                "workspace": {
+                    "configuration": true,
+                    "workspace_folders": true
+                }
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+    let uri =
+        Url::from_file_path(test_bed.directory().join(&test_bed.documents()[0].path)).unwrap();
+
+    let mut diagnostics = None;
+    let mut timeout = Duration::from_secs(10);
+    while let Ok(message) = test_bed.internal_rx.recv_timeout(timeout) {
+        if let InternalMessage::PublishDiagnostics(params) = message {
+            if params.uri == uri {
+                diagnostics = Some(params.diagnostics);
+                timeout = Duration::from_millis(500);
+            }
+        }
+    }
+
+    let mut diagnostics = diagnostics.expect("No diagnostics were published.");
+    diagnostics.retain(|diagnostic| {
+        !matches!(
+            &diagnostic.code,
+            Some(lsp_types::NumberOrString::String(code)) if codes.contains(&code.as_str())
+        )
+    });
+
+    diagnostics
+}
+
+/// Returns the code actions at the cursor, with their edits resolved.
+///
+/// If the fixture has ranges, the actions are requested for the selection going from the start
+/// of the first range to the end of the last one.
+pub fn code_action(fixture: &str) -> Vec<lsp_types::CodeAction> {
+    let test_bed = TestBed::new(fixture, false).unwrap();
+    test_bed
+        .initialize(
+            serde_json::from_value(serde_json::json!({
+                "textDocument": {
+                    "codeAction": {
+                        "resolveSupport": {
+                            "properties": ["edit"]
+                        }
+                    }
+                },
+                "workspace": {
+                    "configuration": true,
+                    "workspace_folders": true
+                }
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+    let (text_document, range) = match (test_bed.locations().first(), test_bed.locations().last()) {
+        (Some(first), Some(last)) => (
+            TextDocumentIdentifier::new(first.uri.clone()),
+            Range::new(first.range.start, last.range.end),
+        ),
+        _ => {
+            let cursor = test_bed.cursor().unwrap();
+            (
+                cursor.text_document,
+                Range::new(cursor.position, cursor.position),
+            )
+        }
+    };
+    let params = lsp_types::CodeActionParams {
+        text_document,
+        range,
+        context: Default::default(),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+
+    test_bed
+        .client()
+        .send_request::<lsp_types::request::CodeActionRequest>(params)
+        .unwrap()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|action| {
+            let lsp_types::CodeActionOrCommand::CodeAction(mut action) = action else {
+                unreachable!("Expected a code action.")
+            };
+            if action.edit.is_none() {
+                action = test_bed
+                    .client()
+                    .send_request::<lsp_types::request::CodeActionResolveRequest>(action)
+                    .unwrap();
+                action.data = None;
+            }
+            if let Some(changes) = action.edit.as_mut().and_then(|it| it.changes.as_mut()) {
+                *changes = changes
+                    .drain()
+                    .map(|(mut uri, edits)| {
+                        test_bed.anonymize_uri(&mut uri);
+                        (uri, edits)
+                    })
+                    .collect();
+            }
+            action
+        })
+        .collect()
+}
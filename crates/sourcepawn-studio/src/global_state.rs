@@ -1,4 +1,4 @@
-use base_db::{Change, FileExtension, SourceRootConfig};
+use base_db::{Change, ContentChange, FileExtension, SourceRootConfig};
 use crossbeam::channel::{unbounded, Receiver, Sender};
 use flycheck::FlycheckHandle;
 use fxhash::FxHashMap;
@@ -13,7 +13,7 @@ use parking_lot::{
     RwLockWriteGuard,
 };
 use serde::Serialize;
-use std::{sync::Arc, time::Instant};
+use std::{mem, sync::Arc, time::Instant};
 use tempfile::TempDir;
 use threadpool::ThreadPool;
 use vfs::{FileId, Vfs};
@@ -84,6 +84,8 @@ pub struct GlobalState {
     pub(crate) vfs_progress_config_version: u32,
     pub(crate) vfs_progress_n_total: usize,
     pub(crate) vfs_progress_n_done: usize,
+    /// Changes of the open documents which have not been applied to the database yet.
+    pub(crate) content_changes: FxHashMap<FileId, Vec<ContentChange>>,
 
     // op queues
     pub(crate) prime_caches_queue: OpQueue,
@@ -141,6 +143,7 @@ impl GlobalState {
             vfs_progress_config_version: 0,
             vfs_progress_n_total: 0,
             vfs_progress_n_done: 0,
+            content_changes: FxHashMap::default(),
 
             prime_caches_queue: Default::default(),
         }
@@ -236,6 +239,7 @@ impl GlobalState {
 
     pub(crate) fn process_changes(&mut self) -> bool {
         let mut file_changes = FxHashMap::default();
+        let mut content_changes = mem::take(&mut self.content_changes);
         let (change, _changed_files) = {
             let mut change = Change::new();
             let mut guard = self.vfs.write();
@@ -338,6 +342,9 @@ impl GlobalState {
                 None => change.change_file(file_id, None),
                 Some((text, line_endings)) => {
                     line_endings_map.insert(file_id, line_endings);
+                    if let Some(changes) = content_changes.remove(&file_id) {
+                        change.set_content_changes(file_id, changes);
+                    }
                     change.change_file(file_id, Some(text));
                 }
             });
@@ -1,10 +1,11 @@
 use std::panic::AssertUnwindSafe;
 
+use base_db::FileExtension;
 use itertools::Itertools;
 use lsp_types::{
     DidChangeConfigurationParams, DidChangeTextDocumentParams, DidChangeWatchedFilesParams,
     DidCloseTextDocumentParams, DidOpenTextDocumentParams, DidSaveTextDocumentParams,
-    WorkDoneProgressCancelParams,
+    FileChangeType, WorkDoneProgressCancelParams,
 };
 use salsa::Cancelled;
 use vfs::{FileId, VfsPath};
@@ -38,16 +39,17 @@ pub(crate) fn handle_did_change_text_document(
             }
         };
 
+        let file_id = state.vfs.read().0.file_id(&path).unwrap();
         let text = apply_document_changes(
             state.config.position_encoding(),
             || {
                 let vfs = &state.vfs.read();
-                let file_id = vfs.0.file_id(&path).unwrap();
                 std::str::from_utf8(vfs.0.file_contents(file_id))
                     .unwrap()
                     .into()
             },
             params.content_changes,
+            state.content_changes.entry(file_id).or_default(),
         );
         state
             .vfs
@@ -140,7 +142,28 @@ pub(crate) fn handle_did_change_watched_files(
     params: DidChangeWatchedFilesParams,
 ) -> anyhow::Result<()> {
     for change in params.changes {
-        if let Ok(path) = from_proto::abs_path(&change.uri) {
+        let Ok(path) = from_proto::abs_path(&change.uri) else {
+            continue;
+        };
+        if change.typ == FileChangeType::DELETED {
+            // The files of a deleted directory are not notified individually.
+            let deleted_files = state
+                .vfs
+                .read()
+                .0
+                .iter()
+                .filter_map(|(_, vfs_path)| vfs_path.as_path().map(|it| it.to_path_buf()))
+                .filter(|file_path| file_path != &path && file_path.starts_with(&path))
+                .collect_vec();
+            for file_path in deleted_files {
+                state.loader.handle.invalidate(file_path);
+            }
+        }
+        let is_source_file = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| FileExtension::try_from(ext).is_ok());
+        if is_source_file {
             state.loader.handle.invalidate(path);
         }
     }
@@ -2,7 +2,10 @@ use std::panic::AssertUnwindSafe;
 
 use anyhow::{bail, Context};
 use base_db::FileRange;
-use ide::{CompletionKind, HoverAction, HoverGotoTypeData};
+use ide::{
+    AssistKind, AssistResolveStrategy, CompletionKind, HoverAction, HoverGotoTypeData, Query,
+    SingleResolve,
+};
 use ide_db::SymbolKind;
 use lsp_types::{
     CallHierarchyIncomingCall, CallHierarchyIncomingCallsParams, CallHierarchyItem,
@@ -19,8 +22,9 @@ use crate::{
     lsp::{
         self,
         ext::{
-            AnalyzerStatusParams, ItemTreeParams, PreprocessedDocumentParams,
-            ProjectMainPathParams, ProjectsGraphvizParams, SyntaxTreeParams,
+            AnalyzerStatusParams, DeadCodeParams, ItemTreeParams, OrganizeIncludesParams,
+            PreprocessedDocumentParams, ProjectMainPathParams, ProjectsGraphvizParams,
+            SyntaxTreeParams,
         },
         from_proto, to_proto,
     },
@@ -71,8 +75,11 @@ pub(crate) fn handle_completion(
                 .into_iter()
                 .map(|item| {
                     let kind = item.kind;
+                    let adds_include = !item.additional_text_edits.is_empty();
                     let mut c_item = to_proto::completion_item(&line_index, item);
                     match kind {
+                        // Rank the symbols which are not included yet last.
+                        _ if adds_include => c_item.sort_text = Some(format!("~{}", c_item.label)),
                         CompletionKind::SymbolKind(SymbolKind::Local)
                         | CompletionKind::SymbolKind(SymbolKind::Global) => {
                             c_item.sort_text = Some("0".to_string())
@@ -114,6 +121,123 @@ pub(crate) fn handle_goto_definition(
     )?))
 }
 
+pub(crate) fn handle_goto_type_definition(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::request::GotoTypeDefinitionParams,
+) -> anyhow::Result<Option<lsp_types::request::GotoTypeDefinitionResponse>> {
+    let pos = from_proto::file_position(&snap, params.text_document_position_params.clone())?;
+
+    let targets = match snap.analysis.goto_type_definition(pos)? {
+        None => return Ok(None),
+        Some(it) => it,
+    };
+    let src = FileRange {
+        file_id: pos.file_id,
+        range: targets.range,
+    };
+
+    Ok(Some(to_proto::goto_definition_response(
+        &snap,
+        Some(src),
+        targets.info,
+    )?))
+}
+
+pub(crate) fn handle_goto_implementation(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::request::GotoImplementationParams,
+) -> anyhow::Result<Option<lsp_types::request::GotoImplementationResponse>> {
+    let pos = from_proto::file_position(&snap, params.text_document_position_params.clone())?;
+
+    let targets = match snap.analysis.goto_implementation(pos)? {
+        None => return Ok(None),
+        Some(it) => it,
+    };
+    let src = FileRange {
+        file_id: pos.file_id,
+        range: targets.range,
+    };
+
+    Ok(Some(to_proto::goto_definition_response(
+        &snap,
+        Some(src),
+        targets.info,
+    )?))
+}
+
+pub(crate) fn handle_code_action(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::CodeActionParams,
+) -> anyhow::Result<Option<Vec<lsp_types::CodeActionOrCommand>>> {
+    let frange = from_proto::file_range(&snap, &params.text_document, params.range)?;
+    let resolve = if snap.config.code_action_resolve() {
+        AssistResolveStrategy::None
+    } else {
+        AssistResolveStrategy::All
+    };
+    let assists = snap
+        .analysis
+        .assists_with_fixes(&snap.config.diagnostics(), resolve, frange)?;
+
+    let res = assists
+        .into_iter()
+        .enumerate()
+        .filter(|(_, assist)| is_requested_kind(&params, assist.id.1))
+        .map(|(index, assist)| {
+            to_proto::code_action(&snap, assist, Some((index, params.clone()))).into()
+        })
+        .collect();
+
+    Ok(Some(res))
+}
+
+pub(crate) fn handle_code_action_resolve(
+    snap: GlobalStateSnapshot,
+    mut code_action: lsp_types::CodeAction,
+) -> anyhow::Result<lsp_types::CodeAction> {
+    let Some(data) = code_action.data.take() else {
+        bail!("no data attached to the code action");
+    };
+    let data: lsp::ext::CodeActionData = serde_json::from_value(data)?;
+    let params = data.code_action_params;
+    let frange = from_proto::file_range(&snap, &params.text_document, params.range)?;
+
+    let (assist_id, assist_kind, index) = match data.id.split(':').collect::<Vec<_>>()[..] {
+        [assist_id, assist_kind, index] => (assist_id, assist_kind, index),
+        _ => bail!("invalid code action id: {}", data.id),
+    };
+    let assist_kind: AssistKind = assist_kind.parse().map_err(anyhow::Error::msg)?;
+    let index: usize = index.parse()?;
+    let resolve = AssistResolveStrategy::Single(SingleResolve {
+        assist_id: assist_id.to_string(),
+        assist_kind,
+    });
+    let assists = snap
+        .analysis
+        .assists_with_fixes(&snap.config.diagnostics(), resolve, frange)?;
+
+    let Some(assist) = assists
+        .into_iter()
+        .nth(index)
+        .filter(|it| it.id.0 == assist_id && it.id.1 == assist_kind)
+    else {
+        bail!("mismatching assist at index {index} for {assist_id}, the document may have changed");
+    };
+    code_action.edit = to_proto::code_action(&snap, assist, None).edit;
+
+    Ok(code_action)
+}
+
+/// Whether the assist is of a kind requested by the client.
+fn is_requested_kind(params: &lsp_types::CodeActionParams, kind: AssistKind) -> bool {
+    let Some(only) = &params.context.only else {
+        return true;
+    };
+    let kind = to_proto::code_action_kind(kind);
+    only.iter()
+        .any(|it| it.as_str().is_empty() || kind.as_str().starts_with(it.as_str()))
+}
+
 pub(crate) fn handle_references(
     snap: GlobalStateSnapshot,
     params: lsp_types::ReferenceParams,
@@ -128,6 +252,26 @@ pub(crate) fn handle_references(
     Ok(Some(to_proto::references_response(&snap, franges)?))
 }
 
+pub(crate) fn handle_document_highlight(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::DocumentHighlightParams,
+) -> anyhow::Result<Option<Vec<lsp_types::DocumentHighlight>>> {
+    let pos = from_proto::file_position(&snap, params.text_document_position_params)?;
+    let line_index = snap.file_line_index(pos.file_id)?;
+
+    let highlights = match snap.analysis.highlight_related(pos)? {
+        None => return Ok(None),
+        Some(it) => it,
+    };
+
+    Ok(Some(
+        highlights
+            .into_iter()
+            .filter_map(|highlight| to_proto::document_highlight(&line_index, highlight))
+            .collect(),
+    ))
+}
+
 pub(crate) fn handle_rename(
     snap: GlobalStateSnapshot,
     params: lsp_types::RenameParams,
@@ -159,6 +303,94 @@ pub(crate) fn handle_symbol(
     )))
 }
 
+pub(crate) fn handle_workspace_symbol(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::WorkspaceSymbolParams,
+) -> anyhow::Result<Option<lsp_types::WorkspaceSymbolResponse>> {
+    let symbols = snap.analysis.symbol_search(Query::new(&params.query))?;
+
+    let mut res = Vec::new();
+    for symbol in symbols {
+        if let Some(symbol) = to_proto::workspace_symbol(&snap, symbol)? {
+            res.push(symbol);
+        }
+    }
+
+    Ok(Some(lsp_types::WorkspaceSymbolResponse::Flat(res)))
+}
+
+pub(crate) fn handle_inlay_hints(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::InlayHintParams,
+) -> anyhow::Result<Option<Vec<lsp_types::InlayHint>>> {
+    let FileRange { file_id, range } =
+        from_proto::file_range(&snap, &params.text_document, params.range)?;
+    let line_index = snap.file_line_index(file_id)?;
+
+    let hints = snap
+        .analysis
+        .inlay_hints(&snap.config.inlay_hints(), file_id, Some(range))?;
+
+    Ok(Some(
+        hints
+            .into_iter()
+            .filter_map(|hint| to_proto::inlay_hint(&line_index, hint))
+            .collect(),
+    ))
+}
+
+pub(crate) fn handle_folding_range(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::FoldingRangeParams,
+) -> anyhow::Result<Option<Vec<lsp_types::FoldingRange>>> {
+    let file_id = from_proto::file_id(&snap, &params.text_document.uri)?;
+    let folds = snap.analysis.folding_ranges(file_id)?;
+    let text = snap.analysis.file_text(file_id)?;
+    let line_index = snap.file_line_index(file_id)?;
+    let line_folding_only = snap.config.line_folding_only();
+
+    Ok(Some(
+        folds
+            .into_iter()
+            .filter_map(|fold| to_proto::folding_range(&text, &line_index, line_folding_only, fold))
+            .collect(),
+    ))
+}
+
+pub(crate) fn handle_formatting(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::DocumentFormattingParams,
+) -> anyhow::Result<Option<Vec<lsp_types::TextEdit>>> {
+    let file_id = from_proto::file_id(&snap, &params.text_document.uri)?;
+    let line_index = snap.file_line_index(file_id)?;
+    let path = from_proto::abs_path(&params.text_document.uri).ok();
+    let config = snap.config.formatting(&params.options, path.as_deref());
+
+    let edits = match snap.analysis.format(file_id, &config)? {
+        None => return Ok(None),
+        Some(it) => it,
+    };
+
+    Ok(Some(to_proto::text_edit_vec(&line_index, edits)))
+}
+
+pub(crate) fn handle_range_formatting(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::DocumentRangeFormattingParams,
+) -> anyhow::Result<Option<Vec<lsp_types::TextEdit>>> {
+    let frange = from_proto::file_range(&snap, &params.text_document, params.range)?;
+    let line_index = snap.file_line_index(frange.file_id)?;
+    let path = from_proto::abs_path(&params.text_document.uri).ok();
+    let config = snap.config.formatting(&params.options, path.as_deref());
+
+    let edits = match snap.analysis.format_range(frange, &config)? {
+        None => return Ok(None),
+        Some(it) => it,
+    };
+
+    Ok(Some(to_proto::text_edit_vec(&line_index, edits)))
+}
+
 pub(crate) fn handle_hover(
     snap: GlobalStateSnapshot,
     params: lsp_types::HoverParams,
@@ -269,7 +501,9 @@ pub(crate) fn handle_semantic_tokens_full(
 
     let text = snap.analysis.file_text(file_id)?;
 
-    let highlights = snap.analysis.highlight(file_id)?;
+    let highlights = snap
+        .analysis
+        .highlight(&snap.config.highlighting_config(), file_id)?;
     let semantic_tokens = to_proto::semantic_tokens(&text, &line_index, highlights);
 
     // Unconditionally cache the tokens
@@ -288,7 +522,9 @@ pub(crate) fn handle_semantic_tokens_full_delta(
     let line_index = snap.file_line_index(file_id)?;
     let text = snap.analysis.file_text(file_id)?;
 
-    let highlights = snap.analysis.highlight(file_id)?;
+    let highlights = snap
+        .analysis
+        .highlight(&snap.config.highlighting_config(), file_id)?;
 
     let semantic_tokens = to_proto::semantic_tokens(&text, &line_index, highlights);
 
@@ -330,7 +566,9 @@ pub(crate) fn handle_semantic_tokens_range(
     let line_index = snap.file_line_index(frange.file_id)?;
     let text = snap.analysis.file_text(frange.file_id)?;
 
-    let highlights = snap.analysis.highlight_range(frange)?;
+    let highlights = snap
+        .analysis
+        .highlight_range(&snap.config.highlighting_config(), frange)?;
     let semantic_tokens = to_proto::semantic_tokens(&text, &line_index, highlights);
 
     Ok(Some(semantic_tokens.into()))
@@ -435,6 +673,53 @@ pub(crate) fn handle_preprocessed_document(
         .map(|it| it.to_string())
 }
 
+pub(crate) fn handle_organize_includes(
+    snap: GlobalStateSnapshot,
+    params: OrganizeIncludesParams,
+) -> anyhow::Result<Vec<lsp_types::TextEdit>> {
+    let uri = params
+        .text_document
+        .ok_or_else(|| anyhow::anyhow!("No uri received in request"))?
+        .uri;
+    let file_id = from_proto::file_id(&snap, &uri)?;
+    let line_index = snap.file_line_index(file_id)?;
+    let edits = snap.analysis.organize_includes(file_id)?;
+
+    Ok(to_proto::text_edit_vec(&line_index, edits))
+}
+
+pub(crate) fn handle_dead_code(
+    snap: GlobalStateSnapshot,
+    params: DeadCodeParams,
+) -> anyhow::Result<Vec<lsp::ext::ProjectDeadCode>> {
+    let file_id = params
+        .text_document
+        .map(|it| from_proto::file_id(&snap, &it.uri))
+        .transpose()?;
+    let projects = snap.analysis.dead_code(file_id)?;
+
+    projects
+        .into_iter()
+        .map(|project| {
+            let mut items = Vec::new();
+            for item in project.items {
+                let Some(location) = to_proto::location(&snap, item.frange)? else {
+                    continue;
+                };
+                items.push(lsp::ext::DeadCodeItem {
+                    name: item.name,
+                    kind: to_proto::symbol_kind(item.kind),
+                    location,
+                });
+            }
+            Ok(lsp::ext::ProjectDeadCode {
+                uri: to_proto::url(&snap, project.root),
+                items,
+            })
+        })
+        .collect()
+}
+
 pub(crate) fn handle_item_tree(
     snap: GlobalStateSnapshot,
     params: ItemTreeParams,
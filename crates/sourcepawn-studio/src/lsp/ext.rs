@@ -38,6 +38,51 @@ pub struct PreprocessedDocumentParams {
     pub text_document: Option<TextDocumentIdentifier>,
 }
 
+pub enum OrganizeIncludes {}
+
+impl Request for OrganizeIncludes {
+    type Params = OrganizeIncludesParams;
+    type Result = Vec<lsp_types::TextEdit>;
+    const METHOD: &'static str = "sourcepawn-studio/organizeIncludes";
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct OrganizeIncludesParams {
+    pub text_document: Option<TextDocumentIdentifier>,
+}
+
+pub enum DeadCode {}
+
+impl Request for DeadCode {
+    type Params = DeadCodeParams;
+    type Result = Vec<ProjectDeadCode>;
+    const METHOD: &'static str = "sourcepawn-studio/deadCode";
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DeadCodeParams {
+    /// Only list the projects including this document, if any.
+    pub text_document: Option<TextDocumentIdentifier>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectDeadCode {
+    /// Main file of the project.
+    pub uri: Url,
+    pub items: Vec<DeadCodeItem>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DeadCodeItem {
+    pub name: String,
+    pub kind: lsp_types::SymbolKind,
+    pub location: lsp_types::Location,
+}
+
 pub enum SyntaxTree {}
 
 impl Request for SyntaxTree {
@@ -177,3 +222,11 @@ pub struct CommandLink {
 pub struct ClientCommandOptions {
     pub commands: Vec<String>,
 }
+
+/// Data attached to a code action, to resolve its edit lazily.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CodeActionData {
+    pub code_action_params: lsp_types::CodeActionParams,
+    /// `{assist_id}:{assist_kind}:{index}` of the assist.
+    pub id: String,
+}
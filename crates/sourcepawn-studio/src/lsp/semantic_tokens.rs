@@ -66,6 +66,7 @@ define_semantic_token_types![
     custom {
         (BOOLEAN, "boolean"),
         (CHAR, "char"),
+        (FORMAT_SPECIFIER, "formatSpecifier"),
         (GENERIC, "generic") => TYPE_PARAMETER,
     }
 ];
@@ -5,8 +5,9 @@ use std::{
 
 use base_db::FileRange;
 use ide::{
-    Cancellable, CompletionKind, Highlight, HlMod, HlRange, HlTag, Markup, NavigationTarget,
-    Severity, SignatureHelp,
+    Assist, AssistKind, Cancellable, CompletionKind, FileSymbol, Fold, FoldKind, Highlight,
+    HighlightedRange, HlMod, HlRange, HlTag, Markup, NavigationTarget, ReferenceCategory, Severity,
+    SignatureHelp,
 };
 use ide_db::{
     CallItem, IncomingCallItem, OutgoingCallItem, SourceChange, SymbolId, SymbolKind, Symbols,
@@ -17,7 +18,7 @@ use paths::AbsPath;
 use rowan::{TextRange, TextSize};
 use vfs::FileId;
 
-use crate::{global_state::GlobalStateSnapshot, line_index::LineIndex};
+use crate::{global_state::GlobalStateSnapshot, line_index::LineIndex, lsp};
 
 use super::semantic_tokens;
 
@@ -181,6 +182,7 @@ fn semantic_token_type_and_modifiers(
         HlTag::CharLiteral => semantic_tokens::CHAR,
         HlTag::FloatLiteral | HlTag::IntLiteral => semantic_tokens::NUMBER,
         HlTag::Comment => semantic_tokens::COMMENT,
+        HlTag::FormatSpecifier => semantic_tokens::FORMAT_SPECIFIER,
         HlTag::None => return None,
     };
 
@@ -249,6 +251,91 @@ pub(crate) fn location(
     Ok(Some(lsp_types::Location::new(url, range)))
 }
 
+pub(crate) fn inlay_hint(
+    line_index: &LineIndex,
+    hint: ide::InlayHint,
+) -> Option<lsp_types::InlayHint> {
+    lsp_types::InlayHint {
+        position: line_index.try_position(hint.range.start())?,
+        label: lsp_types::InlayHintLabel::String(format!("{}:", hint.label)),
+        kind: match hint.kind {
+            ide::InlayKind::Parameter => Some(lsp_types::InlayHintKind::PARAMETER),
+        },
+        text_edits: None,
+        tooltip: None,
+        padding_left: None,
+        padding_right: Some(true),
+        data: None,
+    }
+    .into()
+}
+
+pub(crate) fn document_highlight(
+    line_index: &LineIndex,
+    highlight: HighlightedRange,
+) -> Option<lsp_types::DocumentHighlight> {
+    lsp_types::DocumentHighlight {
+        range: line_index.try_range(highlight.range)?,
+        kind: match highlight.category {
+            ReferenceCategory::Read => Some(lsp_types::DocumentHighlightKind::READ),
+            ReferenceCategory::Write => Some(lsp_types::DocumentHighlightKind::WRITE),
+        },
+    }
+    .into()
+}
+
+pub(crate) fn folding_range(
+    text: &str,
+    line_index: &LineIndex,
+    line_folding_only: bool,
+    fold: Fold,
+) -> Option<lsp_types::FoldingRange> {
+    let kind = match fold.kind {
+        FoldKind::Comment => Some(lsp_types::FoldingRangeKind::Comment),
+        FoldKind::Condition | FoldKind::Region => Some(lsp_types::FoldingRangeKind::Region),
+        FoldKind::Block => None,
+    };
+    let range = line_index.try_range(fold.range)?;
+    if line_folding_only {
+        // Clients which only fold whole lines fold the end line of the range, even if it has
+        // text after the end of the fold, like the closing brace of a block or the next
+        // directive of a condition. Keep that line visible.
+        let has_more_text_on_end_line = text
+            .get(usize::from(fold.range.end())..)?
+            .chars()
+            .take_while(|it| *it != '\n')
+            .any(|it| !it.is_whitespace());
+        let end_line = if has_more_text_on_end_line {
+            range.end.line.saturating_sub(1)
+        } else {
+            range.end.line
+        };
+        if end_line <= range.start.line {
+            return None;
+        }
+        Some(lsp_types::FoldingRange {
+            start_line: range.start.line,
+            start_character: None,
+            end_line,
+            end_character: None,
+            kind,
+            collapsed_text: None,
+        })
+    } else {
+        if range.end.line <= range.start.line {
+            return None;
+        }
+        Some(lsp_types::FoldingRange {
+            start_line: range.start.line,
+            start_character: Some(range.start.character),
+            end_line: range.end.line,
+            end_character: Some(range.end.character),
+            kind,
+            collapsed_text: None,
+        })
+    }
+}
+
 pub(crate) fn completion_item(
     line_index: &LineIndex,
     item: ide::CompletionItem,
@@ -269,6 +356,18 @@ pub(crate) fn completion_item(
             let range = line_index.range(range);
             lsp_types::CompletionTextEdit::Edit(TextEdit::new(range, new_text))
         }),
+        additional_text_edits: (!item.additional_text_edits.is_empty()).then(|| {
+            item.additional_text_edits
+                .into_iter()
+                .map(|(range, new_text)| TextEdit::new(line_index.range(range), new_text))
+                .collect()
+        }),
+        label_details: item.label_description.map(|description| {
+            lsp_types::CompletionItemLabelDetails {
+                detail: None,
+                description: Some(description),
+            }
+        }),
         deprecated: item.deprecated.into(),
         tags: if item.deprecated {
             Some(vec![lsp_types::CompletionItemTag::DEPRECATED])
@@ -339,6 +438,23 @@ pub(crate) fn signature_help(sig: SignatureHelp) -> lsp_types::SignatureHelp {
     }
 }
 
+pub(crate) fn text_edit(line_index: &LineIndex, edit: ide_db::TextEdit) -> lsp_types::TextEdit {
+    lsp_types::TextEdit::new(
+        line_index.range(*edit.range()),
+        edit.replacement_text().to_string(),
+    )
+}
+
+pub(crate) fn text_edit_vec(
+    line_index: &LineIndex,
+    edits: Vec<ide_db::TextEdit>,
+) -> Vec<lsp_types::TextEdit> {
+    edits
+        .into_iter()
+        .map(|edit| text_edit(line_index, edit))
+        .collect()
+}
+
 pub(crate) fn workspace_edit(
     snap: &GlobalStateSnapshot,
     source_change: SourceChange,
@@ -349,15 +465,7 @@ pub(crate) fn workspace_edit(
         .flat_map(|(file_id, edits)| {
             let line_index = snap.file_line_index(file_id).ok()?;
             let uri = url(snap, file_id);
-            let text_edits = edits
-                .into_iter()
-                .map(|edit| {
-                    lsp_types::TextEdit::new(
-                        line_index.range(*edit.range()),
-                        edit.replacement_text().to_string(),
-                    )
-                })
-                .collect();
+            let text_edits = text_edit_vec(&line_index, edits);
             Some((uri, text_edits))
         })
         .collect();
@@ -369,6 +477,49 @@ pub(crate) fn workspace_edit(
     }
 }
 
+pub(crate) fn code_action_kind(kind: AssistKind) -> lsp_types::CodeActionKind {
+    match kind {
+        AssistKind::QuickFix => lsp_types::CodeActionKind::QUICKFIX,
+        AssistKind::Generate => lsp_types::CodeActionKind::EMPTY,
+        AssistKind::Refactor => lsp_types::CodeActionKind::REFACTOR,
+        AssistKind::RefactorExtract => lsp_types::CodeActionKind::REFACTOR_EXTRACT,
+        AssistKind::RefactorInline => lsp_types::CodeActionKind::REFACTOR_INLINE,
+        AssistKind::RefactorRewrite => lsp_types::CodeActionKind::REFACTOR_REWRITE,
+    }
+}
+
+/// Converts an assist to a code action. Without a source change, `resolve_data` is attached to
+/// the code action so that its edit can be computed by a `codeAction/resolve` request.
+pub(crate) fn code_action(
+    snap: &GlobalStateSnapshot,
+    assist: Assist,
+    resolve_data: Option<(usize, lsp_types::CodeActionParams)>,
+) -> lsp_types::CodeAction {
+    let mut res = lsp_types::CodeAction {
+        title: assist.label,
+        kind: Some(code_action_kind(assist.id.1)),
+        is_preferred: (assist.id.1 == AssistKind::QuickFix).then_some(true),
+        ..Default::default()
+    };
+    match (assist.source_change, resolve_data) {
+        (Some(source_change), _) => res.edit = Some(workspace_edit(snap, source_change)),
+        (None, Some((index, code_action_params))) => {
+            res.data = Some(
+                serde_json::to_value(lsp::ext::CodeActionData {
+                    id: format!("{}:{}:{index}", assist.id.0, assist.id.1.name()),
+                    code_action_params,
+                })
+                .unwrap(),
+            );
+        }
+        (None, None) => {
+            tracing::error!("assist should always be resolved if client can't do lazy resolving")
+        }
+    };
+
+    res
+}
+
 pub(crate) fn document_symbols(
     _snap: &GlobalStateSnapshot,
     line_index: &LineIndex,
@@ -380,15 +531,42 @@ pub(crate) fn document_symbols(
         .collect_vec()
 }
 
-fn document_symbol(
-    idx: &SymbolId,
-    symbols: &Symbols,
-    line_index: &LineIndex,
-) -> Option<lsp_types::DocumentSymbol> {
+pub(crate) fn workspace_symbol(
+    snap: &GlobalStateSnapshot,
+    symbol: FileSymbol,
+) -> Cancellable<Option<lsp_types::SymbolInformation>> {
+    let range = symbol.focus_range.unwrap_or(symbol.full_range);
+    let Some(location) = location(
+        snap,
+        FileRange {
+            file_id: symbol.file_id,
+            range,
+        },
+    )?
+    else {
+        return Ok(None);
+    };
+    #[allow(deprecated)]
+    let symbol = lsp_types::SymbolInformation {
+        name: symbol.name.to_string(),
+        kind: symbol_kind(symbol.kind),
+        tags: if symbol.deprecated {
+            Some(vec![lsp_types::SymbolTag::DEPRECATED])
+        } else {
+            None
+        },
+        deprecated: None,
+        location,
+        container_name: symbol.container_name.map(|it| it.to_string()),
+    };
+
+    Ok(Some(symbol))
+}
+
+pub(crate) fn symbol_kind(kind: SymbolKind) -> lsp_types::SymbolKind {
     use lsp_types::SymbolKind as SK;
 
-    let symbol = &symbols[idx];
-    let kind = match symbol.kind() {
+    match kind {
         SymbolKind::Macro => SK::CONSTANT,
         SymbolKind::Function => SK::FUNCTION,
         SymbolKind::Native => SK::FUNCTION,
@@ -407,7 +585,16 @@ fn document_symbol(
         SymbolKind::Enum => SK::ENUM,
         SymbolKind::Variant => SK::ENUM_MEMBER,
         SymbolKind::Global | SymbolKind::Local => SK::VARIABLE,
-    };
+    }
+}
+
+fn document_symbol(
+    idx: &SymbolId,
+    symbols: &Symbols,
+    line_index: &LineIndex,
+) -> Option<lsp_types::DocumentSymbol> {
+    let symbol = &symbols[idx];
+    let kind = symbol_kind(symbol.kind());
     let full_range = line_index.try_range(symbol.full_range())?;
     #[allow(deprecated)]
     let symbol = lsp_types::DocumentSymbol {
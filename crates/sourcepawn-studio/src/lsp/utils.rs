@@ -1,6 +1,9 @@
 //! Utilities for LSP-related boilerplate code.
 use std::{mem, ops::Range, sync::Arc};
 
+use base_db::ContentChange;
+use rowan::TextSize;
+
 use crate::{
     line_index::{LineEndings, LineIndex, PositionEncoding},
     GlobalState,
@@ -53,10 +56,15 @@ impl GlobalState {
     }
 }
 
+/// Applies the content changes to the text of a document, and records them in `changes`.
+///
+/// A full document change clears the changes recorded so far, as they can not be used to
+/// reparse the new text incrementally.
 pub(crate) fn apply_document_changes(
     encoding: PositionEncoding,
     file_contents: impl FnOnce() -> String,
     mut content_changes: Vec<lsp_types::TextDocumentContentChangeEvent>,
+    changes: &mut Vec<ContentChange>,
 ) -> String {
     // Skip to the last full document change, as it invalidates all previous changes anyways.
     let mut start = content_changes
@@ -73,6 +81,7 @@ pub(crate) fn apply_document_changes(
         }) => {
             let text = mem::take(text);
             start += 1;
+            changes.clear();
 
             // The only change is a full document update
             if start == content_changes.len() {
@@ -108,6 +117,10 @@ pub(crate) fn apply_document_changes(
             index_valid = range.start.line;
             if let Ok(range) = from_proto::text_range(&line_index, range) {
                 text.replace_range(Range::<usize>::from(range), &change.text);
+                changes.push(ContentChange {
+                    range,
+                    new_len: TextSize::of(change.text.as_str()),
+                });
             }
         }
     }
@@ -414,6 +414,16 @@ impl GlobalState {
             .on::<lsp_request::References>(handlers::handle_references)
             .on::<lsp_request::Rename>(handlers::handle_rename)
             .on::<lsp_request::DocumentSymbolRequest>(handlers::handle_symbol)
+            .on::<lsp_request::WorkspaceSymbolRequest>(handlers::handle_workspace_symbol)
+            .on::<lsp_request::InlayHintRequest>(handlers::handle_inlay_hints)
+            .on::<lsp_request::Formatting>(handlers::handle_formatting)
+            .on::<lsp_request::RangeFormatting>(handlers::handle_range_formatting)
+            .on::<lsp_request::FoldingRangeRequest>(handlers::handle_folding_range)
+            .on::<lsp_request::DocumentHighlightRequest>(handlers::handle_document_highlight)
+            .on::<lsp_request::GotoTypeDefinition>(handlers::handle_goto_type_definition)
+            .on::<lsp_request::GotoImplementation>(handlers::handle_goto_implementation)
+            .on::<lsp_request::CodeActionRequest>(handlers::handle_code_action)
+            .on::<lsp_request::CodeActionResolveRequest>(handlers::handle_code_action_resolve)
             .on::<lsp_request::CallHierarchyPrepare>(handlers::handle_call_hierarchy_prepare)
             .on::<lsp_request::CallHierarchyIncomingCalls>(handlers::handle_call_hierarchy_incoming)
             .on::<lsp_request::CallHierarchyOutgoingCalls>(handlers::handle_call_hierarchy_outgoing)
@@ -421,6 +431,8 @@ impl GlobalState {
             .on::<lsp::ext::SyntaxTree>(handlers::handle_syntax_tree)
             .on::<lsp::ext::ProjectsGraphviz>(handlers::handle_projects_graphviz)
             .on::<lsp::ext::PreprocessedDocument>(handlers::handle_preprocessed_document)
+            .on::<lsp::ext::OrganizeIncludes>(handlers::handle_organize_includes)
+            .on::<lsp::ext::DeadCode>(handlers::handle_dead_code)
             .on::<lsp::ext::ItemTree>(handlers::handle_item_tree)
             .on::<lsp::ext::AnalyzerStatus>(handlers::handle_analyzer_status)
             .on::<lsp::ext::ProjectMainPath>(handlers::handle_project_main_path)
@@ -441,7 +453,7 @@ impl GlobalState {
         .on_sync_mut::<notifs::DidCloseTextDocument>(handlers::handle_did_close_text_document)?
         .on_sync_mut::<notifs::DidSaveTextDocument>(handlers::handle_did_save_text_document)?
         .on_sync_mut::<notifs::DidChangeConfiguration>(handlers::handle_did_change_configuration)?
-        .on_sync_mut::<notifs::DidChangeWatchedFiles>(handlers::handle_did_change_watched_files)? // TODO: Implement this.
+        .on_sync_mut::<notifs::DidChangeWatchedFiles>(handlers::handle_did_change_watched_files)?
         .on_sync_mut::<notifs::WorkDoneProgressCancel>(handlers::handle_work_done_progress_cancel)?
         .finish();
 
@@ -4,14 +4,17 @@ use std::{mem, vec};
 use flycheck::{FlycheckConfig, FlycheckHandle};
 use fxhash::FxHashMap;
 use itertools::Itertools;
+use lsp_types::notification::Notification;
 use paths::AbsPathBuf;
 use vfs::VfsPath;
 
 use crate::lsp;
-use crate::{config::Config, GlobalState};
+use crate::{capabilities::ClientCapabilitiesExt, config::Config, GlobalState};
 
 use stdx::format_to;
 
+const FILE_WATCHERS_REGISTRATION_ID: &str = "workspace/didChangeWatchedFiles";
+
 impl GlobalState {
     pub(crate) fn is_quiescent(&self) -> bool {
         !(self.last_reported_status.is_none()
@@ -54,7 +57,13 @@ impl GlobalState {
                 .into_iter()
                 .map(vfs::loader::Entry::sp_files_recursively)
                 .collect_vec();
-            let watch = (0..load.len()).collect_vec();
+            // Let the client watch the files if it can, as it is more reliable than `notify`.
+            let watch = if self.config.caps().has_file_watching_support() {
+                self.register_file_watchers(!initialization);
+                Vec::new()
+            } else {
+                (0..load.len()).collect_vec()
+            };
             // The root_path can be the FS' root. Do not scrape the whole FS in that case.
             if self.config.root_path().parent().is_some() {
                 load.push(vfs::loader::Entry::sp_files_recursively(
@@ -77,6 +86,71 @@ impl GlobalState {
         }
     }
 
+    /// Ask the client to notify the server of the changes made to the SourcePawn files of the
+    /// workspace and of the include directories outside of the editor.
+    fn register_file_watchers(&mut self, reregister: bool) {
+        let mut dirs = self.config.include_directories();
+        // The root_path can be the FS' root. Do not watch the whole FS in that case.
+        if self.config.root_path().parent().is_some() {
+            dirs.push(self.config.root_path().clone());
+        }
+        let relative_pattern_support = self
+            .config
+            .caps()
+            .has_relative_pattern_file_watching_support();
+        let glob_pattern = |dir: &AbsPathBuf, pattern: &str| {
+            if relative_pattern_support {
+                lsp_types::GlobPattern::Relative(lsp_types::RelativePattern {
+                    base_uri: lsp_types::OneOf::Right(lsp::to_proto::url_from_abs_path(dir)),
+                    pattern: pattern.to_string(),
+                })
+            } else {
+                // Glob patterns only use `/` as a separator, even on Windows.
+                let dir = dir.to_string().replace('\\', "/");
+                lsp_types::GlobPattern::String(format!("{}/{}", dir.trim_end_matches('/'), pattern))
+            }
+        };
+        let watchers = dirs
+            .iter()
+            .flat_map(|dir| {
+                [
+                    lsp_types::FileSystemWatcher {
+                        glob_pattern: glob_pattern(dir, "**/*.{sp,inc}"),
+                        kind: None,
+                    },
+                    // Deleting a directory only notifies of the deletion of the directory.
+                    lsp_types::FileSystemWatcher {
+                        glob_pattern: glob_pattern(dir, "**"),
+                        kind: Some(lsp_types::WatchKind::Delete),
+                    },
+                ]
+            })
+            .collect();
+
+        if reregister {
+            self.send_request::<lsp_types::request::UnregisterCapability>(
+                lsp_types::UnregistrationParams {
+                    unregisterations: vec![lsp_types::Unregistration {
+                        id: FILE_WATCHERS_REGISTRATION_ID.to_string(),
+                        method: lsp_types::notification::DidChangeWatchedFiles::METHOD.to_string(),
+                    }],
+                },
+                |_, _| (),
+            );
+        }
+        let registration_options = lsp_types::DidChangeWatchedFilesRegistrationOptions { watchers };
+        self.send_request::<lsp_types::request::RegisterCapability>(
+            lsp_types::RegistrationParams {
+                registrations: vec![lsp_types::Registration {
+                    id: FILE_WATCHERS_REGISTRATION_ID.to_string(),
+                    method: lsp_types::notification::DidChangeWatchedFiles::METHOD.to_string(),
+                    register_options: Some(serde_json::to_value(registration_options).unwrap()),
+                }],
+            },
+            |_, _| (),
+        );
+    }
+
     pub fn reload_flycheck(&mut self) {
         let analysis = self.analysis_host.analysis();
         let Some(compiler_path) = self.config.compiler_path() else {
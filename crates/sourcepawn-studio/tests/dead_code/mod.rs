@@ -0,0 +1,66 @@
+use insta::assert_json_snapshot;
+use sourcepawn_studio::fixture::dead_code;
+
+#[test]
+fn project_1() {
+    assert_json_snapshot!(dead_code(
+        r#"
+%! main.sp
+#include "include/utils.inc"
+
+public void OnPluginStart() {
+    int x = UsedHelper() + g_iUsed + Color_Red;
+}
+%! include/utils.inc
+#define UTILS_USED 1
+#define UTILS_UNUSED 2
+
+int g_iUsed = UTILS_USED;
+int g_iUnused;
+public int g_iPublic;
+
+enum Color {
+    Color_Red,
+    Color_Blue,
+}
+
+stock int UsedHelper() {
+    return 1;
+}
+
+stock int UnusedHelper() {
+    return 2;
+}
+"#,
+    ));
+}
+
+#[test]
+fn project_2() {
+    assert_json_snapshot!(dead_code(
+        r#"
+%! main.sp
+#include "include/utils.inc"
+
+void OnClientPutInServer(int client) {
+    CreateTimer(1.0, Timer_Callback);
+}
+
+void Timer_Callback() {
+#if defined DEBUG
+    LogHelper();
+#endif
+}
+
+forward void OnClientPutInServer(int client);
+native void CreateTimer(float interval, Function func);
+%! include/utils.inc
+#if defined UTILS_MAX
+#endinput
+#endif
+#define UTILS_MAX 1
+
+stock void LogHelper() {}
+"#,
+    ));
+}
@@ -0,0 +1,81 @@
+use insta::assert_json_snapshot;
+use sourcepawn_studio::fixture::goto_definition_after_file_changes;
+
+#[test]
+fn create_include_1() {
+    assert_json_snapshot!(goto_definition_after_file_changes(
+        r#"
+%! main.sp
+#include "foo"
+void bar() {
+    baz();
+     |
+}"#,
+        &[vec![("foo.inc", Some("void baz() {}"))]],
+    ));
+}
+
+#[test]
+fn create_include_2() {
+    assert_json_snapshot!(goto_definition_after_file_changes(
+        r#"
+%! main.sp
+#include "include/foo"
+void bar() {
+    baz();
+     |
+}"#,
+        &[vec![("include/foo.inc", Some("void baz() {}"))]],
+    ));
+}
+
+#[test]
+fn change_include_1() {
+    assert_json_snapshot!(goto_definition_after_file_changes(
+        r#"
+%! main.sp
+#include "foo"
+void bar() {
+    baz();
+     |
+}"#,
+        &[
+            vec![("foo.inc", Some("void baz() {}"))],
+            vec![("foo.inc", Some("int qux;\nvoid baz() {}"))],
+        ],
+    ));
+}
+
+#[test]
+fn delete_include_1() {
+    assert_json_snapshot!(goto_definition_after_file_changes(
+        r#"
+%! main.sp
+#include "foo"
+void bar() {
+    baz();
+     |
+}"#,
+        &[
+            vec![("foo.inc", Some("void baz() {}"))],
+            vec![("foo.inc", None)],
+        ],
+    ));
+}
+
+#[test]
+fn delete_directory_1() {
+    assert_json_snapshot!(goto_definition_after_file_changes(
+        r#"
+%! main.sp
+#include "include/foo"
+void bar() {
+    baz();
+     |
+}"#,
+        &[
+            vec![("include/foo.inc", Some("void baz() {}"))],
+            vec![("include", None)],
+        ],
+    ));
+}
@@ -1 +1,4 @@
+mod dead_code;
+mod did_change_watched_files;
 mod text_document;
+mod workspace_symbol;
@@ -0,0 +1,716 @@
+use insta::assert_json_snapshot;
+use sourcepawn_studio::fixture::code_action;
+
+#[test]
+fn unresolved_field_1() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+enum struct Player {
+    int health;
+    int armor;
+}
+
+void foo() {
+    Player player;
+    int a = player.helth;
+                    |
+}
+"#,
+    ));
+}
+
+#[test]
+fn unresolved_field_2() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+methodmap Player {
+    property int Health {
+        public get() { return 0; }
+    }
+}
+
+void foo(Player player) {
+    int a = player.health;
+                    |
+}
+"#,
+    ));
+}
+
+#[test]
+fn unresolved_method_call_1() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+methodmap Player {
+    public void Kill() {}
+    public void Kick() {}
+}
+
+void foo(Player player) {
+    player.Kil();
+             |
+}
+"#,
+    ));
+}
+
+#[test]
+fn unresolved_include_1() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+#include "Foo/bar"
+            |
+%! foo/Bar.inc
+int bar;
+"#,
+    ));
+}
+
+#[test]
+fn unresolved_include_2() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+#include <Foo.inc>
+            |
+%! include/foo.inc
+int bar;
+"#,
+    ));
+}
+
+#[test]
+fn incorrect_number_of_arguments_1() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+void foo(int client, int weapon, int slot = 0) {}
+
+void bar() {
+    foo(1);
+        |
+}
+"#,
+    ));
+}
+
+#[test]
+fn incorrect_number_of_arguments_2() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+void foo(int client, int weapon, int slot = 0) {}
+
+void bar() {
+    foo();
+       |
+}
+"#,
+    ));
+}
+
+#[test]
+fn unresolved_named_arg_1() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+void foo(int client = 0, int weapon = 0) {}
+
+void bar() {
+    foo(.clinet = 1);
+          |
+}
+"#,
+    ));
+}
+
+#[test]
+fn no_code_action_1() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+enum struct Player {
+    int health;
+}
+
+void foo() {
+    Player player;
+       |
+    int a = player.helth;
+}
+"#,
+    ));
+}
+
+#[test]
+fn convert_to_new_syntax_1() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+new Float:g_x, g_y[3], String:g_s[] = "a";
+      |
+"#,
+    ));
+}
+
+#[test]
+fn convert_to_new_syntax_2() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+static const String:g_name[] = "name";
+                      |
+"#,
+    ));
+}
+
+#[test]
+fn convert_to_new_syntax_3() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+public Action:Cmd(client, &Float:f, const String:name[], any:...)
+          |
+{
+    return Plugin_Handled;
+}
+"#,
+    ));
+}
+
+#[test]
+fn convert_to_new_syntax_4() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+public OnPluginStart()
+        |
+{
+}
+"#,
+    ));
+}
+
+#[test]
+fn convert_to_new_syntax_5() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+native Foo(_:a, bool:b = false, Handle:h = INVALID_HANDLE);
+              |
+"#,
+    ));
+}
+
+#[test]
+fn convert_to_new_syntax_6() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+void foo()
+{
+    decl String:buf[64], Float:pos[3], count;
+     |
+}
+"#,
+    ));
+}
+
+#[test]
+fn convert_to_new_syntax_7() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+void foo()
+{
+    for (new i = 0, j; i < 3; i++) {}
+          |
+}
+"#,
+    ));
+}
+
+#[test]
+fn convert_to_new_syntax_8() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+stock Float:Sum(Float:a, Float:b)
+^
+{
+    new Float:sum = a + b;
+    return sum;
+}
+new g_count;
+      ^
+new g_total;
+"#,
+    ));
+}
+
+#[test]
+fn convert_to_new_syntax_9() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+#define SIZE 64
+new String:g_buf[SIZE];
+     |
+"#,
+    ));
+}
+
+#[test]
+fn convert_to_new_syntax_10() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+void foo(int client)
+{
+    int a = client;
+        |
+}
+"#,
+    ));
+}
+
+#[test]
+fn generate_callback_1() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+typedef Timer = function Action (Handle timer, any data);
+native Handle CreateTimer(float interval, Timer func, any data = 0, int flags = 0);
+
+public void OnPluginStart() {
+    CreateTimer(1.0, Timer_Respawn);
+                      |
+}
+
+void foo() {}
+"#,
+    ));
+}
+
+#[test]
+fn generate_callback_2() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+typeset SDKHookCB {
+    function void (int client);
+    function Action (int victim, int &attacker, float &damage);
+};
+native void SDKHook(int entity, int type, SDKHookCB callback);
+
+public void OnClientPutInServer(int client) {
+    SDKHook(client, 0, OnTakeDamage);
+                           |
+}
+"#,
+    ));
+}
+
+#[test]
+fn generate_callback_3() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+functag public Action:SrvCmd(args);
+native RegServerCmd(const String:cmd[], SrvCmd:callback);
+
+public OnPluginStart() {
+    RegServerCmd("sm_test", Command_Test);
+                              |
+}
+"#,
+    ));
+}
+
+#[test]
+fn generate_callback_4() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+typedef Callback = function bool (int client, any ...);
+methodmap Hooks {
+    public native void Add(Callback callback);
+}
+
+void foo(Hooks hooks) {
+    hooks.Add(OnHook);
+               |
+}
+"#,
+    ));
+}
+
+#[test]
+fn generate_callback_5() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+native void PrintValue(int value);
+
+void foo() {
+    PrintValue(value);
+                |
+}
+"#,
+    ));
+}
+
+#[test]
+fn extract_function_1() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+native void PrintToServer(const char[] format, any ...);
+
+public void OnPluginStart()
+{
+    int count = 0;
+    count++;
+^
+    PrintToServer("%d", count);
+                              ^
+}
+"#,
+    ));
+}
+
+#[test]
+fn extract_function_2() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+native void PrintToServer(const char[] format, any ...);
+
+void foo(int client, const char[] name, float pos[3])
+{
+    char buf[64];
+    Format(buf, sizeof(buf), "%s", name);
+^
+    pos[0] = 1.0;
+    PrintToServer("%d %s", client, buf);
+                                       ^
+}
+"#,
+    ));
+}
+
+#[test]
+fn extract_function_3() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+native void PrintToServer(const char[] format, any ...);
+
+void foo(int a)
+{
+    int b = a * 2;
+^
+    int c = b + 1;
+                  ^
+    PrintToServer("%d", c);
+}
+"#,
+    ));
+}
+
+#[test]
+fn extract_function_4() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+int foo(int a)
+{
+    if (a > 0)
+^
+    {
+        return a;
+    }
+    ^
+    return 0;
+}
+"#,
+    ));
+}
+
+#[test]
+fn extract_function_5() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+void foo()
+{
+    for (int i = 0; i < 3; i++)
+^
+    {
+        if (i == 1)
+        {
+            continue;
+        }
+    }
+    ^
+    int x = 1;
+}
+"#,
+    ));
+}
+
+#[test]
+fn extract_function_6() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+native void SetValue(int &value);
+
+int foo()
+{
+    int value = 0;
+    SetValue(value);
+^
+    return value;
+^
+}
+"#,
+    ));
+}
+
+#[test]
+fn inline_local_variable_1() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+int foo(int a, int b)
+{
+    int sum = a + b;
+        |
+    return sum * sum;
+}
+"#,
+    ));
+}
+
+#[test]
+fn inline_local_variable_2() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+void bar(int x) {}
+
+void foo(int a)
+{
+    int x = a, y = 2;
+    bar(x);
+        |
+    bar(y);
+}
+"#,
+    ));
+}
+
+#[test]
+fn inline_local_variable_3() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+int foo(int a)
+{
+    int x = a;
+        |
+    x++;
+    return x;
+}
+"#,
+    ));
+}
+
+#[test]
+fn inline_local_variable_4() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+native void SetValue(int &value);
+
+int foo()
+{
+    int value = 0;
+        |
+    SetValue(value);
+    return value;
+}
+"#,
+    ));
+}
+
+#[test]
+fn inline_macro_1() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+#define SQ(%1) %1 * %1
+int foo(int a)
+{
+    return SQ(a + 1);
+           |
+}
+"#,
+    ));
+}
+
+#[test]
+fn inline_macro_2() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+#define SUM 1 + 2
+int foo()
+{
+    return SUM == 3;
+           |
+}
+"#,
+    ));
+}
+
+#[test]
+fn inline_macro_3() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+#define ONE 1
+#define ADD_ONE(%1) %1 + ONE
+int foo(int a)
+{
+    return 2 * ADD_ONE(a);
+               |
+}
+"#,
+    ));
+}
+
+#[test]
+fn generate_methodmap_members_1() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+methodmap Handle __nullable__ {
+    public native void Close();
+}
+methodmap StringMap < Handle {
+    public native StringMap();
+    public native bool SetValue(const char[] key, any value, bool replace = true);
+    public native bool GetValue(const char[] key, any &value);
+}
+methodmap Player < StringMap {
+          |
+}
+"#,
+    ));
+}
+
+#[test]
+fn generate_methodmap_members_2() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+methodmap Handle __nullable__ {
+    public native void Close();
+}
+methodmap KeyValues < Handle {
+    public native KeyValues(const char[] name, const char[] firstKey = "", const char[] firstValue = "");
+}
+enum struct PlayerInfo {
+    int kills;
+    float speed;
+    char player_name[32];
+}
+methodmap Player < KeyValues {
+    public Player(const char[] name) {
+        return view_as<Player>(new KeyValues(name));
+    }
+    property int kills {
+        public get() { return this.GetNum("kills"); }
+    }
+          |
+}
+"#,
+    ));
+}
+
+#[test]
+fn generate_methodmap_members_3() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+methodmap Counter __nullable__ {
+    public void Reset() {
+        int a = 0;
+            |
+    }
+}
+"#,
+    ));
+}
+
+#[test]
+fn organize_includes_1() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+#include "utils"
+#include <sdktools>
+#include <sourcemod>
+#include <sdktools.inc>
+#include <cstrike>
+   |
+#pragma semicolon 1
+
+public void OnPluginStart() {
+    Foo();
+    TeleportEntity();
+}
+%! utils.inc
+void Foo() {}
+%! $include/sourcemod.inc
+#define SOURCEMOD_V_MAJOR 1
+%! $include/sdktools.inc
+native void TeleportEntity();
+%! $include/cstrike.inc
+native void CS_RespawnPlayer(int client);
+"#,
+    ));
+}
+
+#[test]
+fn organize_includes_2() {
+    assert_json_snapshot!(code_action(
+        r#"
+%! main.sp
+#include <sourcemod>
+#include <clientprefs>
+#include <extra>
+#include <unused>
+   |
+
+#if defined _extra_included
+#endif
+
+public void OnClientCookiesCached(int client) {}
+%! $include/sourcemod.inc
+#define SOURCEMOD_V_MAJOR 1
+%! $include/clientprefs.inc
+forward void OnClientCookiesCached(int client);
+%! $include/extra.inc
+#define _extra_included
+%! $include/unused.inc
+native void Unused();
+"#,
+    ));
+}
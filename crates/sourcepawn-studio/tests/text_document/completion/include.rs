@@ -95,3 +95,75 @@ int foo;"#,
         Some("/".to_string())
     ));
 }
+
+#[test]
+fn auto_include_1() {
+    let completions = complete(
+        r#"
+%! main.sp
+#include "foo.sp"
+
+void OnPluginStart() {
+    ZHoo
+        |
+}
+%! foo.sp
+%! $include/zhooks.inc
+native void ZHook(int entity, int type);
+methodmap ZHooker {
+    public native void ZHookMethod();
+}
+%! $include/zlib/trace.inc
+native void ZHookTrace();
+"#,
+        None,
+    );
+    assert_json_snapshot!(completions
+        .into_iter()
+        .filter(|item| item.label.starts_with("ZHook"))
+        .collect::<Vec<_>>());
+}
+
+#[test]
+fn auto_include_2() {
+    let completions = complete(
+        r#"
+%! main.sp
+#include <zhooks>
+
+void OnPluginStart() {
+    ZHoo
+        |
+}
+%! $include/zhooks.inc
+native void ZHook(int entity, int type);
+%! $include/zother.inc
+native void ZHookOther();
+"#,
+        None,
+    );
+    assert_json_snapshot!(completions
+        .into_iter()
+        .filter(|item| item.label.starts_with("ZHook"))
+        .collect::<Vec<_>>());
+}
+
+#[test]
+fn auto_include_3() {
+    let completions = complete(
+        r#"
+%! main.sp
+#pragma semicolon 1
+
+ZHoo
+    |
+%! $include/zhooks.inc
+native void ZHook(int entity, int type);
+"#,
+        None,
+    );
+    assert_json_snapshot!(completions
+        .into_iter()
+        .filter(|item| item.label.starts_with("ZHook"))
+        .collect::<Vec<_>>());
+}
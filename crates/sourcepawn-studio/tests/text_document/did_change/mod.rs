@@ -0,0 +1,106 @@
+use insta::assert_json_snapshot;
+use lsp_types::{Position, Range};
+use sourcepawn_studio::fixture::goto_definition_after_changes;
+
+fn insert(line: u32, character: u32) -> Range {
+    Range::new(
+        Position::new(line, character),
+        Position::new(line, character),
+    )
+}
+
+#[test]
+fn rename_global_1() {
+    assert_json_snapshot!(goto_definition_after_changes(
+        r#"
+%! main.sp
+int fo;
+void bar() {
+    foo = 1;
+     |
+}"#,
+        &[vec![(insert(2, 12), " // 1")], vec![(insert(0, 6), "o")]],
+    ));
+}
+
+#[test]
+fn shadow_global_1() {
+    assert_json_snapshot!(goto_definition_after_changes(
+        r#"
+%! main.sp
+int foo;
+void bar() {
+    int baz;
+    foo = 1;
+     |
+}"#,
+        &[
+            vec![(insert(3, 12), " // 1")],
+            vec![(insert(2, 11), " int foo;")]
+        ],
+    ));
+}
+
+#[test]
+fn remove_global_1() {
+    assert_json_snapshot!(goto_definition_after_changes(
+        r#"
+%! main.sp
+int foo;
+void bar() {
+    foo = 1;
+     |
+}"#,
+        &[
+            vec![(insert(2, 12), " // 1")],
+            vec![(Range::new(Position::new(0, 0), Position::new(0, 8)), "")],
+        ],
+    ));
+}
+
+#[test]
+fn several_changes_1() {
+    assert_json_snapshot!(goto_definition_after_changes(
+        r#"
+%! main.sp
+int fo;
+void bar() {
+    foo = 1;
+     |
+}"#,
+        &[
+            vec![(insert(2, 12), " // 1")],
+            vec![(insert(2, 17), " 2"), (insert(0, 6), "o")],
+        ],
+    ));
+}
+
+#[test]
+fn edit_macro_1() {
+    assert_json_snapshot!(goto_definition_after_changes(
+        r#"
+%! main.sp
+#define TYPE in
+TYPE foo;
+void bar() {
+    foo = 1;
+     |
+}"#,
+        &[vec![(insert(3, 12), " // 1")], vec![(insert(0, 15), "t")]],
+    ));
+}
+
+#[test]
+fn edit_after_macro_1() {
+    assert_json_snapshot!(goto_definition_after_changes(
+        r#"
+%! main.sp
+#define TYPE int
+TYPE fo;
+void bar() {
+    foo = 1;
+     |
+}"#,
+        &[vec![(insert(3, 12), " // 1")], vec![(insert(1, 7), "o")]],
+    ));
+}
@@ -0,0 +1,105 @@
+use insta::assert_json_snapshot;
+use sourcepawn_studio::fixture::document_highlight;
+
+#[test]
+fn global_variable_1() {
+    assert_json_snapshot!(document_highlight(
+        r#"
+%! main.sp
+Handle g_hTimer;
+        |
+void foo() {
+    if (g_hTimer != null) {
+        delete g_hTimer;
+    }
+    g_hTimer = CreateTimer();
+}
+Handle CreateTimer() {
+    return null;
+}
+"#,
+    ));
+}
+
+#[test]
+fn local_variable_1() {
+    assert_json_snapshot!(document_highlight(
+        r#"
+%! main.sp
+void foo() {
+    int a = 1;
+        |
+    a += 2;
+    a++;
+    --a;
+    int b = a;
+}
+"#,
+    ));
+}
+
+#[test]
+fn array_1() {
+    assert_json_snapshot!(document_highlight(
+        r#"
+%! main.sp
+int a[4];
+    |
+int b;
+void foo() {
+    a[b] = 1;
+    b = a[0];
+}
+"#,
+    ));
+}
+
+#[test]
+fn by_ref_argument_1() {
+    assert_json_snapshot!(document_highlight(
+        r#"
+%! main.sp
+void foo(int &a, int b, char[] buffer, const char[] format) {}
+void bar() {
+    int c;
+        |
+    char d[8];
+    foo(c, c, d, d);
+    foo(.a = c, .b = c, .buffer = d, .format = d);
+}
+"#,
+    ));
+}
+
+#[test]
+fn by_ref_argument_2() {
+    assert_json_snapshot!(document_highlight(
+        r#"
+%! main.sp
+void foo(int &a, int b, char[] buffer, const char[] format) {}
+void bar() {
+    int c;
+    char d[8];
+         |
+    foo(c, c, d, d);
+    foo(.a = c, .b = c, .buffer = d, .format = d);
+}
+"#,
+    ));
+}
+
+#[test]
+fn enum_struct_field_1() {
+    assert_json_snapshot!(document_highlight(
+        r#"
+%! main.sp
+enum struct Foo {
+    int a;
+        |
+    void Bar() {
+        this.a = this.a + 1;
+    }
+}
+"#,
+    ));
+}
@@ -0,0 +1,157 @@
+use insta::assert_json_snapshot;
+use sourcepawn_studio::fixture::folding_range;
+
+#[test]
+fn function_1() {
+    assert_json_snapshot!(folding_range(
+        r#"
+%! main.sp
+void foo(int a)
+{
+    if (a)
+    {
+        a = 1;
+    }
+}
+"#,
+    ));
+}
+
+#[test]
+fn methodmap_1() {
+    assert_json_snapshot!(folding_range(
+        r#"
+%! main.sp
+methodmap Foo {
+    public Foo() {
+        return view_as<Foo>(1);
+    }
+    property int Bar {
+        public get() {
+            return 1;
+        }
+    }
+}
+"#,
+    ));
+}
+
+#[test]
+fn enum_struct_1() {
+    assert_json_snapshot!(folding_range(
+        r#"
+%! main.sp
+enum struct Foo {
+    int a;
+    void Bar() {
+        this.a = 1;
+    }
+}
+"#,
+    ));
+}
+
+#[test]
+fn enum_1() {
+    assert_json_snapshot!(folding_range(
+        r#"
+%! main.sp
+enum Foo {
+    Foo_A,
+    Foo_B
+}
+"#,
+    ));
+}
+
+#[test]
+fn switch_1() {
+    assert_json_snapshot!(folding_range(
+        r#"
+%! main.sp
+void foo(int a) {
+    switch (a) {
+        case 1: {
+            a = 2;
+        }
+        case 2:
+            a = 3;
+        default:
+            a = 4;
+    }
+}
+"#,
+    ));
+}
+
+#[test]
+fn comments_1() {
+    assert_json_snapshot!(folding_range(
+        r#"
+%! main.sp
+// First line.
+// Second line.
+// Third line.
+int a;
+// Lonely comment.
+int b; // Trailing comment.
+/**
+ * Block comment.
+ */
+int c;
+"#,
+    ));
+}
+
+#[test]
+fn condition_1() {
+    assert_json_snapshot!(folding_range(
+        r#"
+%! main.sp
+#if defined FOO
+int a;
+int b;
+#else
+int c;
+int d;
+#endif
+"#,
+    ));
+}
+
+#[test]
+fn condition_nested_1() {
+    assert_json_snapshot!(folding_range(
+        r#"
+%! main.sp
+#define FOO
+#if defined FOO
+int a;
+#if defined BAR
+int b;
+int c;
+#endif
+#elseif defined BAZ
+int d;
+int e;
+#endif
+"#,
+    ));
+}
+
+#[test]
+fn region_1() {
+    assert_json_snapshot!(folding_range(
+        r#"
+%! main.sp
+// #region Globals
+int a;
+int b;
+// #endregion
+#pragma region Functions
+void foo() {}
+void bar() {}
+#pragma endregion
+"#,
+    ));
+}
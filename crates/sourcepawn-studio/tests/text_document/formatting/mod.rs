@@ -0,0 +1,234 @@
+use insta::assert_snapshot;
+use sourcepawn_studio::fixture::formatting;
+
+#[test]
+fn function_1() {
+    assert_snapshot!(formatting(
+        r#"
+%! main.sp
+public void OnPluginStart(){
+  int x=1;
+    x++;
+}
+"#,
+    ));
+}
+
+#[test]
+fn control_flow_1() {
+    assert_snapshot!(formatting(
+        r#"
+%! main.sp
+void foo(int x) {
+  if(x>1)x=2; else if (x < 0) { x=3; } else {
+  x = 4;
+  }
+  for(int i=0;i<10;i++){ x += i; }
+  while(x) x--;
+  do { x--; } while (x > 0);
+}
+"#,
+    ));
+}
+
+#[test]
+fn switch_1() {
+    assert_snapshot!(formatting(
+        r#"
+%! main.sp
+void foo(int x) {
+  switch(x){ case 1: { x=1; } case 2, 3: x=2; default:
+  x=4; }
+}
+"#,
+    ));
+}
+
+#[test]
+fn methodmap_1() {
+    assert_snapshot!(formatting(
+        r#"
+%! main.sp
+methodmap Foo < Handle {
+  public Foo(int a) { return view_as<Foo>(a); }
+  public native void Bar();
+  property int Baz {
+    public get() { return 1; }
+    public set(int value) {}
+  }
+};
+"#,
+    ));
+}
+
+#[test]
+fn enum_struct_1() {
+    assert_snapshot!(formatting(
+        r#"
+%! main.sp
+enum struct Foo { int a; float b;
+  void Bar(int c) { this.a = c; } }
+"#,
+    ));
+}
+
+#[test]
+fn enum_1() {
+    assert_snapshot!(formatting(
+        r#"
+%! main.sp
+enum Foo { Foo_A = 1, Foo_B, }
+enum { Bar_A = 1 << 0, Bar_B = 1 << 1 }
+"#,
+    ));
+}
+
+#[test]
+fn operators_1() {
+    assert_snapshot!(formatting(
+        r#"
+%! main.sp
+int foo(int a, int &b, const char[] c, any ...) {
+  int x=a*b+-c[0], y[]={1, 2};
+  x+=1; x=!x; ++x;
+  b = x?1:2;
+  x = view_as<int>(Foo::Bar)<<2;
+  foo(.a=1, .b=x);
+  return -x;
+}
+"#,
+    ));
+}
+
+#[test]
+fn old_syntax_1() {
+    assert_snapshot!(formatting(
+        r#"
+%! main.sp
+public Action:Foo(client, const String:name[]) {
+  new Float:x=1.0;
+  decl String:buffer[32];
+  return Plugin_Continue;
+}
+"#,
+    ));
+}
+
+#[test]
+fn preprocessor_1() {
+    assert_snapshot!(formatting(
+        r#"
+%! main.sp
+#include <sourcemod>
+#pragma semicolon 1
+#define FOO(%1) (%1+1)
+void foo() {
+    #if defined DEBUG
+        int x=1;
+    #else
+    int x = 2;
+    #endif
+}
+"#,
+    ));
+}
+
+#[test]
+fn comments_1() {
+    assert_snapshot!(formatting(
+        r#"
+%! main.sp
+// Line comment
+// Another one
+methodmap Foo {
+/**
+ * Documentation.
+ */
+public void Bar() { int x; // Trailing comment
+  /* block */ x = 1;
+  }
+}
+"#,
+    ));
+}
+
+#[test]
+fn blank_lines_1() {
+    assert_snapshot!(formatting(
+        r#"
+%! main.sp
+int x;
+
+
+
+int y;
+void foo() {
+
+  x = 1;
+
+
+  y = 2;
+
+}
+"#,
+    ));
+}
+
+#[test]
+fn range_1() {
+    assert_snapshot!(formatting(
+        r#"
+%! main.sp
+void foo() {
+  int x=1;
+  ^^^^^^^^
+  int y=2;
+}
+"#,
+    ));
+}
+
+#[test]
+fn editorconfig_1() {
+    assert_snapshot!(formatting(
+        r#"
+%! main.sp
+void foo() {
+  int x;
+}
+%! .editorconfig
+root = true
+
+[*.sp]
+indent_style = tab
+"#,
+    ));
+}
+
+#[test]
+fn editorconfig_2() {
+    assert_snapshot!(formatting(
+        r#"
+%! main.sp
+void foo() {
+  int x;
+}
+%! .editorconfig
+[*]
+indent_size = 2
+"#,
+    ));
+}
+
+#[test]
+fn syntax_error_1() {
+    assert_snapshot!(formatting(
+        r#"
+%! main.sp
+void foo() {
+  int x = ;
+  x  = 1;
+}
+"#,
+    ));
+}
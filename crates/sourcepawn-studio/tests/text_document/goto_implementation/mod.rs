@@ -0,0 +1,103 @@
+use insta::assert_json_snapshot;
+use sourcepawn_studio::fixture::goto_implementation;
+
+#[test]
+fn forward_1() {
+    assert_json_snapshot!(goto_implementation(
+        r#"
+%! main.sp
+#include "foo.inc"
+public void OnFoo(int client) {}
+%! foo.inc
+forward void OnFoo(int client);
+               |
+               ^
+"#,
+    ));
+}
+
+#[test]
+fn forward_2() {
+    assert_json_snapshot!(goto_implementation(
+        r#"
+%! main.sp
+#include "foo.inc"
+public void OnFoo(int client) {}
+%! foo.inc
+forward void OnFoo(int client);
+               |
+               ^
+%! other.sp
+#include "foo.inc"
+public void OnFoo(int client) {}
+"#,
+    ));
+}
+
+#[test]
+fn forward_not_public_1() {
+    assert_json_snapshot!(goto_implementation(
+        r#"
+%! main.sp
+forward void OnFoo(int client);
+               |
+               ^
+void OnFoo(int client) {}
+"#,
+    ));
+}
+
+#[test]
+fn typedef_1() {
+    assert_json_snapshot!(goto_implementation(
+        r#"
+%! main.sp
+typedef FooCallback = function void (int client, const char[] name);
+          |
+          ^
+void Foo1(int client, const char[] name) {}
+void Foo2(int client) {}
+void Foo3() {}
+void Foo4(float client) {}
+int Foo5(int client) {}
+void Foo6(int client, const char[] name, int extra) {}
+"#,
+    ));
+}
+
+#[test]
+fn typeset_1() {
+    assert_json_snapshot!(goto_implementation(
+        r#"
+%! main.sp
+typeset FooCallback {
+          |
+          ^
+    function void (int client);
+    function Action (int client, int &value);
+};
+enum Action {
+    Plugin_Continue
+}
+void Foo1(int client) {}
+Action Foo2(int client, int &value) {}
+Action Foo3(int client, int value) {}
+"#,
+    ));
+}
+
+#[test]
+fn functag_1() {
+    assert_json_snapshot!(goto_implementation(
+        r#"
+%! main.sp
+functag public Action:FooCallback(Handle:client);
+                          |
+                          ^
+public Action:Foo1(Handle:client) {}
+public Action Foo2(Handle client) {}
+public Foo3(Handle:client) {}
+public Action:Foo4(client) {}
+"#,
+    ));
+}
@@ -0,0 +1,94 @@
+use insta::assert_json_snapshot;
+use sourcepawn_studio::fixture::goto_type_definition;
+
+#[test]
+fn global_variable_1() {
+    assert_json_snapshot!(goto_type_definition(
+        r#"
+%! main.sp
+enum struct Foo {
+    int a;
+}
+Foo foo;
+     |
+     ^"#,
+    ));
+}
+
+#[test]
+fn local_variable_1() {
+    assert_json_snapshot!(goto_type_definition(
+        r#"
+%! main.sp
+methodmap Foo {}
+void bar() {
+    Foo foo;
+    foo;
+     |
+     ^
+}"#,
+    ));
+}
+
+#[test]
+fn function_1() {
+    assert_json_snapshot!(goto_type_definition(
+        r#"
+%! main.sp
+enum Foo {
+    Foo_A,
+}
+Foo bar() {
+     |
+     ^
+    return Foo_A;
+}"#,
+    ));
+}
+
+#[test]
+fn field_1() {
+    assert_json_snapshot!(goto_type_definition(
+        r#"
+%! main.sp
+methodmap Bar {}
+enum struct Foo {
+    Bar bar;
+}
+void baz() {
+    Foo foo;
+    foo.bar;
+         |
+         ^
+}"#,
+    ));
+}
+
+#[test]
+fn include_1() {
+    assert_json_snapshot!(goto_type_definition(
+        r#"
+%! main.sp
+#include "foo.inc"
+void bar() {
+    Foo foo;
+    foo;
+     |
+     ^
+}
+%! foo.inc
+methodmap Foo {}
+"#,
+    ));
+}
+
+#[test]
+fn builtin_type_1() {
+    assert_json_snapshot!(goto_type_definition(
+        r#"
+%! main.sp
+int foo;
+     |
+     ^"#,
+    ));
+}
@@ -0,0 +1,120 @@
+use insta::assert_json_snapshot;
+use sourcepawn_studio::fixture::inlay_hints;
+
+#[test]
+fn function_1() {
+    assert_json_snapshot!(inlay_hints(
+        r#"
+%! main.sp
+void foo(int health, bool alive) {}
+void bar() {
+    foo(100, true);
+}
+"#,
+    ));
+}
+
+#[test]
+fn argument_matches_name_1() {
+    assert_json_snapshot!(inlay_hints(
+        r#"
+%! main.sp
+void foo(int client, int health) {}
+void bar(int client, int Health) {
+    foo(client, Health);
+}
+"#,
+    ));
+}
+
+#[test]
+fn rest_parameter_1() {
+    assert_json_snapshot!(inlay_hints(
+        r#"
+%! main.sp
+void foo(const char[] format, any ...) {}
+void bar() {
+    foo("%d %d", 1, 2);
+}
+"#,
+    ));
+}
+
+#[test]
+fn named_arg_1() {
+    assert_json_snapshot!(inlay_hints(
+        r#"
+%! main.sp
+void foo(int a, int b = 0, int c = 0) {}
+void bar() {
+    foo(1, .c = 3);
+}
+"#,
+    ));
+}
+
+#[test]
+fn methodmap_method_1() {
+    assert_json_snapshot!(inlay_hints(
+        r#"
+%! main.sp
+methodmap Foo {
+    public native void Bar(int index, bool value);
+}
+void bar(Foo foo) {
+    foo.Bar(1, false);
+}
+"#,
+    ));
+}
+
+#[test]
+fn methodmap_constructor_1() {
+    assert_json_snapshot!(inlay_hints(
+        r#"
+%! main.sp
+methodmap Foo {
+    public native Foo(int size);
+}
+void bar() {
+    Foo foo = new Foo(10);
+    Foo foo2 = Foo(20);
+}
+"#,
+    ));
+}
+
+#[test]
+fn native_1() {
+    assert_json_snapshot!(inlay_hints(
+        r#"
+%! main.sp
+#include "entity.inc"
+void bar(int client) {
+    SetEntProp(client, Prop_Send, "m_iHealth", 100, 4, 0);
+}
+%! entity.inc
+enum PropType
+{
+    Prop_Send = 0,
+    Prop_Data
+};
+native void SetEntProp(int entity, PropType type, const char[] prop, any value, int size=4, int element=0);
+"#,
+    ));
+}
+
+#[test]
+fn range_1() {
+    assert_json_snapshot!(inlay_hints(
+        r#"
+%! main.sp
+void foo(int a) {}
+void bar() {
+    foo(1);
+    foo(2);
+    ^^^^^^^
+}
+"#,
+    ));
+}
@@ -1,4 +1,14 @@
+mod code_action;
 mod completion;
+mod did_change;
+mod document_highlight;
+mod folding_range;
+mod formatting;
 mod goto_definition;
+mod goto_implementation;
+mod goto_type_definition;
 mod hover;
+mod inlay_hints;
+mod publish_diagnostics;
+mod semantic_tokens;
 mod signature_help;
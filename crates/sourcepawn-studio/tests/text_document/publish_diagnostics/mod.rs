@@ -0,0 +1,660 @@
+use insta::assert_json_snapshot;
+use sourcepawn_studio::fixture::{diagnostics, diagnostics_without};
+
+/// Lints which are not the subject of the `unresolved_ident` fixtures.
+const LINTS: &[&str] = &[
+    "unused-include",
+    "unused-variable",
+    "unused-parameter",
+    "dead-code",
+];
+
+#[test]
+fn unresolved_ident_1() {
+    assert_json_snapshot!(diagnostics_without(
+        r#"
+%! main.sp
+void foo() {
+    int a = b;
+}
+"#,
+        LINTS,
+    ));
+}
+
+#[test]
+fn unresolved_ident_2() {
+    assert_json_snapshot!(diagnostics_without(
+        r#"
+%! main.sp
+void foo() {
+    bar();
+}
+"#,
+        LINTS,
+    ));
+}
+
+#[test]
+fn unresolved_ident_did_you_mean_1() {
+    assert_json_snapshot!(diagnostics_without(
+        r#"
+%! main.sp
+int g_iCounter;
+
+void foo(int counter) {
+    int a = countr + g_iCountr;
+}
+"#,
+        LINTS,
+    ));
+}
+
+#[test]
+fn unresolved_ident_did_you_mean_2() {
+    assert_json_snapshot!(diagnostics_without(
+        r#"
+%! main.sp
+#include "console.inc"
+
+void foo() {
+    printtoserver("foo");
+}
+%! console.inc
+native void PrintToServer(const char[] format, any ...);
+"#,
+        LINTS,
+    ));
+}
+
+#[test]
+fn unresolved_ident_resolved_1() {
+    assert_json_snapshot!(diagnostics_without(
+        r#"
+%! main.sp
+#include "console.inc"
+#include "foo.sp"
+
+enum Color {
+    Color_Red,
+}
+
+int g_iCounter;
+
+void foo(int counter, const char[] name) {
+    int a = counter + g_iCounter + Color_Red + MaxClients + cellmax + bar();
+    PrintToServer("%s %d", name, a);
+    for (int i = 0; i < sizeof(name); i++) {
+        a += i;
+    }
+    Function f = INVALID_FUNCTION;
+}
+%! console.inc
+native void PrintToServer(const char[] format, any ...);
+%! foo.sp
+public const int MaxClients;
+
+int bar() {
+    return 0;
+}
+"#,
+        LINTS,
+    ));
+}
+
+#[test]
+fn unresolved_ident_inactive_code_1() {
+    assert_json_snapshot!(diagnostics_without(
+        r#"
+%! main.sp
+void foo() {
+#if 0
+    int a = b;
+#endif
+}
+"#,
+        LINTS,
+    ));
+}
+
+#[test]
+fn unresolved_ident_macro_1() {
+    assert_json_snapshot!(diagnostics_without(
+        r#"
+%! main.sp
+#define FOO bar
+
+void foo() {
+    int a = FOO;
+}
+"#,
+        LINTS,
+    ));
+}
+
+#[test]
+fn unresolved_ident_unresolved_include_1() {
+    assert_json_snapshot!(diagnostics_without(
+        r#"
+%! main.sp
+#include "missing.sp"
+
+void foo() {
+    bar();
+}
+"#,
+        LINTS,
+    ));
+}
+
+#[test]
+fn unused_include_1() {
+    assert_json_snapshot!(diagnostics(
+        r#"
+%! main.sp
+#include <sourcemod>
+#include <sdktools>
+#include <cstrike>
+
+public void OnPluginStart() {
+    TeleportEntity();
+}
+%! $include/sourcemod.inc
+#define SOURCEMOD_V_MAJOR 1
+%! $include/sdktools.inc
+#include <sdktools_functions>
+%! $include/sdktools_functions.inc
+native void TeleportEntity();
+%! $include/cstrike.inc
+native void CS_RespawnPlayer(int client);
+"#,
+    ));
+}
+
+#[test]
+fn unused_include_2() {
+    assert_json_snapshot!(diagnostics(
+        r#"
+%! main.sp
+#include <cstrike>
+#include <clientprefs>
+#include <extra> // IWYU pragma: keep
+#include "module.sp"
+%! module.sp
+public void OnClientCookiesCached(int client) {
+    CS_RespawnPlayer(client);
+}
+%! $include/cstrike.inc
+native void CS_RespawnPlayer(int client);
+%! $include/clientprefs.inc
+forward void OnClientCookiesCached(int client);
+%! $include/extra.inc
+native void Extra();
+"#,
+    ));
+}
+
+#[test]
+fn unused_variable_1() {
+    assert_json_snapshot!(diagnostics(
+        r#"
+%! main.sp
+int foo() {
+    int a;
+    int b = 1;
+    int c;
+    c = 2;
+    int d = 3;
+    int _e;
+    return d;
+}
+"#,
+    ));
+}
+
+#[test]
+fn unused_parameter_1() {
+    assert_json_snapshot!(diagnostics(
+        r#"
+%! main.sp
+public void OnClientPutInServer(int client) {
+    foo(client, 1, 0);
+}
+
+void foo(int client, int unused, int _reserved) {
+    bar(client);
+}
+
+void bar(int client) {
+    CreateTimer(1.0, Timer_Callback, client);
+}
+
+void Timer_Callback(Handle timer, int client) {
+    bar(client);
+}
+
+native void CreateTimer(float interval, Function func, any data);
+"#,
+    ));
+}
+
+#[test]
+fn unused_parameter_2() {
+    assert_json_snapshot!(diagnostics(
+        r#"
+%! main.sp
+public void OnPluginStart() {
+    int foo = 1;
+    bar(foo);
+}
+
+void bar(int value) {
+    foo(value, 0);
+}
+
+void foo(int value, int unused) {
+    bar(value);
+}
+"#,
+    ));
+}
+
+#[test]
+fn dead_code_1() {
+    assert_json_snapshot!(diagnostics(
+        r#"
+%! main.sp
+#define MAX_ITEMS 8
+#define UNUSED_LIMIT 4
+
+enum Mode {
+    Mode_Normal,
+    Mode_Debug,
+}
+
+int g_iItems[MAX_ITEMS];
+int g_iUnused;
+public int g_iExported;
+
+public void OnPluginStart() {
+    Helper(Mode_Normal);
+}
+
+stock void Helper(Mode mode) {
+    g_iItems[0] = view_as<int>(mode);
+}
+
+stock void UnusedHelper() {}
+"#,
+    ));
+}
+
+#[test]
+fn type_mismatch_1() {
+    assert_json_snapshot!(diagnostics(
+        r#"
+%! main.sp
+public int OnPluginStart() {
+    float f = 1.5;
+    int i = f;
+    i = 2.0 * i;
+    float g = i > 0 ? f : 0.0;
+    bool b = !i;
+    char buffer[8];
+    buffer[0] = 'a';
+    g = Twice(i) + f;
+    return b ? i : g;
+}
+
+int Twice(float value) {
+    return value * 2;
+}
+"#,
+    ));
+}
+
+#[test]
+fn type_mismatch_2() {
+    assert_json_snapshot!(diagnostics(
+        r#"
+%! main.sp
+methodmap Handle __nullable__ {}
+methodmap Menu < Handle {
+    public Menu() {
+        return view_as<Menu>(0);
+    }
+}
+
+void Close(Handle handle) {
+    delete handle;
+}
+
+void Display(Menu menu) {
+    delete menu;
+}
+
+public void OnPluginStart() {
+    Menu menu = new Menu();
+    Handle handle = menu;
+    Close(menu);
+    Close(0);
+    Display(handle);
+    int count = handle;
+    Close(count);
+}
+"#,
+    ));
+}
+
+#[test]
+fn type_mismatch_3() {
+    assert_json_snapshot!(diagnostics(
+        r#"
+%! main.sp
+enum struct Player {
+    float speed;
+    int score;
+}
+
+Player g_players[4];
+
+public void OnPluginStart() {
+    float f = 5;
+    int i = 5.0;
+    f = g_players[0].speed;
+    i = g_players[1].score;
+    i = g_players[2].speed;
+}
+"#,
+    ));
+}
+
+#[test]
+fn callback_signature_mismatch_1() {
+    assert_json_snapshot!(diagnostics(
+        r#"
+%! main.sp
+enum Action {
+    Plugin_Continue,
+}
+
+typedef ConCmd = function Action (int client, int args);
+typedef SDKHookCB = function void (int client, float &damage);
+
+native void RegConsoleCmd(const char[] cmd, ConCmd callback);
+native void SDKHook(int client, SDKHookCB callback);
+
+public void OnPluginStart() {
+    RegConsoleCmd("sm_ok", Command_Ok);
+    RegConsoleCmd("sm_short", Command_Short);
+    RegConsoleCmd("sm_type", Command_Type);
+    RegConsoleCmd("sm_return", Command_Return);
+    RegConsoleCmd("sm_params", Command_Params);
+    SDKHook(0, Hook_Damage);
+}
+
+public Action Command_Ok(int client, int args) { return Plugin_Continue; }
+public Action Command_Short(int client) { return Plugin_Continue; }
+public Action Command_Type(float client, int args) { return Plugin_Continue; }
+public void Command_Return(int client, int args) {}
+public Action Command_Params(int client, int args, int extra) { return Plugin_Continue; }
+public void Hook_Damage(int client, float damage) {}
+"#,
+    ));
+}
+
+#[test]
+fn callback_signature_mismatch_2() {
+    assert_json_snapshot!(diagnostics(
+        r#"
+%! main.sp
+enum Action {
+    Plugin_Continue,
+}
+
+methodmap Handle __nullable__ {}
+
+typeset Timer {
+    function Action (Handle timer);
+    function Action (Handle timer, any data);
+};
+
+native void CreateTimer(float interval, Timer func, any data = 0);
+
+public void OnPluginStart() {
+    CreateTimer(1.0, Timer_Ok);
+    CreateTimer(1.0, Timer_Data, 1);
+    CreateTimer(1.0, Timer_Void);
+}
+
+public Action Timer_Ok(Handle timer) { return Plugin_Continue; }
+public Action Timer_Data(Handle timer, any data) { return Plugin_Continue; }
+public void Timer_Void(Handle timer) {}
+"#,
+    ));
+}
+
+#[test]
+fn unreachable_code_1() {
+    assert_json_snapshot!(diagnostics(
+        r#"
+%! main.sp
+native void PrintToServer(const char[] format, any ...);
+
+public void OnPluginStart() {
+    for (int i = 0; i < 10; i++) {
+        if (i == 5) {
+            continue;
+            PrintToServer("skipped");
+        }
+        switch (i) {
+            case 1: {
+                break;
+            }
+            default: {
+                PrintToServer("default");
+            }
+        }
+        PrintToServer("loop");
+    }
+    while (true) {
+        PrintToServer("forever");
+    }
+    PrintToServer("never");
+    return;
+}
+"#,
+    ));
+}
+
+#[test]
+fn missing_return_1() {
+    assert_json_snapshot!(diagnostics(
+        r#"
+%! main.sp
+enum Action {
+    Plugin_Continue,
+    Plugin_Handled,
+}
+
+native void RegConsoleCmd(const char[] cmd, Action callback);
+
+public void OnPluginStart() {
+    RegConsoleCmd("sm_ok", Command_Ok);
+    RegConsoleCmd("sm_missing", Command_Missing);
+    Foo(1);
+    Bar(1);
+    Baz(1);
+}
+
+public Action Command_Ok(int client, int args) {
+    if (client == 0) {
+        return Plugin_Handled;
+    }
+    return Plugin_Continue;
+}
+
+public Action Command_Missing(int client, int args) {
+    if (client == 0) {
+        return Plugin_Handled;
+    }
+}
+
+int Foo(int a) {
+    if (a) {
+        return 1;
+    }
+    return;
+}
+
+int Bar(int a) {
+    switch (a) {
+        case 1: {
+            return 1;
+        }
+        default: {
+            return 0;
+        }
+    }
+}
+
+int Baz(int a) {
+    for (;;) {
+        if (a) {
+            return a;
+        }
+    }
+}
+"#,
+    ));
+}
+
+#[test]
+fn format_string_1() {
+    assert_json_snapshot!(diagnostics(
+        r#"
+%! main.sp
+native int Format(char[] buffer, int maxlength, const char[] format, any ...);
+
+public void OnPluginStart() {
+    char buffer[64];
+    char name[32];
+    float speed = 1.0;
+    int count = 2;
+    Format(buffer, sizeof(buffer), "%s has %d items at %.2f", name, count, speed);
+    Format(buffer, sizeof(buffer), "%d%%", speed);
+    Format(buffer, sizeof(buffer), "%s %d", name);
+    Format(buffer, sizeof(buffer), "%d", count, count, count);
+    Format(buffer, sizeof(buffer), "%y", count);
+}
+"#,
+    ));
+}
+
+#[test]
+fn format_string_2() {
+    assert_json_snapshot!(diagnostics(
+        r#"
+%! main.sp
+native void PrintToChat(int client, const String:format[], any:...);
+native void ReplyToCommand(int client, const char[] format, any ...);
+
+public void OnPluginStart() {
+    int client = 1;
+    float damage = 2.0;
+    PrintToChat(client, "%N took %f damage", client, damage);
+    PrintToChat(client, "%N took %d damage", client, damage);
+    ReplyToCommand(client, "%t %d", "Phrase", client, 1, 2);
+    ReplyToCommand(client, "%T", "Phrase");
+    ReplyToCommand(client, "%T", 1.0, client);
+}
+"#,
+    ));
+}
+
+#[test]
+fn format_string_3() {
+    assert_json_snapshot!(diagnostics(
+        r#"
+%! main.sp
+native int Format(char[] buffer, int maxlength, const char[] format, any ...);
+
+enum struct Player {
+    char name[32];
+    int score;
+}
+
+char g_sName[64];
+Player g_player;
+
+public void OnPluginStart() {
+    char buffer[64];
+    Format(buffer, sizeof(buffer), "%s %s", g_sName, g_player.name);
+    Format(buffer, sizeof(buffer), "%d %d", g_sName, g_player.name);
+    Format(buffer, sizeof(buffer), "%s", g_player.score);
+}
+"#,
+    ));
+}
+
+#[test]
+fn buffer_size_1() {
+    assert_json_snapshot!(diagnostics(
+        r#"
+%! main.sp
+#define MAX_NAME_LENGTH 128
+native void GetClientName(int client, char[] name, int maxlen);
+
+public void OnPluginStart() {
+    char name[64], other[32];
+    GetClientName(1, name, sizeof(name));
+    GetClientName(1, name, sizeof(other));
+    GetClientName(1, name, 64);
+    GetClientName(1, name, MAX_NAME_LENGTH);
+}
+"#,
+    ));
+}
+
+#[test]
+fn buffer_size_2() {
+    assert_json_snapshot!(diagnostics(
+        r#"
+%! main.sp
+native GetClientAuthString(client, String:auth[], maxlen);
+
+new String:g_auth[32];
+new String:g_names[4][16];
+
+public OnPluginStart() {
+    GetClientAuthString(1, g_auth, sizeof(g_auth));
+    GetClientAuthString(1, g_auth, 33);
+    GetClientAuthString(1, g_names[2], sizeof(g_names[]));
+    GetClientAuthString(1, g_names[2], sizeof(g_names));
+    GetClientAuthString(1, g_names[2], 32);
+}
+"#,
+    ));
+}
+
+#[test]
+fn buffer_size_3() {
+    assert_json_snapshot!(diagnostics(
+        r#"
+%! main.sp
+native void GetClientName(int client, char[] name, int maxlen);
+
+enum struct Player {
+    char name[32];
+    char auth[64];
+}
+
+Player g_Players[4];
+
+public void OnPluginStart() {
+    GetClientName(1, g_Players[0].name, sizeof(Player::name));
+    GetClientName(1, g_Players[0].name, sizeof(Player::auth));
+}
+"#,
+    ));
+}
@@ -0,0 +1,16 @@
+use insta::assert_json_snapshot;
+use sourcepawn_studio::fixture::semantic_tokens;
+
+#[test]
+fn format_specifier_1() {
+    assert_json_snapshot!(semantic_tokens(
+        r#"
+%! main.sp
+native int Format(char[] buffer, int maxlength, const char[] format, any ...);
+void foo() {
+    char buffer[64];
+    Format(buffer, sizeof(buffer), "%d%% of %-5.2f", 1, 2.0);
+}
+"#,
+    ));
+}
@@ -0,0 +1,79 @@
+use insta::assert_json_snapshot;
+use sourcepawn_studio::fixture::workspace_symbol;
+
+#[test]
+fn function_1() {
+    assert_json_snapshot!(workspace_symbol(
+        r#"
+%! main.sp
+void OnPluginStart() {
+    int local;
+}
+int g_iCount;
+"#,
+        "OnPlugin",
+    ));
+}
+
+#[test]
+fn fuzzy_1() {
+    assert_json_snapshot!(workspace_symbol(
+        r#"
+%! main.sp
+void GetClientName() {}
+void GetCount() {}
+void Unrelated() {}
+"#,
+        "gcn",
+    ));
+}
+
+#[test]
+fn methodmap_1() {
+    assert_json_snapshot!(workspace_symbol(
+        r#"
+%! main.sp
+#include "include/client.inc"
+%! include/client.inc
+methodmap ClientList {
+    public native int GetClient(int index);
+    property int Length {
+        public native get();
+    }
+}
+methodmap Other {
+    public native int GetClient(int index);
+}
+"#,
+        "Cl.Get",
+    ));
+}
+
+#[test]
+fn enum_1() {
+    assert_json_snapshot!(workspace_symbol(
+        r#"
+%! main.sp
+enum Color {
+    Color_Red,
+    Color_Blue
+}
+enum struct Point {
+    int x;
+    int y;
+}
+"#,
+        "Color",
+    ));
+}
+
+#[test]
+fn no_match_1() {
+    assert_json_snapshot!(workspace_symbol(
+        r#"
+%! main.sp
+void foo() {}
+"#,
+        "bar",
+    ));
+}
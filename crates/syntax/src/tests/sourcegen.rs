@@ -44,7 +44,8 @@ fn generate_node_kinds() {
         #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
         #[repr(u16)]
         pub enum TSKind {
-            #(#entries),*
+            #(#entries,)*
+            ERROR = 65535,
         }
 
         impl From<tree_sitter::Node<'_>> for TSKind {
@@ -187,3 +187,48 @@ fn intersect_3() {
         })
     );
 }
+
+/// Build the Tree-sitter [InputEdit](tree_sitter::InputEdit) which replaces the bytes
+/// `start..old_end` of `old_text` to get `new_text`, where the replacement ends at `new_end`.
+///
+/// # Arguments
+///
+/// * `old_text` - Text before the edit.
+/// * `new_text` - Text after the edit.
+/// * `start` - Byte offset of the start of the edit, in both texts.
+/// * `old_end` - Byte offset of the end of the replaced text, in `old_text`.
+/// * `new_end` - Byte offset of the end of the replacement, in `new_text`.
+pub fn input_edit(
+    old_text: &str,
+    new_text: &str,
+    start: usize,
+    old_end: usize,
+    new_end: usize,
+) -> tree_sitter::InputEdit {
+    tree_sitter::InputEdit {
+        start_byte: start,
+        old_end_byte: old_end,
+        new_end_byte: new_end,
+        start_position: byte_to_point(old_text, start),
+        old_end_position: byte_to_point(old_text, old_end),
+        new_end_position: byte_to_point(new_text, new_end),
+    }
+}
+
+/// Convert a byte offset in a text to a Tree-sitter [Point](tree_sitter::Point).
+///
+/// # Arguments
+///
+/// * `text` - Text the offset is in.
+/// * `offset` - Byte offset to convert.
+pub fn byte_to_point(text: &str, offset: usize) -> tree_sitter::Point {
+    let before = &text.as_bytes()[..offset];
+    let line_start = before
+        .iter()
+        .rposition(|&byte| byte == b'\n')
+        .map_or(0, |idx| idx + 1);
+    tree_sitter::Point {
+        row: before.iter().filter(|&&byte| byte == b'\n').count(),
+        column: offset - line_start,
+    }
+}